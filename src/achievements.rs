@@ -0,0 +1,153 @@
+use std::collections::BTreeSet;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    daily::DailyBests, fever::Fever, score::Score, screenshot::spawn_toast, stats::Stats, storage,
+};
+
+pub struct Achievement {
+    pub id: &'static str,
+    pub name: &'static str,
+    /// What it takes to unlock, shown wherever something is gated behind it.
+    pub description: &'static str,
+}
+
+pub const ACHIEVEMENTS: [Achievement; 7] = [
+    Achievement {
+        id: "score-10",
+        name: "Getting the hang of it",
+        description: "Score 10 in one run",
+    },
+    Achievement {
+        id: "score-50",
+        name: "Golden touch",
+        description: "Score 50 in one run",
+    },
+    Achievement {
+        id: "score-100",
+        name: "Over the rainbow",
+        description: "Score 100 in one run",
+    },
+    Achievement {
+        id: "fever",
+        name: "Feeling hot",
+        description: "Start a fever",
+    },
+    Achievement {
+        id: "games-25",
+        name: "Regular",
+        description: "Play 25 games",
+    },
+    Achievement {
+        id: "daily",
+        name: "Daily flyer",
+        description: "Finish a daily challenge",
+    },
+    Achievement {
+        id: "perfect-25",
+        name: "Threading the needle",
+        description: "Make 25 perfect passes",
+    },
+];
+
+pub fn achievement(id: &str) -> Option<&'static Achievement> {
+    ACHIEVEMENTS.iter().find(|achievement| achievement.id == id)
+}
+
+/// Unlocked achievements, persisted as RON in the platform's data directory.
+#[derive(Resource, Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct Achievements {
+    pub unlocked: BTreeSet<String>,
+}
+
+impl storage::SaveFile for Achievements {
+    const VERSION: u32 = 1;
+}
+
+impl Achievements {
+    const FILE: &'static str = "achievements.ron";
+
+    pub fn load() -> Self {
+        storage::data_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = storage::data_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+
+    pub fn is_unlocked(&self, id: &str) -> bool {
+        self.unlocked.contains(id)
+    }
+}
+
+/// Sent the moment an achievement is unlocked for the first time.
+pub struct AchievementUnlocked(pub &'static Achievement);
+
+/// Checks every condition and unlocks whatever has newly been met. Cheap
+/// enough to run every frame, and only touches `Achievements` when something
+/// changes so screens can redraw on change.
+fn check_achievements_system(
+    score: Res<Score>,
+    stats: Res<Stats>,
+    daily: Res<DailyBests>,
+    fever: Option<Res<Fever>>,
+    mut achievements: ResMut<Achievements>,
+    mut unlocked: EventWriter<AchievementUnlocked>,
+) {
+    let met = |id: &str| match id {
+        "score-10" => score.0 >= 10,
+        "score-50" => score.0 >= 50,
+        "score-100" => score.0 >= 100,
+        "fever" => fever.is_some(),
+        "games-25" => stats.games_played >= 25,
+        "daily" => !daily.bests.is_empty(),
+        "perfect-25" => stats.perfect_passes >= 25,
+        _ => false,
+    };
+
+    let newly_met = ACHIEVEMENTS
+        .iter()
+        .filter(|achievement| !achievements.is_unlocked(achievement.id) && met(achievement.id))
+        .collect::<Vec<_>>();
+    if newly_met.is_empty() {
+        return;
+    }
+
+    for achievement in newly_met {
+        achievements.unlocked.insert(achievement.id.into());
+        unlocked.send(AchievementUnlocked(achievement));
+    }
+    achievements.save();
+}
+
+fn announce_achievements_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut unlocked: EventReader<AchievementUnlocked>,
+) {
+    for AchievementUnlocked(achievement) in unlocked.iter() {
+        spawn_toast(
+            &mut commands,
+            &asset_server,
+            format!("Achievement unlocked: {}", achievement.name),
+        );
+    }
+}
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Achievements::load())
+            .add_event::<AchievementUnlocked>()
+            .add_system(check_achievements_system)
+            .add_system(announce_achievements_system.after(check_achievements_system));
+    }
+}
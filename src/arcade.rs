@@ -0,0 +1,316 @@
+use bevy::{prelude::*, sprite::collide_aabb::collide};
+use rand::Rng;
+
+use crate::{
+    count_collected,
+    difficulty::Difficulty,
+    mode::{endless_run, in_mode, GameMode},
+    pipe_bounds,
+    pipe_themes::PipeTheme,
+    pool::PipePool,
+    score::{ScorePipe, ScoredEvent},
+    tween::{Ease, SpriteColor, Tween},
+    DespawnWhenOffscreen, GameRng, GameState, GameplaySet, Pipe, Player, PowerUp,
+    PowerUpCollectedEvent, RunScoped, Velocity, WorldDirection, FONT, PIPE_WIDTH,
+};
+
+const START_AMMO: u32 = 3;
+const MAX_AMMO: u32 = 5;
+const PROJECTILE_SPEED: f32 = 12.;
+const PROJECTILE_SIZE: Vec2 = Vec2::new(14., 6.);
+/// Chance of an ammo pickup spawning in the gap of a new pipe pair.
+const AMMO_CHANCE: f64 = 1. / 5.;
+const AMMO_SIZE: Vec2 = Vec2::new(16., 16.);
+const AMMO_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+const DEBRIS_PIECES: usize = 10;
+const DEBRIS_SECS: f32 = 0.6;
+const DEBRIS_COLOR: Color = Color::rgb(0.45, 0.75, 0.25);
+
+/// Shots left in a [`GameMode::Arcade`] run. Only present during such runs.
+#[derive(Resource, Deref, DerefMut)]
+pub struct Ammo(pub u32);
+
+#[derive(Component)]
+struct Projectile;
+
+#[derive(Component)]
+struct AmmoPickup;
+
+#[derive(Component)]
+struct Debris;
+
+#[derive(Component)]
+struct AmmoText;
+
+fn start_arcade_system(mut commands: Commands, ammo: Option<Res<Ammo>>) {
+    if ammo.is_none() {
+        commands.insert_resource(Ammo(START_AMMO));
+    }
+}
+
+fn stop_arcade_system(mut commands: Commands) {
+    commands.remove_resource::<Ammo>();
+}
+
+fn fire_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    direction: Res<WorldDirection>,
+    mut ammo: ResMut<Ammo>,
+    player: Query<&Transform, With<Player>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F) || ammo.0 == 0 {
+        return;
+    }
+
+    ammo.0 -= 1;
+    let player = player.single();
+    commands.spawn((
+        Projectile,
+        RunScoped,
+        Velocity {
+            x: PROJECTILE_SPEED * direction.sign(),
+            y: 0.,
+        },
+        DespawnWhenOffscreen {
+            margin: PROJECTILE_SIZE.x,
+        },
+        SpriteBundle {
+            sprite: Sprite {
+                color: Color::WHITE,
+                custom_size: Some(PROJECTILE_SIZE),
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(
+                player.translation.x + 25. * direction.sign(),
+                player.translation.y,
+                5.,
+            ),
+            ..Default::default()
+        },
+    ));
+}
+
+fn spawn_debris(
+    commands: &mut Commands,
+    rng: &mut impl Rng,
+    direction: WorldDirection,
+    center: Vec3,
+) {
+    for _ in 0..DEBRIS_PIECES {
+        let size = rng.gen_range(6.0..14.0);
+        commands.spawn((
+            Debris,
+            Tween::new(
+                SpriteColor {
+                    start: DEBRIS_COLOR,
+                    end: DEBRIS_COLOR.with_a(0.),
+                },
+                DEBRIS_SECS,
+                Ease::Linear,
+            )
+            .despawning(),
+            RunScoped,
+            Velocity {
+                x: rng.gen_range(-3.0..3.0) + direction.scroll_velocity().x,
+                y: rng.gen_range(-2.0..5.0),
+            },
+            DespawnWhenOffscreen { margin: size },
+            SpriteBundle {
+                sprite: Sprite {
+                    color: DEBRIS_COLOR,
+                    custom_size: Some(Vec2::splat(size)),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(center.truncate().extend(6.)),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+/// Breaks the first pipe segment each projectile touches. Breaking the one that
+/// carries the point for a pair awards it right away.
+#[allow(clippy::too_many_arguments)]
+fn projectile_hit_system(
+    mut commands: Commands,
+    mut passed: EventWriter<ScoredEvent>,
+    mut pool: ResMut<PipePool>,
+    mut rng: ResMut<GameRng>,
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    projectiles: Query<(Entity, &Transform), With<Projectile>>,
+    pipes: Query<(Entity, &Transform, Option<&ScorePipe>), With<Pipe>>,
+) {
+    for (projectile, projectile_transform) in projectiles.iter() {
+        let hit = pipes.iter().find(|(_, pipe, _)| {
+            let (center, size) = pipe_bounds(pipe, theme.size);
+            collide(
+                projectile_transform.translation,
+                PROJECTILE_SIZE,
+                center,
+                size,
+            )
+            .is_some()
+        });
+
+        if let Some((pipe, _, score_pipe)) = hit {
+            commands.entity(projectile).despawn();
+            pool.release(&mut commands, pipe, &theme);
+            spawn_debris(
+                &mut commands,
+                &mut rng.0,
+                *direction,
+                projectile_transform.translation,
+            );
+
+            if score_pipe.is_some() {
+                passed.send(ScoredEvent);
+            }
+        }
+    }
+}
+
+fn debris_system(time: Res<Time>, mut debris: Query<&mut Velocity, With<Debris>>) {
+    for mut velocity in debris.iter_mut() {
+        velocity.y -= 9.8 * time.delta_seconds();
+    }
+}
+
+fn spawn_ammo_pickups_system(
+    mut commands: Commands,
+    difficulty: Res<Difficulty>,
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    mut rng: ResMut<GameRng>,
+    new_pipes: Query<&Transform, Added<ScorePipe>>,
+) {
+    for pipe in new_pipes.iter() {
+        if !rng.gen_bool(AMMO_CHANCE) {
+            continue;
+        }
+
+        commands.spawn((
+            AmmoPickup,
+            RunScoped,
+            direction.scroll_velocity(),
+            DespawnWhenOffscreen { margin: PIPE_WIDTH },
+            SpriteBundle {
+                sprite: Sprite {
+                    color: AMMO_COLOR,
+                    custom_size: Some(AMMO_SIZE),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(
+                    pipe.translation.x + theme.width() / 2.,
+                    pipe.translation.y + difficulty.pipe_gap / 2.,
+                    5.,
+                )
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::FRAC_PI_4)),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn collect_ammo_pickups_system(
+    mut commands: Commands,
+    mut collected: EventWriter<PowerUpCollectedEvent>,
+    player: Query<&Transform, With<Player>>,
+    pickups: Query<(Entity, &Transform), With<AmmoPickup>>,
+) {
+    let player = player.single();
+
+    for (entity, transform) in pickups.iter() {
+        let hit = collide(
+            player.translation,
+            Vec2::new(45., 45.),
+            transform.translation,
+            AMMO_SIZE,
+        )
+        .is_some();
+
+        if hit {
+            collected.send(PowerUpCollectedEvent(PowerUp::Ammo));
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn add_collected_ammo_system(
+    mut collected: EventReader<PowerUpCollectedEvent>,
+    mut ammo: ResMut<Ammo>,
+) {
+    let pickups = count_collected(&mut collected, PowerUp::Ammo);
+    if pickups > 0 {
+        ammo.0 = (ammo.0 + pickups).min(MAX_AMMO);
+    }
+}
+
+fn spawn_ammo_text_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        AmmoText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 22.,
+                color: AMMO_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.),
+                left: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+fn update_ammo_text_system(ammo: Option<Res<Ammo>>, mut query: Query<&mut Text, With<AmmoText>>) {
+    let value = ammo.map_or(String::new(), |ammo| {
+        "●".repeat(ammo.0 as usize) + &"○".repeat(MAX_AMMO.saturating_sub(ammo.0) as usize)
+    });
+
+    for mut text in query.iter_mut() {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+pub struct ArcadePlugin;
+
+impl Plugin for ArcadePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_ammo_text_system)
+            .add_system(
+                start_arcade_system
+                    .run_if(in_mode(GameMode::Arcade))
+                    .run_if(endless_run)
+                    .in_schedule(OnEnter(GameState::InGame)),
+            )
+            .add_system(stop_arcade_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_systems(
+                (
+                    fire_system,
+                    spawn_ammo_pickups_system,
+                    collect_ammo_pickups_system.in_set(GameplaySet::Collision),
+                    add_collected_ammo_system.in_set(GameplaySet::Scoring),
+                )
+                    .distributive_run_if(resource_exists::<Ammo>())
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_systems(
+                (
+                    projectile_hit_system.in_set(GameplaySet::Collision),
+                    debris_system,
+                )
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(update_ammo_text_system);
+    }
+}
@@ -0,0 +1,375 @@
+use bevy::{audio::AddAudioSource, ecs::system::SystemParam, prelude::*, utils::HashMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    best_score::NewBestScore,
+    score::ScoredEvent,
+    settings::Settings,
+    synth::{Note, Tone, Wave},
+    touch::SafeArea,
+    Crashed, FlapEvent, GameState, FONT,
+};
+
+/// Group of sounds sharing one volume setting.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Bus {
+    Music,
+    Sfx,
+    Ui,
+}
+
+impl Bus {
+    pub const ALL: [Bus; 3] = [Bus::Music, Bus::Sfx, Bus::Ui];
+}
+
+/// Volume of every bus from 0 to 1, persisted with the settings.
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+#[serde(default)]
+pub struct BusVolumes {
+    pub music: f32,
+    pub sfx: f32,
+    pub ui: f32,
+}
+
+impl Default for BusVolumes {
+    fn default() -> Self {
+        Self {
+            music: 0.6,
+            sfx: 1.,
+            ui: 1.,
+        }
+    }
+}
+
+impl BusVolumes {
+    pub fn get(&self, bus: Bus) -> f32 {
+        match bus {
+            Bus::Music => self.music,
+            Bus::Sfx => self.sfx,
+            Bus::Ui => self.ui,
+        }
+    }
+
+    /// Volume to play a sound on `bus` at, taking muting into account.
+    fn effective(&self, bus: Bus, muted: bool) -> f32 {
+        if muted {
+            0.
+        } else {
+            self.get(bus)
+        }
+    }
+
+    pub fn get_mut(&mut self, bus: Bus) -> &mut f32 {
+        match bus {
+            Bus::Music => &mut self.music,
+            Bus::Sfx => &mut self.sfx,
+            Bus::Ui => &mut self.ui,
+        }
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Sound {
+    Flap,
+    Score,
+    Crash,
+    Select,
+    /// A pipe rushing past, played positionally.
+    Whoosh,
+    /// Beating the best score.
+    Fanfare,
+    /// Layers of the music, which all loop over the same two seconds.
+    MusicBase,
+    MusicDrums,
+    MusicLead,
+}
+
+impl Sound {
+    const ALL: [Sound; 9] = [
+        Sound::Flap,
+        Sound::Score,
+        Sound::Crash,
+        Sound::Select,
+        Sound::Whoosh,
+        Sound::Fanfare,
+        Sound::MusicBase,
+        Sound::MusicDrums,
+        Sound::MusicLead,
+    ];
+
+    pub fn bus(self) -> Bus {
+        match self {
+            Sound::Flap | Sound::Score | Sound::Crash | Sound::Whoosh | Sound::Fanfare => Bus::Sfx,
+            Sound::Select => Bus::Ui,
+            Sound::MusicBase | Sound::MusicDrums | Sound::MusicLead => Bus::Music,
+        }
+    }
+
+    fn notes(self) -> Vec<Note> {
+        match self {
+            Sound::Flap => vec![Note::slide(Wave::Square, 300., 600., 0.08, 0.15)],
+            Sound::Score => vec![
+                Note::new(Wave::Square, 988., 0.06, 0.15),
+                Note::new(Wave::Square, 1319., 0.12, 0.15),
+            ],
+            Sound::Crash => vec![
+                Note::new(Wave::Noise, 4000., 0.12, 0.3),
+                Note::slide(Wave::Square, 200., 60., 0.25, 0.2),
+            ],
+            Sound::Select => vec![Note::new(Wave::Triangle, 660., 0.05, 0.2)],
+            Sound::Whoosh => vec![Note::slide(Wave::Noise, 1500., 6000., 0.35, 0.08)],
+            Sound::Fanfare => vec![
+                Note::new(Wave::Square, 523., 0.08, 0.15),
+                Note::new(Wave::Square, 659., 0.08, 0.15),
+                Note::new(Wave::Square, 784., 0.08, 0.15),
+                Note::new(Wave::Square, 1047., 0.3, 0.15),
+            ],
+            Sound::MusicBase => vec![
+                Note::new(Wave::Triangle, 262., 0.25, 0.15),
+                Note::new(Wave::Triangle, 330., 0.25, 0.15),
+                Note::new(Wave::Triangle, 392., 0.25, 0.15),
+                Note::new(Wave::Triangle, 330., 0.25, 0.15),
+                Note::new(Wave::Triangle, 220., 0.25, 0.15),
+                Note::new(Wave::Triangle, 262., 0.25, 0.15),
+                Note::new(Wave::Triangle, 330., 0.25, 0.15),
+                Note::rest(0.25),
+            ],
+            Sound::MusicDrums => {
+                let kick = [
+                    Note::slide(Wave::Sine, 150., 40., 0.12, 0.5),
+                    Note::rest(0.13),
+                ];
+                let hat = [Note::new(Wave::Noise, 8000., 0.04, 0.12), Note::rest(0.21)];
+                [kick, hat].repeat(4).concat()
+            }
+            Sound::MusicLead => vec![
+                Note::new(Wave::Square, 659., 0.25, 0.07),
+                Note::new(Wave::Square, 784., 0.25, 0.07),
+                Note::new(Wave::Square, 880., 0.25, 0.07),
+                Note::new(Wave::Square, 784., 0.25, 0.07),
+                Note::new(Wave::Square, 659., 0.25, 0.07),
+                Note::new(Wave::Square, 587., 0.25, 0.07),
+                Note::new(Wave::Square, 523., 0.25, 0.07),
+                Note::rest(0.25),
+            ],
+        }
+    }
+}
+
+/// Every sound, synthesised once at startup.
+#[derive(Resource)]
+struct Sounds(HashMap<Sound, Handle<Tone>>);
+
+impl FromWorld for Sounds {
+    fn from_world(world: &mut World) -> Self {
+        let mut tones = world.resource_mut::<Assets<Tone>>();
+        Self(
+            Sound::ALL
+                .iter()
+                .map(|sound| (*sound, tones.add(Tone::new(&sound.notes()))))
+                .collect(),
+        )
+    }
+}
+
+struct Loop {
+    sound: Sound,
+    sink: Handle<AudioSink>,
+    /// Volume relative to the bus.
+    gain: f32,
+}
+
+/// Looping sounds that are playing, so their volume can follow the settings.
+#[derive(Resource, Default)]
+struct Loops(Vec<Loop>);
+
+/// Plays sounds at the volume of their bus. All playback goes through here.
+#[derive(SystemParam)]
+pub struct AudioManager<'w> {
+    audio: Res<'w, Audio<Tone>>,
+    sounds: Res<'w, Sounds>,
+    settings: Res<'w, Settings>,
+    sinks: Res<'w, Assets<AudioSink>>,
+    spatial_sinks: Res<'w, Assets<SpatialAudioSink>>,
+    loops: ResMut<'w, Loops>,
+}
+
+impl AudioManager<'_> {
+    fn volume(&self, sound: Sound) -> f32 {
+        self.settings
+            .volumes
+            .effective(sound.bus(), self.settings.muted)
+    }
+
+    pub fn play(&self, sound: Sound) {
+        self.audio.play_with_settings(
+            self.sounds.0[&sound].clone(),
+            PlaybackSettings::ONCE.with_volume(self.volume(sound)),
+        );
+    }
+
+    /// Plays a sound coming from `emitter` as heard by ears either side of
+    /// `listener`, one unit apart. The emitter can be moved while it plays.
+    pub fn play_spatial(
+        &self,
+        sound: Sound,
+        listener: Vec3,
+        emitter: Vec3,
+    ) -> Handle<SpatialAudioSink> {
+        let sink = self.audio.play_spatial_with_settings(
+            self.sounds.0[&sound].clone(),
+            PlaybackSettings::ONCE.with_volume(self.volume(sound)),
+            Transform::from_translation(listener),
+            1.,
+            emitter,
+        );
+        self.spatial_sinks.get_handle(sink)
+    }
+
+    /// Plays a sound over and over until the game quits, at `gain` times the
+    /// volume of its bus. Loops started in the same frame stay in sync.
+    pub fn play_looped(&mut self, sound: Sound, gain: f32) {
+        let sink = self.audio.play_with_settings(
+            self.sounds.0[&sound].clone(),
+            PlaybackSettings::LOOP.with_volume(self.volume(sound) * gain),
+        );
+        let sink = self.sinks.get_handle(sink);
+        self.loops.0.push(Loop { sound, sink, gain });
+    }
+
+    /// Gain of a looping sound, zero if it isn't playing.
+    pub fn loop_gain(&self, sound: Sound) -> f32 {
+        self.loops
+            .0
+            .iter()
+            .find(|playing| playing.sound == sound)
+            .map_or(0., |playing| playing.gain)
+    }
+
+    pub fn set_loop_gain(&mut self, sound: Sound, gain: f32) {
+        for playing in self.loops.0.iter_mut() {
+            if playing.sound == sound {
+                playing.gain = gain;
+            }
+        }
+    }
+}
+
+fn apply_bus_volumes_system(
+    settings: Res<Settings>,
+    loops: Res<Loops>,
+    sinks: Res<Assets<AudioSink>>,
+) {
+    if !settings.is_changed() && !loops.is_changed() {
+        return;
+    }
+
+    for playing in &loops.0 {
+        if let Some(sink) = sinks.get(&playing.sink) {
+            let volume = settings
+                .volumes
+                .effective(playing.sound.bus(), settings.muted);
+            sink.set_volume(volume * playing.gain);
+        }
+    }
+}
+
+fn sound_effects_system(
+    audio: AudioManager,
+    mut flapped: EventReader<FlapEvent>,
+    mut passed: EventReader<ScoredEvent>,
+    mut crashed: EventReader<Crashed>,
+    mut new_best: EventReader<NewBestScore>,
+) {
+    if crashed.iter().count() > 0 {
+        audio.play(Sound::Crash);
+    }
+    if new_best.iter().count() > 0 {
+        audio.play(Sound::Fanfare);
+    }
+    if passed.iter().count() > 0 {
+        audio.play(Sound::Score);
+    }
+    if flapped.iter().count() > 0 {
+        audio.play(Sound::Flap);
+    }
+}
+
+/// Clicks when moving between screens outside of a run.
+fn menu_sounds_system(audio: AudioManager, state: Res<State<GameState>>) {
+    let in_run = matches!(state.0, GameState::InGame | GameState::GameOver);
+    if state.is_changed() && !state.is_added() && !in_run {
+        audio.play(Sound::Select);
+    }
+}
+
+#[derive(Component)]
+struct MutedIcon;
+
+fn toggle_mute_system(keyboard_input: Res<Input<KeyCode>>, mut settings: ResMut<Settings>) {
+    if keyboard_input.just_pressed(KeyCode::M) {
+        settings.muted = !settings.muted;
+        settings.save();
+    }
+}
+
+fn spawn_muted_icon_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    safe_area: Res<SafeArea>,
+) {
+    commands.spawn((
+        MutedIcon,
+        TextBundle::from_section(
+            "♪ off",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 16.,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                bottom: Val::Px(safe_area.bottom + 10.),
+                right: Val::Px(safe_area.right + 10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+fn update_muted_icon_system(
+    settings: Res<Settings>,
+    mut query: Query<&mut Visibility, With<MutedIcon>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    for mut visibility in query.iter_mut() {
+        *visibility = if settings.muted {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+pub struct AudioManagerPlugin;
+
+impl Plugin for AudioManagerPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_audio_source::<Tone>()
+            .init_resource::<Sounds>()
+            .init_resource::<Loops>()
+            .add_startup_system(spawn_muted_icon_system)
+            // M would otherwise end up muting while typing a profile name.
+            .add_system(toggle_mute_system.run_if(not(in_state(GameState::Profiles))))
+            .add_system(update_muted_icon_system)
+            .add_system(apply_bus_volumes_system)
+            .add_system(sound_effects_system)
+            .add_system(menu_sounds_system);
+    }
+}
@@ -0,0 +1,120 @@
+use bevy::{app::AppExit, prelude::*};
+use flappy_core::{
+    pipe_themes::PipeTheme, pool::PipePool, spawn_pipe_pair, InfiniteScrolling, Pipe,
+    WorldDirection, MIN_PIPE_OFFSET, PIPE_GAP, PIPE_SPAWN_X, WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+use rand::Rng;
+
+/// Pipes kept on screen, respawned as fast as they scroll off.
+const BENCH_PIPES: usize = 2000;
+/// Scrolling particles that wrap around forever.
+const BENCH_PARTICLES: usize = 5000;
+
+/// Frames skipped while the textures and pools settle.
+const WARMUP_FRAMES: u32 = 60;
+/// Frames timed before printing the results and quitting.
+const MEASURED_FRAMES: usize = 1000;
+
+/// Frame times of the benchmark so far, in seconds.
+#[derive(Resource, Default)]
+struct Bench {
+    warmup: u32,
+    frame_times: Vec<f32>,
+}
+
+/// The frame time that `percent` percent of frames were at or under.
+fn percentile(sorted: &[f32], percent: f32) -> f32 {
+    let index = ((sorted.len() - 1) as f32 * percent / 100.).round() as usize;
+    sorted[index]
+}
+
+fn spawn_particles_system(mut commands: Commands) {
+    let mut rng = rand::thread_rng();
+    for _ in 0..BENCH_PARTICLES {
+        let x = rng.gen_range(-WINDOW_WIDTH / 2.0..WINDOW_WIDTH / 2.);
+        let y = rng.gen_range(-WINDOW_HEIGHT / 2.0..WINDOW_HEIGHT / 2.);
+        commands.spawn((
+            InfiniteScrolling {
+                segment_width: WINDOW_WIDTH / 2.,
+                segments: 2,
+                speed: -rng.gen_range(1.0..6.),
+            },
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::hsl(rng.gen_range(0.0..360.), 0.8, 0.6),
+                    custom_size: Some(Vec2::splat(4.)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(x, y, 5.),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+/// Tops the pipes back up, spread across the screen so they're all moving at
+/// once.
+fn spawn_pipes_system(
+    mut commands: Commands,
+    theme: Res<PipeTheme>,
+    mut pool: ResMut<PipePool>,
+    pipes: Query<(), With<Pipe>>,
+) {
+    let mut rng = rand::thread_rng();
+    for _ in (pipes.iter().count()..BENCH_PIPES).step_by(2) {
+        let x = rng.gen_range(-WINDOW_WIDTH / 2.0..PIPE_SPAWN_X);
+        let gap_bottom = rng.gen_range(
+            (-WINDOW_HEIGHT / 2. + MIN_PIPE_OFFSET)
+                ..(WINDOW_HEIGHT / 2. - MIN_PIPE_OFFSET - PIPE_GAP),
+        );
+        spawn_pipe_pair(
+            &mut commands,
+            &mut pool,
+            &theme,
+            WorldDirection::default(),
+            x,
+            gap_bottom,
+            PIPE_GAP,
+        );
+    }
+}
+
+fn record_frame_system(time: Res<Time>, mut bench: ResMut<Bench>, mut exit: EventWriter<AppExit>) {
+    if bench.warmup < WARMUP_FRAMES {
+        bench.warmup += 1;
+        return;
+    }
+
+    bench.frame_times.push(time.delta_seconds());
+    if bench.frame_times.len() < MEASURED_FRAMES {
+        return;
+    }
+
+    let mut sorted = bench.frame_times.clone();
+    sorted.sort_by(f32::total_cmp);
+    println!(
+        "{} frames, {BENCH_PIPES} pipes, {BENCH_PARTICLES} particles",
+        sorted.len()
+    );
+    for percent in [50., 90., 99., 100.] {
+        println!(
+            "p{percent:<3} {:7.2} ms",
+            percentile(&sorted, percent) * 1000.
+        );
+    }
+    exit.send(AppExit);
+}
+
+/// Fills the menu with pipes and particles and prints frame time percentiles
+/// after a fixed number of frames, for catching slowdowns in the movement and
+/// scrolling systems.
+pub struct BenchPlugin;
+
+impl Plugin for BenchPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Bench>()
+            .add_startup_system(spawn_particles_system)
+            .add_system(spawn_pipes_system)
+            .add_system(record_frame_system);
+    }
+}
@@ -0,0 +1,172 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+    bonus::BonusStage,
+    score::Score,
+    settings::Settings,
+    stats::Stats,
+    touch::SafeArea,
+    tween::{Ease, SpriteColor, Translation, Tween},
+    GameState, GameplaySet, Player, RunScoped, FONT,
+};
+
+const BEATEN_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+const BURST_PARTICLES: usize = 16;
+const BURST_RADIUS: f32 = 70.;
+const BURST_SECS: f32 = 0.7;
+const PARTICLE_SIZE: f32 = 6.;
+
+/// Sent once a run, the moment its score goes past the previous best.
+pub struct NewBestScore;
+
+/// Small line under the score with the best score and how far ahead or behind
+/// of it the run is.
+#[derive(Component)]
+struct BestText;
+
+fn spawn_best_text_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    safe_area: Res<SafeArea>,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::width(Val::Percent(100.)),
+                position_type: PositionType::Absolute,
+                // Just below the score.
+                position: UiRect::top(Val::Px(safe_area.top + 88.)),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                BestText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 18.,
+                        color: Color::WHITE,
+                    },
+                ),
+            ));
+        });
+}
+
+/// The best score only changes once a run is over, so during one it's still
+/// the best from before it.
+fn update_best_text_system(
+    state: Res<State<GameState>>,
+    score: Res<Score>,
+    stats: Res<Stats>,
+    mut celebrated: Local<bool>,
+    mut new_best: EventWriter<NewBestScore>,
+    mut query: Query<&mut Text, With<BestText>>,
+) {
+    if !score.is_changed() && !stats.is_changed() && !state.is_changed() {
+        return;
+    }
+
+    if score.0 == 0 {
+        *celebrated = false;
+    }
+    // A first run has nothing to beat.
+    let beaten = stats.best_score > 0 && score.0 > stats.best_score;
+    if beaten && !*celebrated {
+        *celebrated = true;
+        new_best.send(NewBestScore);
+    }
+
+    let delta = i64::from(score.0) - i64::from(stats.best_score);
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("best: {} ({delta:+})", stats.best_score);
+        text.sections[0].style.color = if beaten { BEATEN_COLOR } else { Color::WHITE };
+    }
+}
+
+/// Shows the line along with the score, and leaves it as it was at the end of
+/// the run on the game over screen. It makes way for the banner of a bonus
+/// stage, which sits in the same spot.
+fn show_best_text_system(
+    state: Res<State<GameState>>,
+    bonus: Option<Res<BonusStage>>,
+    mut query: Query<&mut Visibility, With<BestText>>,
+) {
+    let visible = matches!(state.0, GameState::InGame | GameState::GameOver) && bonus.is_none();
+    let target = if visible {
+        Visibility::Inherited
+    } else {
+        Visibility::Hidden
+    };
+    for mut visibility in query.iter_mut() {
+        if *visibility != target {
+            *visibility = target;
+        }
+    }
+}
+
+/// Scatters a ring of gold sparks from the bird. Low-spec mode goes without.
+fn burst_system(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    mut new_best: EventReader<NewBestScore>,
+    player: Query<&Transform, With<Player>>,
+) {
+    if new_best.iter().count() == 0 || settings.low_spec {
+        return;
+    }
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+
+    let start = player.translation.truncate().extend(15.);
+    for index in 0..BURST_PARTICLES {
+        let angle = index as f32 / BURST_PARTICLES as f32 * TAU;
+        let end = start + Vec2::from_angle(angle).extend(0.) * BURST_RADIUS;
+        commands.spawn((
+            RunScoped,
+            Tween::new(Translation { start, end }, BURST_SECS, Ease::QuadOut),
+            Tween::new(
+                SpriteColor {
+                    start: BEATEN_COLOR,
+                    end: BEATEN_COLOR.with_a(0.),
+                },
+                BURST_SECS,
+                Ease::QuadIn,
+            )
+            .despawning(),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: BEATEN_COLOR,
+                    custom_size: Some(Vec2::splat(PARTICLE_SIZE)),
+                    ..Default::default()
+                },
+                transform: Transform::from_translation(start),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+/// A line under the score tracking the best score, which turns gold with a
+/// fanfare and a burst of sparks once a run beats it.
+pub struct BestScorePlugin;
+
+impl Plugin for BestScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<NewBestScore>()
+            .add_startup_system(spawn_best_text_system)
+            .add_systems(
+                (update_best_text_system, burst_system)
+                    .chain()
+                    .in_set(GameplaySet::Presentation)
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(show_best_text_system);
+    }
+}
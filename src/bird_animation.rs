@@ -0,0 +1,159 @@
+use std::f32::consts::TAU;
+
+use bevy::prelude::*;
+
+use crate::{
+    demo::Demo, low_spec, settings::Settings, FlapEvent, GameState, GameplaySet, Player, Velocity,
+};
+
+/// Falling faster than this, in pixels per frame, the bird stops flapping and
+/// glides.
+const GLIDE_SPEED: f32 = -5.;
+/// How long the wings beat faster after a flap.
+const FLAP_BURST_SECS: f32 = 0.3;
+const BOB_HEIGHT: f32 = 6.;
+/// Bobs per second while idling on the menu.
+const BOB_RATE: f32 = 0.8;
+
+/// What the bird is doing, as far as its sprite is concerned.
+#[derive(Clone, Copy, PartialEq, Eq, Default, Debug)]
+enum BirdAnimation {
+    /// Bobbing in place on the menu, beating its wings lazily.
+    #[default]
+    Idle,
+    Flying,
+    /// Wings beating hard just after a flap.
+    FlapBurst,
+    /// Wings held up while falling fast.
+    Gliding,
+    /// Wings hanging limp and twitching after a crash.
+    Dizzy,
+}
+
+impl BirdAnimation {
+    /// Frames of `bird.png` played in a loop, and how long each one shows.
+    /// The frames go wings up, level, down and level again.
+    fn frames(self) -> (&'static [usize], f32) {
+        match self {
+            BirdAnimation::Idle => (&[0, 1, 2, 3], 0.15),
+            BirdAnimation::Flying => (&[0, 1, 2, 3], 0.1),
+            BirdAnimation::FlapBurst => (&[0, 1, 2, 3], 0.05),
+            BirdAnimation::Gliding => (&[0], 1.),
+            BirdAnimation::Dizzy => (&[2, 2, 3, 2, 1, 2], 0.2),
+        }
+    }
+}
+
+/// Plays the bird's frames for whichever [`BirdAnimation`] it's in, switching
+/// between them as its velocity changes, it flaps and the run ends.
+#[derive(Component)]
+pub struct BirdAnimator {
+    state: BirdAnimation,
+    frame: usize,
+    timer: Timer,
+    /// Runs while the wings beat faster after a flap.
+    burst: Timer,
+}
+
+impl Default for BirdAnimator {
+    fn default() -> Self {
+        let mut burst = Timer::from_seconds(FLAP_BURST_SECS, TimerMode::Once);
+        burst.tick(burst.duration());
+        Self {
+            state: BirdAnimation::default(),
+            frame: 0,
+            timer: Timer::from_seconds(BirdAnimation::default().frames().1, TimerMode::Repeating),
+            burst,
+        }
+    }
+}
+
+impl BirdAnimator {
+    fn set_state(&mut self, state: BirdAnimation) {
+        if self.state != state {
+            self.state = state;
+            self.frame = 0;
+            self.timer = Timer::from_seconds(state.frames().1, TimerMode::Repeating);
+        }
+    }
+}
+
+fn flap_burst_system(mut flapped: EventReader<FlapEvent>, mut animators: Query<&mut BirdAnimator>) {
+    if flapped.iter().count() == 0 {
+        return;
+    }
+    for mut animator in animators.iter_mut() {
+        animator.burst.reset();
+    }
+}
+
+/// Picks the animation for the frame. The bot playing the demo behind the menu
+/// flies like a player would.
+fn pick_animation_system(
+    time: Res<Time>,
+    state: Res<State<GameState>>,
+    demo: Option<Res<Demo>>,
+    mut animators: Query<(&mut BirdAnimator, &Velocity)>,
+) {
+    let idling = state.0 == GameState::Menu && demo.is_none();
+
+    for (mut animator, velocity) in animators.iter_mut() {
+        animator.burst.tick(time.delta());
+        let next = if state.0 == GameState::GameOver {
+            BirdAnimation::Dizzy
+        } else if idling {
+            BirdAnimation::Idle
+        } else if !animator.burst.finished() {
+            BirdAnimation::FlapBurst
+        } else if velocity.y < GLIDE_SPEED {
+            BirdAnimation::Gliding
+        } else {
+            BirdAnimation::Flying
+        };
+        animator.set_state(next);
+    }
+}
+
+fn play_animation_system(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut animators: Query<(&mut BirdAnimator, &mut TextureAtlasSprite)>,
+) {
+    let delta = low_spec::animation_delta(&time, &settings);
+    for (mut animator, mut sprite) in animators.iter_mut() {
+        let (frames, _) = animator.state.frames();
+        if animator.timer.tick(delta).just_finished() {
+            animator.frame = (animator.frame + 1) % frames.len();
+        }
+        let index = frames[animator.frame % frames.len()];
+        if sprite.index != index {
+            sprite.index = index;
+        }
+    }
+}
+
+/// Bobs the bird up and down around where it waits on the menu.
+fn bob_system(time: Res<Time>, mut player: Query<(&mut Transform, &BirdAnimator), With<Player>>) {
+    for (mut transform, animator) in player.iter_mut() {
+        if animator.state == BirdAnimation::Idle {
+            transform.translation.y = (time.elapsed_seconds() * BOB_RATE * TAU).sin() * BOB_HEIGHT;
+        }
+    }
+}
+
+pub struct BirdAnimationPlugin;
+
+impl Plugin for BirdAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                flap_burst_system,
+                pick_animation_system,
+                play_animation_system,
+                bob_system,
+            )
+                .chain()
+                .in_set(GameplaySet::Presentation),
+        );
+    }
+}
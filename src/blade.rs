@@ -0,0 +1,125 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    difficulty::Difficulty,
+    hazard::Hazard,
+    mode::endless_run,
+    pipe_themes::PipeTheme,
+    score::{Score, ScorePipe},
+    DespawnWhenOffscreen, GameRng, GameState, RunScoped, WorldDirection, PIPE_WIDTH, WINDOW_WIDTH,
+};
+
+/// Blades only show up once the run has got going.
+const MIN_SCORE: u32 = 20;
+/// Chance of a blade spinning in the gap of a new pipe pair.
+const BLADE_CHANCE: f64 = 1. / 4.;
+/// Blade length as a fraction of the gap, leaving room to slip past it lying flat.
+const BLADE_LENGTH: f32 = 0.6;
+const BLADE_THICKNESS: f32 = 10.;
+/// Radians per second once spun up.
+const SPIN_SPEED: f32 = 1.5;
+/// How long a blade flashes and spins up after coming on screen before it's deadly.
+const TELEGRAPH_SECS: f32 = 1.;
+const FLASH_SECS: f32 = 0.15;
+const BLADE_COLOR: Color = Color::rgb(0.75, 0.78, 0.82);
+const WARNING_COLOR: Color = Color::rgb(1., 0.25, 0.2);
+
+#[derive(Component)]
+struct Blade {
+    size: Vec2,
+    /// Spin direction, so neighbouring blades don't all turn the same way.
+    direction: f32,
+    telegraph: Timer,
+}
+
+fn spawn_blades_system(
+    mut commands: Commands,
+    score: Res<Score>,
+    difficulty: Res<Difficulty>,
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    mut rng: ResMut<GameRng>,
+    new_pipes: Query<&Transform, Added<ScorePipe>>,
+) {
+    if score.0 < MIN_SCORE {
+        return;
+    }
+
+    for pipe in new_pipes.iter() {
+        if !rng.gen_bool(BLADE_CHANCE) {
+            continue;
+        }
+
+        let size = Vec2::new(difficulty.pipe_gap * BLADE_LENGTH, BLADE_THICKNESS);
+        commands.spawn((
+            Blade {
+                size,
+                direction: if rng.gen_bool(0.5) { 1. } else { -1. },
+                telegraph: Timer::from_seconds(TELEGRAPH_SECS, TimerMode::Once),
+            },
+            RunScoped,
+            direction.scroll_velocity(),
+            DespawnWhenOffscreen { margin: PIPE_WIDTH },
+            SpriteBundle {
+                sprite: Sprite {
+                    color: WARNING_COLOR.with_a(0.5),
+                    custom_size: Some(size),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(
+                    pipe.translation.x + theme.width() / 2.,
+                    pipe.translation.y + difficulty.pipe_gap / 2.,
+                    5.,
+                )
+                .with_rotation(Quat::from_rotation_z(
+                    rng.gen_range(0.0..std::f32::consts::PI),
+                )),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn spin_blades_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    direction: Res<WorldDirection>,
+    mut blades: Query<(Entity, &mut Blade, &mut Transform, &mut Sprite)>,
+) {
+    for (entity, mut blade, mut transform, mut sprite) in blades.iter_mut() {
+        let x = direction.mirror_x(transform.translation.x, 0.);
+        let on_screen = x - blade.size.x / 2. < WINDOW_WIDTH / 2.;
+        if on_screen && !blade.telegraph.finished() {
+            blade.telegraph.tick(time.delta());
+
+            if blade.telegraph.just_finished() {
+                sprite.color = BLADE_COLOR;
+                commands.entity(entity).insert(Hazard { size: blade.size });
+            } else {
+                let flash_on =
+                    ((blade.telegraph.elapsed_secs() / FLASH_SECS) as u32).is_multiple_of(2);
+                sprite.color = if flash_on {
+                    WARNING_COLOR
+                } else {
+                    WARNING_COLOR.with_a(0.5)
+                };
+            }
+        }
+
+        // Spins up over the telegraph so the first deadly turn isn't a surprise.
+        let spin = SPIN_SPEED * blade.telegraph.percent() * blade.direction;
+        transform.rotate_z(spin * time.delta_seconds());
+    }
+}
+
+pub struct BladePlugin;
+
+impl Plugin for BladePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (spawn_blades_system.run_if(endless_run), spin_blades_system)
+                .in_set(OnUpdate(GameState::InGame)),
+        );
+    }
+}
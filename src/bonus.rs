@@ -0,0 +1,204 @@
+use bevy::{prelude::*, sprite::collide_aabb::collide};
+
+use crate::{
+    count_collected,
+    mode::endless_run,
+    score::{Score, ScoredEvent},
+    GameState, GameplaySet, Player, PowerUp, PowerUpCollectedEvent, RunScoped, WorldDirection,
+    FONT, PIPE_SPAWN_X, PIPE_WIDTH, WINDOW_WIDTH,
+};
+
+/// A bonus stage starts every this many points.
+const BONUS_INTERVAL: u32 = 50;
+const STAR_COUNT: usize = 12;
+const STAR_SPACING: f32 = 60.;
+/// Height of the wave the trail of stars follows.
+const TRAIL_AMPLITUDE: f32 = 120.;
+/// Leaves room for the last pipes to scroll past before the first star.
+const TRAIL_LEAD_IN: f32 = PIPE_WIDTH + 150.;
+const STAR_SIZE: Vec2 = Vec2::new(30., 30.);
+const STAR_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+
+/// Present while a bonus stage is running. The endless spawner holds off on
+/// pipes until every star has been collected or scrolled away.
+#[derive(Resource)]
+pub struct BonusStage {
+    stars_left: usize,
+}
+
+/// Score at which the next bonus stage starts.
+#[derive(Resource)]
+struct NextBonus(u32);
+
+impl NextBonus {
+    fn after(score: u32) -> Self {
+        Self((score / BONUS_INTERVAL + 1) * BONUS_INTERVAL)
+    }
+}
+
+impl Default for NextBonus {
+    fn default() -> Self {
+        Self::after(0)
+    }
+}
+
+#[derive(Component)]
+struct Star;
+
+#[derive(Component)]
+struct BonusBanner;
+
+fn schedule_bonus_system(mut next_bonus: ResMut<NextBonus>, score: Res<Score>) {
+    *next_bonus = NextBonus::after(score.0);
+}
+
+/// The trail is a fixed wave rather than random, so a bonus stage never draws
+/// from the pipe RNG and the layout carries on exactly where it left off.
+fn start_bonus_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    score: Res<Score>,
+    direction: Res<WorldDirection>,
+    mut passed: EventReader<ScoredEvent>,
+    mut next_bonus: ResMut<NextBonus>,
+) {
+    if passed.iter().count() == 0 || score.0 < next_bonus.0 {
+        return;
+    }
+
+    *next_bonus = NextBonus::after(score.0);
+    commands.insert_resource(BonusStage {
+        stars_left: STAR_COUNT,
+    });
+
+    let font = asset_server.load(FONT);
+    for i in 0..STAR_COUNT {
+        let x = direction.mirror_x(PIPE_SPAWN_X + TRAIL_LEAD_IN + i as f32 * STAR_SPACING, 0.);
+        let y = (i as f32 * 0.6).sin() * TRAIL_AMPLITUDE;
+        commands.spawn((
+            Star,
+            RunScoped,
+            direction.scroll_velocity(),
+            Text2dBundle {
+                text: Text::from_section(
+                    "★",
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 36.,
+                        color: STAR_COLOR,
+                    },
+                ),
+                transform: Transform::from_xyz(x, y, 5.),
+                ..Default::default()
+            },
+        ));
+    }
+
+    commands.spawn((
+        BonusBanner,
+        TextBundle::from_section(
+            "BONUS!",
+            TextStyle {
+                font,
+                font_size: 28.,
+                color: STAR_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(90.),
+                left: Val::Px(WINDOW_WIDTH / 2. - 50.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+fn collect_stars_system(
+    mut commands: Commands,
+    mut collected: EventWriter<PowerUpCollectedEvent>,
+    mut bonus: ResMut<BonusStage>,
+    direction: Res<WorldDirection>,
+    player: Query<&Transform, With<Player>>,
+    stars: Query<(Entity, &Transform), With<Star>>,
+) {
+    let player = player.single();
+
+    for (entity, transform) in stars.iter() {
+        let hit = collide(
+            player.translation,
+            Vec2::new(45., 45.),
+            transform.translation,
+            STAR_SIZE,
+        )
+        .is_some();
+
+        if hit {
+            collected.send(PowerUpCollectedEvent(PowerUp::Star));
+        }
+        let missed =
+            direction.mirror_x(transform.translation.x, 0.) < -WINDOW_WIDTH / 2. - STAR_SIZE.x;
+        if hit || missed {
+            commands.entity(entity).despawn();
+            bonus.stars_left -= 1;
+        }
+    }
+}
+
+fn add_collected_stars_system(
+    mut collected: EventReader<PowerUpCollectedEvent>,
+    mut score: ResMut<Score>,
+) {
+    score.0 += count_collected(&mut collected, PowerUp::Star);
+}
+
+fn finish_bonus_system(
+    mut commands: Commands,
+    bonus: Res<BonusStage>,
+    banners: Query<Entity, With<BonusBanner>>,
+) {
+    if bonus.stars_left > 0 {
+        return;
+    }
+
+    commands.remove_resource::<BonusStage>();
+    for entity in banners.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn stop_bonus_system(mut commands: Commands, banners: Query<Entity, With<BonusBanner>>) {
+    commands.remove_resource::<BonusStage>();
+    for entity in banners.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+pub struct BonusPlugin;
+
+impl Plugin for BonusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NextBonus>()
+            .add_system(schedule_bonus_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(
+                start_bonus_system
+                    .after(GameplaySet::Scoring)
+                    .run_if(endless_run)
+                    .run_if(not(resource_exists::<BonusStage>()))
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_systems(
+                (
+                    collect_stars_system.in_set(GameplaySet::Collision),
+                    add_collected_stars_system.in_set(GameplaySet::Scoring),
+                    finish_bonus_system,
+                )
+                    .chain()
+                    .distributive_run_if(resource_exists::<BonusStage>())
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(stop_bonus_system.in_schedule(OnEnter(GameState::Menu)));
+    }
+}
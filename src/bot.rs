@@ -0,0 +1,34 @@
+use bevy::prelude::*;
+
+use crate::{WorldDirection, FLAP_SPEED};
+
+/// How far above the bottom of the next gap the bot keeps the bird. A flap
+/// from here peaks well below the top of the gap.
+const TARGET_ABOVE_GAP: f32 = 40.;
+
+/// Where the bot hovers while there's no gap ahead.
+const IDLE_TARGET: f32 = -40.;
+
+/// Decides whether to flap now, given the bird's position and vertical speed
+/// and the top left corners of the bottom pipes, `pipe_width` wide, of the gaps
+/// still ahead. Lets the bird fall towards the bottom of the next gap and flaps
+/// to stay above it.
+pub fn should_flap(
+    direction: WorldDirection,
+    player: Vec3,
+    velocity_y: f32,
+    pipe_width: f32,
+    gaps: impl Iterator<Item = Vec3>,
+) -> bool {
+    let target = gaps
+        .filter(|pipe| {
+            let far_side = direction.past_pipe(pipe.x, pipe_width, pipe_width);
+            !direction.passed(far_side, player.x)
+        })
+        .min_by(|a, b| (a.x - player.x).abs().total_cmp(&(b.x - player.x).abs()))
+        .map_or(IDLE_TARGET, |pipe| pipe.y + TARGET_ABOVE_GAP);
+
+    // Flapping again before the last flap has worn off climbs faster when the
+    // next gap is far above.
+    player.y < target && velocity_y < FLAP_SPEED / 2.
+}
@@ -0,0 +1,143 @@
+use bevy::prelude::*;
+
+use crate::{
+    console::{console_closed, Console},
+    touch::SafeArea,
+    DespawnOnExit, GameState, GameplaySet,
+};
+
+/// How fast the game runs while bullet time is on.
+const SLOW_SPEED: f32 = 0.4;
+/// Real seconds a full meter lasts.
+const DRAIN_SECS: f32 = 3.;
+/// Real seconds an empty meter takes to fill back up.
+const RECHARGE_SECS: f32 = 6.;
+const GAUGE_SIZE: Vec2 = Vec2::new(120., 10.);
+const GAUGE_COLOR: Color = Color::rgb(0.55, 0.6, 1.);
+const ACTIVE_COLOR: Color = Color::rgb(0.85, 0.9, 1.);
+
+/// How much bullet time is left in the current run, from 0 to 1.
+#[derive(Resource)]
+struct BulletTime {
+    meter: f32,
+    active: bool,
+}
+
+impl Default for BulletTime {
+    fn default() -> Self {
+        Self {
+            meter: 1.,
+            active: false,
+        }
+    }
+}
+
+#[derive(Component)]
+struct GaugeFill;
+
+fn reset_meter_system(mut bullet_time: ResMut<BulletTime>) {
+    *bullet_time = BulletTime::default();
+}
+
+/// Slows the game down while Shift is held and there's meter left, draining it,
+/// and fills it back up the rest of the time. Goes by real time, so slowing
+/// down doesn't make it last longer. Shift typed into the console doesn't count.
+fn bullet_time_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    console: Res<Console>,
+    mut time: ResMut<Time>,
+    mut bullet_time: ResMut<BulletTime>,
+) {
+    let delta = time.raw_delta_seconds();
+    let held =
+        console_closed(console) && keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+
+    bullet_time.active = held && bullet_time.meter > 0.;
+    bullet_time.meter = if bullet_time.active {
+        (bullet_time.meter - delta / DRAIN_SECS).max(0.)
+    } else {
+        (bullet_time.meter + delta / RECHARGE_SECS).min(1.)
+    };
+
+    let speed = if bullet_time.active { SLOW_SPEED } else { 1. };
+    if time.relative_speed() != speed {
+        time.set_relative_speed(speed);
+    }
+}
+
+/// Puts time back to normal when the run ends mid slow-down.
+fn stop_bullet_time_system(mut time: ResMut<Time>, mut bullet_time: ResMut<BulletTime>) {
+    bullet_time.active = false;
+    time.set_relative_speed(1.);
+}
+
+fn spawn_gauge_system(mut commands: Commands, safe_area: Res<SafeArea>) {
+    commands
+        .spawn((
+            DespawnOnExit(GameState::InGame),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        bottom: Val::Px(safe_area.bottom + 20.),
+                        left: Val::Px(safe_area.left + 10.),
+                        ..Default::default()
+                    },
+                    size: Size::new(Val::Px(GAUGE_SIZE.x), Val::Px(GAUGE_SIZE.y)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                GaugeFill,
+                NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                        ..Default::default()
+                    },
+                    background_color: GAUGE_COLOR.into(),
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+fn update_gauge_system(
+    bullet_time: Res<BulletTime>,
+    mut fill: Query<(&mut Style, &mut BackgroundColor), With<GaugeFill>>,
+) {
+    if !bullet_time.is_changed() {
+        return;
+    }
+
+    for (mut style, mut color) in fill.iter_mut() {
+        style.size.width = Val::Percent(bullet_time.meter * 100.);
+        color.0 = if bullet_time.active {
+            ACTIVE_COLOR
+        } else {
+            GAUGE_COLOR
+        };
+    }
+}
+
+/// Holding Shift during a run slows time down to thread tight gaps, for as long
+/// as the meter lasts.
+pub struct BulletTimePlugin;
+
+impl Plugin for BulletTimePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BulletTime>()
+            .add_system(reset_meter_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(spawn_gauge_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(stop_bullet_time_system.in_schedule(OnExit(GameState::InGame)))
+            .add_system(
+                bullet_time_system
+                    .in_set(GameplaySet::Input)
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(update_gauge_system.in_set(GameplaySet::Presentation));
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use crate::{
+    resolution::{ManifestTextures, WorldScale},
+    settings::Settings,
+    Player,
+};
+
+/// Lists the playable characters and their physics.
+const MANIFEST: &str = "bird.characters.ron";
+/// Size of a frame in a character's sprite sheet, which has four of them side
+/// by side like `bird.png`.
+const FRAME_SIZE: Vec2 = Vec2::new(20., 20.);
+
+fn one() -> f32 {
+    1.
+}
+
+/// How a character looks and flies.
+#[derive(Deserialize, Clone, Debug)]
+pub struct CharacterProfile {
+    pub id: String,
+    pub name: String,
+    texture: String,
+    /// Multiplies how fast the character falls.
+    #[serde(default = "one")]
+    pub gravity: f32,
+    /// Multiplies the strength of a flap.
+    #[serde(default = "one")]
+    pub flap: f32,
+    /// Multiplies the strength of a flap that follows the last one within
+    /// `double_flap_window`, in place of `flap`.
+    #[serde(default = "one")]
+    pub double_flap: f32,
+    /// Seconds.
+    #[serde(default)]
+    pub double_flap_window: f32,
+}
+
+impl Default for CharacterProfile {
+    /// The classic bird, for before the manifest has loaded.
+    fn default() -> Self {
+        Self {
+            id: "bird".into(),
+            name: "Bird".into(),
+            texture: "bird.png".into(),
+            gravity: 1.,
+            flap: 1.,
+            double_flap: 1.,
+            double_flap_window: 0.,
+        }
+    }
+}
+
+impl CharacterProfile {
+    /// Strength of a flap `since_last_flap` seconds after the previous one, and
+    /// whether it's a double flap.
+    pub fn flap_strength(&self, since_last_flap: f32) -> (f32, bool) {
+        if since_last_flap < self.double_flap_window {
+            (self.double_flap, true)
+        } else {
+            (self.flap, false)
+        }
+    }
+}
+
+/// The character manifest, stored as a `.characters.ron` asset.
+#[derive(Deserialize, TypeUuid, Debug)]
+#[uuid = "a3f6c1d8-7e42-4b0a-9c5d-2e8b71f4a690"]
+struct CharacterManifest {
+    characters: Vec<CharacterProfile>,
+}
+
+#[derive(Default)]
+struct CharacterManifestLoader;
+
+impl AssetLoader for CharacterManifestLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let manifest: CharacterManifest = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(manifest));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["characters.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct Manifest(Handle<CharacterManifest>);
+
+/// Every character that can be picked in the settings.
+#[derive(Resource)]
+pub struct Characters {
+    pub available: Vec<CharacterProfile>,
+}
+
+impl Default for Characters {
+    fn default() -> Self {
+        Self {
+            available: vec![CharacterProfile::default()],
+        }
+    }
+}
+
+impl Characters {
+    /// Id of the character `step` places on from `id`, wrapping around.
+    pub fn step(&self, id: &str, step: isize) -> String {
+        let index = self
+            .available
+            .iter()
+            .position(|character| character.id == id)
+            .unwrap_or(0) as isize;
+        let len = self.available.len() as isize;
+        self.available[(index + step).rem_euclid(len) as usize]
+            .id
+            .clone()
+    }
+
+    pub fn name<'a>(&'a self, id: &'a str) -> &'a str {
+        self.available
+            .iter()
+            .find(|character| character.id == id)
+            .map_or(id, |character| &character.name)
+    }
+}
+
+/// The character picked in the settings.
+#[derive(Resource, Default)]
+pub struct Character {
+    pub profile: CharacterProfile,
+    atlas: Handle<TextureAtlas>,
+}
+
+fn load_manifest_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Manifest(asset_server.load(MANIFEST)));
+}
+
+fn update_characters_system(
+    manifest: Res<Manifest>,
+    manifests: Res<Assets<CharacterManifest>>,
+    mut manifest_events: EventReader<AssetEvent<CharacterManifest>>,
+    mut characters: ResMut<Characters>,
+    mut manifest_textures: ResMut<ManifestTextures>,
+) {
+    if manifest_events.iter().count() == 0 {
+        return;
+    }
+
+    if let Some(manifest) = manifests.get(&manifest.0) {
+        characters.available = manifest.characters.clone();
+        let textures = manifest
+            .characters
+            .iter()
+            .map(|character| character.texture.clone())
+            .collect();
+        manifest_textures.set(MANIFEST, textures);
+    }
+}
+
+/// Switches to the character picked in the settings, falling back to the first
+/// one if it's gone from the manifest. Cuts the character's atlas again from
+/// the new set when the [`WorldScale`] changes.
+fn pick_character_system(
+    asset_server: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+    settings: Res<Settings>,
+    characters: Res<Characters>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    mut character: ResMut<Character>,
+) {
+    if !settings.is_changed() && !characters.is_changed() && !world_scale.is_changed() {
+        return;
+    }
+
+    let profile = characters
+        .available
+        .iter()
+        .find(|character| character.id == settings.character)
+        .or(characters.available.first())
+        .cloned()
+        .unwrap_or_default();
+    if character.profile.id == profile.id
+        && character.atlas != Handle::default()
+        && !world_scale.is_changed()
+    {
+        return;
+    }
+
+    let atlas = world_scale.atlas(&asset_server, &profile.texture, FRAME_SIZE, 4, 1);
+    *character = Character {
+        profile,
+        atlas: texture_atlases.add(atlas),
+    };
+}
+
+fn dress_player_system(
+    character: Res<Character>,
+    mut player: Query<&mut Handle<TextureAtlas>, With<Player>>,
+) {
+    if !character.is_changed() {
+        return;
+    }
+
+    for mut atlas in player.iter_mut() {
+        *atlas = character.atlas.clone();
+    }
+}
+
+/// Characters that fly differently, picked in the settings and described in a
+/// manifest of physics profiles.
+pub struct CharactersPlugin;
+
+impl Plugin for CharactersPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<CharacterManifest>()
+            .init_asset_loader::<CharacterManifestLoader>()
+            .init_resource::<Characters>()
+            .init_resource::<Character>()
+            .add_startup_system(load_manifest_system)
+            .add_systems(
+                (
+                    update_characters_system,
+                    pick_character_system,
+                    dress_player_system,
+                )
+                    .chain(),
+            );
+    }
+}
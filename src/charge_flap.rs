@@ -0,0 +1,167 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{DespawnOnExit, GameState, GameplaySet, Player};
+
+/// Holding this long charges a flap all the way.
+const FULL_CHARGE_SECS: f32 = 0.5;
+/// Strength of a flap released straight away, and of one fully charged, as a
+/// multiple of an ordinary flap.
+const STRENGTH_RANGE: (f32, f32) = (0.5, 1.5);
+const METER_SIZE: Vec2 = Vec2::new(40., 6.);
+/// How far above the bird's centre the meter floats.
+const METER_OFFSET: f32 = 40.;
+const METER_COLOR: Color = Color::rgb(1., 0.8, 0.25);
+const FULL_COLOR: Color = Color::rgb(1., 0.4, 0.2);
+
+/// How flapping is controlled.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum FlapControl {
+    /// Every press flaps the same, as in the original game.
+    #[default]
+    Tap,
+    /// Hold to charge a flap and release to let it go, flapping harder the
+    /// longer it was held.
+    Charge,
+}
+
+impl FlapControl {
+    const ALL: [FlapControl; 2] = [FlapControl::Tap, FlapControl::Charge];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FlapControl::Tap => "tap",
+            FlapControl::Charge => "hold to charge",
+        }
+    }
+
+    pub fn step(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0) as isize;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
+/// The flap being charged with [`FlapControl::Charge`], if any.
+#[derive(Resource, Default)]
+pub struct FlapCharge {
+    /// Seconds the flap button has been held, `None` while it's up.
+    held: Option<f32>,
+}
+
+impl FlapCharge {
+    pub fn start(&mut self) {
+        self.held = Some(0.);
+    }
+
+    pub fn tick(&mut self, delta: f32) {
+        if let Some(held) = &mut self.held {
+            *held += delta;
+        }
+    }
+
+    /// Lets go of the charge, returning how strong the flap is if one was
+    /// being charged.
+    pub fn release(&mut self) -> Option<f32> {
+        let fraction = self.fraction()?;
+        self.held = None;
+        Some(STRENGTH_RANGE.0 + (STRENGTH_RANGE.1 - STRENGTH_RANGE.0) * fraction)
+    }
+
+    /// How far the flap is charged, from 0 to 1.
+    fn fraction(&self) -> Option<f32> {
+        self.held
+            .map(|held| (held / FULL_CHARGE_SECS).clamp(0., 1.))
+    }
+}
+
+#[derive(Component)]
+struct ChargeMeter;
+
+#[derive(Component)]
+struct ChargeMeterFill;
+
+/// Drops a charge left over from the last run, like one held through a crash.
+fn reset_charge_system(mut charge: ResMut<FlapCharge>) {
+    *charge = FlapCharge::default();
+}
+
+fn spawn_meter_system(mut commands: Commands) {
+    commands
+        .spawn((
+            ChargeMeter,
+            DespawnOnExit(GameState::InGame),
+            SpriteBundle {
+                sprite: Sprite {
+                    color: Color::rgba(0., 0., 0., 0.5),
+                    custom_size: Some(METER_SIZE),
+                    ..Default::default()
+                },
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ChargeMeterFill,
+                SpriteBundle {
+                    sprite: Sprite {
+                        color: METER_COLOR,
+                        custom_size: Some(METER_SIZE),
+                        ..Default::default()
+                    },
+                    transform: Transform::from_xyz(0., 0., 0.01),
+                    ..Default::default()
+                },
+            ));
+        });
+}
+
+/// Shows the meter above the bird while a flap is charging, filling up from
+/// the left.
+#[allow(clippy::type_complexity)]
+fn update_meter_system(
+    charge: Res<FlapCharge>,
+    player: Query<&Transform, (With<Player>, Without<ChargeMeter>)>,
+    mut meter: Query<(&mut Transform, &mut Visibility), With<ChargeMeter>>,
+    mut fill: Query<
+        (&mut Transform, &mut Sprite),
+        (With<ChargeMeterFill>, Without<ChargeMeter>, Without<Player>),
+    >,
+) {
+    let (Ok(player), Ok((mut transform, mut visibility))) =
+        (player.get_single(), meter.get_single_mut())
+    else {
+        return;
+    };
+
+    let Some(fraction) = charge.fraction() else {
+        *visibility = Visibility::Hidden;
+        return;
+    };
+    *visibility = Visibility::Inherited;
+    transform.translation = player.translation + Vec3::new(0., METER_OFFSET, 10.);
+
+    for (mut transform, mut sprite) in fill.iter_mut() {
+        let width = METER_SIZE.x * fraction;
+        sprite.custom_size = Some(Vec2::new(width, METER_SIZE.y));
+        sprite.color = if fraction >= 1. {
+            FULL_COLOR
+        } else {
+            METER_COLOR
+        };
+        transform.translation.x = (width - METER_SIZE.x) / 2.;
+    }
+}
+
+/// Flapping harder the longer the flap button is held, picked in the settings.
+/// The timing itself is tracked by `flap_system`.
+pub struct ChargeFlapPlugin;
+
+impl Plugin for ChargeFlapPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlapCharge>()
+            .add_system(reset_charge_system.in_schedule(OnExit(GameState::InGame)))
+            .add_system(spawn_meter_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(update_meter_system.in_set(GameplaySet::Presentation));
+    }
+}
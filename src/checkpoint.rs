@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    climb::ClimbGap,
+    clipboard,
+    mode::{continues_allowed, endless_run},
+    score::{Score, ScorePipe},
+    tournament::Tournament,
+    GameState, GameplaySet,
+};
+
+/// Every this many pipes the endless mode spawns a checkpoint pipe.
+const CHECKPOINT_INTERVAL: u32 = 25;
+const CHECKPOINT_COLOR: Color = Color::rgb(1., 0.8, 0.2);
+
+/// Checkpoint bookkeeping for the current endless run.
+#[derive(Resource, Serialize, Deserialize, Clone, Default)]
+pub struct Checkpoints {
+    /// Pipe pairs spawned, which can be fewer than points once bonus points
+    /// have been scored.
+    pipes_spawned: u32,
+    /// Checkpoint pipes passed, the last of which is where a resumed run
+    /// spawns pipes on from.
+    #[serde(default)]
+    passed: u32,
+    /// Score as the last checkpoint pipe was passed.
+    reached: Option<u32>,
+    /// Whether the once-per-run resume was spent.
+    used: bool,
+}
+
+impl Checkpoints {
+    /// Counts a newly spawned pipe pair, returning whether it's a checkpoint.
+    pub fn next_pipe_is_checkpoint(&mut self) -> bool {
+        self.pipes_spawned += 1;
+        self.pipes_spawned.is_multiple_of(CHECKPOINT_INTERVAL)
+    }
+
+    /// The score the player may continue from after dying.
+    pub fn resumable(&self) -> Option<u32> {
+        self.reached.filter(|_| !self.used)
+    }
+
+    /// Fills in the checkpoints passed for runs saved before they were
+    /// counted, when checkpoints only came every [`CHECKPOINT_INTERVAL`]
+    /// points.
+    pub fn migrate_passed(&mut self) {
+        self.passed = self.reached.unwrap_or(0) / CHECKPOINT_INTERVAL;
+    }
+}
+
+#[derive(Component)]
+pub struct CheckpointPipe;
+
+fn tint_checkpoint_pipes_system(mut query: Query<&mut Sprite, Added<CheckpointPipe>>) {
+    for mut sprite in query.iter_mut() {
+        sprite.color = CHECKPOINT_COLOR;
+    }
+}
+
+/// Notices checkpoint pipes being passed by them giving up what scores them,
+/// whether a [`ScoreZone`](crate::score::ScoreZone) or a [`ClimbGap`]. Pipes
+/// released back to the pool lose their [`CheckpointPipe`] at the same time,
+/// so they don't count.
+fn reach_checkpoint_system(
+    score: Res<Score>,
+    mut scored: RemovedComponents<ScorePipe>,
+    mut climbed: RemovedComponents<ClimbGap>,
+    checkpoint_pipes: Query<(), With<CheckpointPipe>>,
+    mut checkpoints: ResMut<Checkpoints>,
+) {
+    for pipe in scored.iter().chain(climbed.iter()) {
+        if checkpoint_pipes.contains(pipe) {
+            checkpoints.passed += 1;
+            checkpoints.reached = Some(score.0);
+        }
+    }
+}
+
+fn resume_from_checkpoint_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut checkpoints: ResMut<Checkpoints>,
+    mut score: ResMut<Score>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Ctrl+C copies the seed code instead.
+    if !keyboard_input.just_pressed(KeyCode::C) || clipboard::shortcut_held(&keyboard_input) {
+        return;
+    }
+
+    if let Some(resume_score) = checkpoints.resumable() {
+        checkpoints.used = true;
+        checkpoints.pipes_spawned = checkpoints.passed * CHECKPOINT_INTERVAL;
+        score.0 = resume_score;
+        next_state.set(GameState::InGame);
+    }
+}
+
+fn reset_checkpoints_system(mut checkpoints: ResMut<Checkpoints>) {
+    *checkpoints = Checkpoints::default();
+}
+
+pub struct CheckpointPlugin;
+
+impl Plugin for CheckpointPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Checkpoints>()
+            .add_system(tint_checkpoint_pipes_system)
+            .add_system(
+                reach_checkpoint_system
+                    .in_set(GameplaySet::Presentation)
+                    .in_set(OnUpdate(GameState::InGame))
+                    .run_if(endless_run)
+                    .run_if(continues_allowed)
+                    .run_if(not(resource_exists::<Tournament>())),
+            )
+            .add_system(resume_from_checkpoint_system.in_set(OnUpdate(GameState::GameOver)))
+            .add_system(reset_checkpoints_system.in_schedule(OnEnter(GameState::Menu)));
+    }
+}
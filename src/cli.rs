@@ -0,0 +1,72 @@
+use std::path::PathBuf;
+
+use clap::Parser;
+use flappy_core::{mode::GameMode, GameConfig};
+
+/// Turns down every `--replay`, so asking for one says why rather than being
+/// taken for a typo.
+fn parse_replay(_: &str) -> Result<PathBuf, String> {
+    Err("replays aren't supported yet, since runs aren't recorded".into())
+}
+
+/// The [`GameMode`] named `value`, in any case.
+fn parse_mode(value: &str) -> Result<GameMode, String> {
+    GameMode::ALL
+        .into_iter()
+        .find(|mode| mode.name().eq_ignore_ascii_case(value))
+        .ok_or_else(|| {
+            let names: Vec<_> = GameMode::ALL
+                .iter()
+                .map(|mode| mode.name().to_lowercase())
+                .collect();
+            format!("expected one of {}", names.join(", "))
+        })
+}
+
+/// Launch options, read before the app is built.
+#[derive(Parser, Debug)]
+#[command(version, about = "Flappy Bird in Bevy")]
+pub struct Cli {
+    /// Seed for the first run's pipe layout.
+    #[arg(long)]
+    pub seed: Option<u64>,
+    /// Mode to start with selected on the menu.
+    #[arg(long, value_parser = parse_mode)]
+    pub mode: Option<GameMode>,
+    /// Start in borderless fullscreen.
+    #[arg(long)]
+    pub fullscreen: bool,
+    /// Start muted.
+    #[arg(long)]
+    pub mute: bool,
+    /// Let the demo bot fly every run.
+    #[arg(long)]
+    pub bot: bool,
+    /// Play back a recorded run (not supported yet).
+    #[arg(long, value_name = "FILE", value_parser = parse_replay)]
+    pub replay: Option<PathBuf>,
+    /// Read and write settings in this directory instead of the platform's
+    /// config directory.
+    #[arg(long, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+    /// Append newline-delimited JSON for every run start, flap, point and death
+    /// to this file.
+    #[arg(long, value_name = "PATH")]
+    pub event_log: Option<PathBuf>,
+    /// Measure frame times with lots of entities on screen, print them and exit.
+    #[arg(long)]
+    pub bench: bool,
+}
+
+impl Cli {
+    /// What the game itself is told, leaving out the options for the window,
+    /// storage and benchmarking.
+    pub fn game_config(&self) -> GameConfig {
+        GameConfig {
+            seed: self.seed,
+            mode: self.mode,
+            bot: self.bot,
+            event_log: self.event_log.clone(),
+        }
+    }
+}
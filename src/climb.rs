@@ -0,0 +1,182 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    checkpoint::{CheckpointPipe, Checkpoints},
+    difficulty::Difficulty,
+    mode::{endless_run, in_mode, GameMode},
+    pause::not_paused,
+    pipe_bounds, pipe_sprite,
+    pipe_themes::PipeTheme,
+    pool::PipePool,
+    score::ScoredEvent,
+    GameRng, GameState, GameplaySet, Player, ScrollAxis, Velocity, WorldDirection, PIPE_GAP,
+    WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+
+/// Seconds between pipes coming down.
+const SPAWN_INTERVAL_SECS: f32 = 1.2;
+/// Keeps gaps from opening right against the sides of the window.
+const MIN_GAP_OFFSET: f32 = 40.;
+/// Sideways speed of the bird while steering.
+const STEER_SPEED: f32 = 4.;
+/// How far the bird can steer from the middle of the window.
+const STEER_LIMIT: f32 = WINDOW_WIDTH / 2. - 25.;
+
+/// Marks the left pipe of each pair, which scores once it's below the bird.
+#[derive(Component)]
+pub struct ClimbGap;
+
+#[derive(Resource)]
+struct ClimbSpawner(Timer);
+
+impl Default for ClimbSpawner {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            SPAWN_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+fn scrolls_vertically(axis: Res<ScrollAxis>) -> bool {
+    *axis == ScrollAxis::Vertical
+}
+
+/// A pipe in `theme` lying on its side with its middle at `center`, turned by
+/// `angle` so its opening faces the gap.
+fn lying_pipe_sprite(theme: &PipeTheme, center: Vec2, angle: f32) -> SpriteBundle {
+    let mut sprite = pipe_sprite(theme, 0., 0., false);
+    let rotation = Quat::from_rotation_z(angle);
+    let corner_to_center = rotation * (theme.size * Vec2::new(0.5, -0.5)).extend(0.);
+    sprite.transform.rotation = rotation;
+    sprite.transform.translation = center.extend(0.) - corner_to_center;
+    sprite
+}
+
+fn start_climb_system(
+    mut axis: ResMut<ScrollAxis>,
+    mut spawner: ResMut<ClimbSpawner>,
+    mut player: Query<&mut Transform, With<Player>>,
+) {
+    *axis = ScrollAxis::Vertical;
+    spawner.0.reset();
+    for mut transform in player.iter_mut() {
+        transform.translation.x = 0.;
+    }
+}
+
+fn reset_scroll_axis_system(mut axis: ResMut<ScrollAxis>) {
+    *axis = ScrollAxis::Horizontal;
+}
+
+/// Drops a pair of pipes in from above the window, leaving a gap between them
+/// somewhere across its width.
+#[allow(clippy::too_many_arguments)]
+fn spawn_climb_pipes_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    difficulty: Res<Difficulty>,
+    theme: Res<PipeTheme>,
+    axis: Res<ScrollAxis>,
+    direction: Res<WorldDirection>,
+    mut spawner: ResMut<ClimbSpawner>,
+    mut pool: ResMut<PipePool>,
+    mut checkpoints: ResMut<Checkpoints>,
+    mut rng: ResMut<GameRng>,
+) {
+    // Sped up along with the game, so pipes stay as far apart.
+    if !spawner
+        .0
+        .tick(time.delta().mul_f32(difficulty.speed))
+        .just_finished()
+    {
+        return;
+    }
+
+    let gap_left = rng.gen_range(
+        (-WINDOW_WIDTH / 2. + MIN_GAP_OFFSET)..(WINDOW_WIDTH / 2. - MIN_GAP_OFFSET - PIPE_GAP),
+    );
+    let y = WINDOW_HEIGHT / 2. + theme.width();
+    let length = theme.size.y;
+
+    let left = lying_pipe_sprite(&theme, Vec2::new(gap_left - length / 2., y), -FRAC_PI_2);
+    let right = lying_pipe_sprite(
+        &theme,
+        Vec2::new(gap_left + PIPE_GAP + length / 2., y),
+        FRAC_PI_2,
+    );
+    let velocity = axis.velocity(*direction);
+    let left = pool.spawn(&mut commands, left, velocity);
+    let right = pool.spawn(&mut commands, right, velocity);
+    commands.entity(left).insert(ClimbGap);
+    if checkpoints.next_pipe_is_checkpoint() {
+        for pipe in [left, right] {
+            commands.entity(pipe).insert(CheckpointPipe);
+        }
+    }
+}
+
+fn steer_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut player: Query<(&Transform, &mut Velocity), With<Player>>,
+) {
+    let left = keyboard_input.any_pressed([KeyCode::Left, KeyCode::A]);
+    let right = keyboard_input.any_pressed([KeyCode::Right, KeyCode::D]);
+    let direction = right as i32 - left as i32;
+
+    for (transform, mut velocity) in player.iter_mut() {
+        let x = transform.translation.x;
+        let at_edge = (direction < 0 && x <= -STEER_LIMIT) || (direction > 0 && x >= STEER_LIMIT);
+        velocity.x = if at_edge {
+            0.
+        } else {
+            direction as f32 * STEER_SPEED
+        };
+    }
+}
+
+fn climb_score_system(
+    mut commands: Commands,
+    mut passed: EventWriter<ScoredEvent>,
+    theme: Res<PipeTheme>,
+    player: Query<&Transform, With<Player>>,
+    gaps: Query<(Entity, &Transform), With<ClimbGap>>,
+) {
+    let player_y = player.single().translation.y;
+
+    for (entity, transform) in gaps.iter() {
+        let (center, size) = pipe_bounds(transform, theme.size);
+        if center.y + size.y / 2. < player_y {
+            passed.send(ScoredEvent);
+            commands.entity(entity).remove::<ClimbGap>();
+        }
+    }
+}
+
+pub struct ClimbPlugin;
+
+impl Plugin for ClimbPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ClimbSpawner>()
+            .add_system(
+                start_climb_system
+                    .run_if(in_mode(GameMode::Climb))
+                    .run_if(endless_run)
+                    .in_schedule(OnEnter(GameState::InGame)),
+            )
+            .add_system(reset_scroll_axis_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_systems(
+                (
+                    spawn_climb_pipes_system,
+                    steer_system.in_set(GameplaySet::Input),
+                    climb_score_system.in_set(GameplaySet::Collision),
+                )
+                    .distributive_run_if(scrolls_vertically)
+                    .distributive_run_if(not_paused)
+                    .in_set(OnUpdate(GameState::InGame)),
+            );
+    }
+}
@@ -0,0 +1,85 @@
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use bevy::prelude::*;
+
+/// Programs that put their input on the clipboard, tried in order.
+#[cfg(target_os = "macos")]
+const COPY_COMMANDS: &[&[&str]] = &[&["pbcopy"]];
+#[cfg(target_os = "windows")]
+const COPY_COMMANDS: &[&[&str]] = &[&["clip"]];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const COPY_COMMANDS: &[&[&str]] = &[
+    // Wayland first, as the X11 tools only reach XWayland apps there.
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+];
+
+/// Programs that print what's on the clipboard, tried in order.
+#[cfg(target_os = "macos")]
+const PASTE_COMMANDS: &[&[&str]] = &[&["pbpaste"]];
+#[cfg(target_os = "windows")]
+const PASTE_COMMANDS: &[&[&str]] = &[&["powershell", "-NoProfile", "-Command", "Get-Clipboard"]];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const PASTE_COMMANDS: &[&[&str]] = &[
+    &["wl-paste", "--no-newline"],
+    &["xclip", "-selection", "clipboard", "-o"],
+    &["xsel", "--clipboard", "--output"],
+];
+
+fn copy_with(command: &[&str], text: &str) -> std::io::Result<bool> {
+    let mut child = Command::new(command[0])
+        .args(&command[1..])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    Ok(child.wait()?.success())
+}
+
+/// Puts `text` on the system clipboard through the platform's own tools, as
+/// Bevy has no clipboard of its own.
+pub fn copy(text: &str) -> Result<(), String> {
+    for command in COPY_COMMANDS {
+        match copy_with(command, text) {
+            Ok(true) => return Ok(()),
+            Ok(false) => debug!("{} couldn't copy to the clipboard", command[0]),
+            Err(err) => debug!("Couldn't run {}: {err}", command[0]),
+        }
+    }
+    Err("no clipboard tool available".into())
+}
+
+/// The text on the system clipboard.
+pub fn paste() -> Result<String, String> {
+    for command in PASTE_COMMANDS {
+        match Command::new(command[0])
+            .args(&command[1..])
+            .stderr(Stdio::null())
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+            }
+            Ok(_) => debug!("{} couldn't paste from the clipboard", command[0]),
+            Err(err) => debug!("Couldn't run {}: {err}", command[0]),
+        }
+    }
+    Err("no clipboard tool available".into())
+}
+
+/// Whether Ctrl, or Cmd on macOS, is held for a copy or paste shortcut.
+pub fn shortcut_held(keyboard_input: &Input<KeyCode>) -> bool {
+    keyboard_input.any_pressed([
+        KeyCode::LControl,
+        KeyCode::RControl,
+        KeyCode::LWin,
+        KeyCode::RWin,
+    ])
+}
@@ -0,0 +1,208 @@
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    time::Duration,
+};
+
+use bevy::{app::AppExit, prelude::*, window::WindowCloseRequested};
+use serde::{Deserialize, Serialize};
+
+use crate::{stats::Stats, storage};
+
+/// Keeps an unreachable endpoint from holding up startup or quitting for long.
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Where to mirror the stats file, read as RON from the platform's config
+/// directory. Without one the game never touches the network.
+///
+/// Any endpoint that answers `GET` and `PUT` on a path works, like a WebDAV
+/// folder or an S3-compatible bucket that allows it. Only plain `http://` URLs
+/// are supported, so anything beyond a local network should sit behind a proxy
+/// that adds TLS.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct SyncConfig {
+    /// Base URL, like `http://nas.local:8080/dav/flappybird/`.
+    url: String,
+    /// Sent with basic auth when given.
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+}
+
+impl storage::SaveFile for SyncConfig {
+    const VERSION: u32 = 1;
+}
+
+impl SyncConfig {
+    const FILE: &'static str = "cloud_sync.ron";
+
+    fn load() -> Option<Self> {
+        storage::config_path(Self::FILE).and_then(|path| storage::load(&path))
+    }
+
+    /// Host, port and path of the stats file of the active profile. Profiles
+    /// get their own remote files just like their local ones.
+    fn stats_location(&self) -> Result<(String, u16, String), String> {
+        let rest = self
+            .url
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("unsupported URL {}, only http:// is", self.url))?;
+        let (authority, base_path) = rest.split_once('/').unwrap_or((rest, ""));
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|_| format!("invalid port in {}", self.url))?,
+            ),
+            None => (authority, 80),
+        };
+
+        let file = storage::profile_relative_path(Stats::FILE);
+        let file = file
+            .iter()
+            .map(|part| part.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+        let base_path = base_path.trim_end_matches('/');
+        let path = if base_path.is_empty() {
+            format!("/{file}")
+        } else {
+            format!("/{base_path}/{file}")
+        };
+
+        Ok((host.to_string(), port, path))
+    }
+
+    /// Sends a bare HTTP/1.0 request, which keeps responses free of chunked
+    /// encoding, and returns the status code and body.
+    fn request(&self, method: &str, body: &str) -> Result<(u16, String), String> {
+        let (host, port, path) = self.stats_location()?;
+
+        let address = (host.as_str(), port)
+            .to_socket_addrs()
+            .map_err(|err| err.to_string())?
+            .next()
+            .ok_or_else(|| format!("no address for {host}"))?;
+        let mut stream =
+            TcpStream::connect_timeout(&address, TIMEOUT).map_err(|err| err.to_string())?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(TIMEOUT)))
+            .map_err(|err| err.to_string())?;
+
+        let mut request = format!(
+            "{method} {path} HTTP/1.0\r\nHost: {host}\r\nContent-Length: {}\r\n",
+            body.len()
+        );
+        if let Some(username) = &self.username {
+            let credentials = format!("{username}:{}", self.password.as_deref().unwrap_or(""));
+            request += &format!("Authorization: Basic {}\r\n", base64::encode(credentials));
+        }
+        request += "\r\n";
+        request += body;
+        stream
+            .write_all(request.as_bytes())
+            .map_err(|err| err.to_string())?;
+
+        let mut response = String::new();
+        stream
+            .read_to_string(&mut response)
+            .map_err(|err| err.to_string())?;
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+        let status = head
+            .split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse().ok())
+            .ok_or_else(|| "malformed response".to_string())?;
+
+        Ok((status, body.to_string()))
+    }
+
+    /// The remote stats, `None` if there aren't any yet.
+    fn pull(&self) -> Result<Option<Stats>, String> {
+        match self.request("GET", "")? {
+            (404, _) => Ok(None),
+            (200, body) => ron::from_str(&body)
+                .map(Some)
+                .map_err(|err| format!("invalid remote stats: {err}")),
+            (status, _) => Err(format!("GET failed with status {status}")),
+        }
+    }
+
+    fn push(&self, stats: &Stats) -> Result<(), String> {
+        let body =
+            ron::ser::to_string_pretty(stats, Default::default()).map_err(|err| err.to_string())?;
+        match self.request("PUT", &body)? {
+            (200..=299, _) => Ok(()),
+            (status, _) => Err(format!("PUT failed with status {status}")),
+        }
+    }
+}
+
+/// Whichever of two copies of the stats has the higher high score, the local
+/// one on a tie.
+fn resolve(local: Stats, remote: Option<Stats>) -> Stats {
+    match remote {
+        Some(remote) if remote.best_score > local.best_score => remote,
+        _ => local,
+    }
+}
+
+/// Replaces the local stats with the remote ones if they're ahead. Has to run
+/// before the stats are loaded, after the profile is activated.
+pub fn pull_on_startup() {
+    let Some(config) = SyncConfig::load() else {
+        return;
+    };
+
+    match config.pull() {
+        Ok(remote) => {
+            let local = Stats::load();
+            let local_best = local.best_score;
+            let stats = resolve(local, remote);
+            if stats.best_score > local_best {
+                info!("Pulled stats with a best score of {}", stats.best_score);
+                stats.save();
+            }
+        }
+        Err(err) => warn!("Couldn't pull stats from {}: {err}", config.url),
+    }
+}
+
+/// Uploads the stats when quitting, unless another device has pushed a higher
+/// high score in the meantime.
+fn push_on_quit_system(
+    mut exit: EventReader<AppExit>,
+    mut close_requested: EventReader<WindowCloseRequested>,
+    stats: Res<Stats>,
+) {
+    if exit.iter().count() == 0 && close_requested.iter().count() == 0 {
+        return;
+    }
+    let Some(config) = SyncConfig::load() else {
+        return;
+    };
+
+    let result = config.pull().and_then(|remote| {
+        let remote_ahead = remote
+            .as_ref()
+            .is_some_and(|remote| remote.best_score > stats.best_score);
+        if remote_ahead {
+            Ok(())
+        } else {
+            config.push(&stats)
+        }
+    });
+    if let Err(err) = result {
+        warn!("Couldn't push stats to {}: {err}", config.url);
+    }
+}
+
+pub struct CloudSyncPlugin;
+
+impl Plugin for CloudSyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(push_on_quit_system.in_base_set(CoreSet::Last));
+    }
+}
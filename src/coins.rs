@@ -0,0 +1,234 @@
+use bevy::{app::AppExit, prelude::*, sprite::collide_aabb::collide, window::WindowCloseRequested};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    console::AddConsoleCommand, count_collected, difficulty::Difficulty, mode::endless_run,
+    pipe_themes::PipeTheme, score::ScorePipe, storage, DespawnWhenOffscreen, GameRng, GameState,
+    GameplaySet, Player, PowerUp, PowerUpCollectedEvent, RunScoped, WorldDirection, FONT,
+    PIPE_WIDTH,
+};
+
+/// Chance of a coin floating between a new pipe pair and the next one.
+const COIN_CHANCE: f64 = 1. / 2.;
+/// Roughly halfway to the next pair at the normal spawn rate.
+const COIN_OFFSET_X: f32 = PIPE_WIDTH + 100.;
+const COIN_SIZE: Vec2 = Vec2::new(24., 24.);
+const COIN_COLOR: Color = Color::rgb(1., 0.8, 0.1);
+
+/// Coins collected across every run, persisted as RON in the platform's data
+/// directory.
+#[derive(Resource, Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct Wallet {
+    pub coins: u64,
+}
+
+impl storage::SaveFile for Wallet {
+    const VERSION: u32 = 1;
+}
+
+impl Wallet {
+    const FILE: &'static str = "wallet.ron";
+
+    pub fn load() -> Self {
+        storage::data_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = storage::data_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+}
+
+/// Coins collected in the current run.
+#[derive(Resource, Default)]
+pub struct RunCoins(pub u32);
+
+#[derive(Component)]
+pub struct Coin;
+
+#[derive(Component)]
+struct CoinText;
+
+fn spawn_coins_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    difficulty: Res<Difficulty>,
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    mut rng: ResMut<GameRng>,
+    new_pipes: Query<&Transform, Added<ScorePipe>>,
+) {
+    for pipe in new_pipes.iter() {
+        if !rng.gen_bool(COIN_CHANCE) {
+            continue;
+        }
+
+        let y = pipe.translation.y + difficulty.pipe_gap / 2. + rng.gen_range(-60.0..60.0);
+        commands.spawn((
+            Coin,
+            RunScoped,
+            direction.scroll_velocity(),
+            DespawnWhenOffscreen {
+                margin: COIN_SIZE.x,
+            },
+            Text2dBundle {
+                text: Text::from_section(
+                    "●",
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 28.,
+                        color: COIN_COLOR,
+                    },
+                ),
+                transform: Transform::from_xyz(
+                    direction.past_pipe(pipe.translation.x, theme.width(), COIN_OFFSET_X),
+                    y,
+                    5.,
+                ),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+/// Whether the player at `player` is touching the coin at `coin`.
+pub fn touches_coin(player: Vec3, coin: Vec3) -> bool {
+    collide(player, Vec2::new(45., 45.), coin, COIN_SIZE).is_some()
+}
+
+/// Picks up `coin` for the player, wherever it was caught.
+pub fn collect_coin(
+    commands: &mut Commands,
+    collected: &mut EventWriter<PowerUpCollectedEvent>,
+    coin: Entity,
+) {
+    collected.send(PowerUpCollectedEvent(PowerUp::Coin));
+    commands.entity(coin).despawn();
+}
+
+fn collect_coins_system(
+    mut commands: Commands,
+    mut collected: EventWriter<PowerUpCollectedEvent>,
+    player: Query<&Transform, With<Player>>,
+    coins: Query<(Entity, &Transform), With<Coin>>,
+) {
+    let player = player.single();
+
+    for (entity, transform) in coins.iter() {
+        if touches_coin(player.translation, transform.translation) {
+            collect_coin(&mut commands, &mut collected, entity);
+        }
+    }
+}
+
+fn add_collected_coins_system(
+    mut collected: EventReader<PowerUpCollectedEvent>,
+    mut wallet: ResMut<Wallet>,
+    mut run_coins: ResMut<RunCoins>,
+) {
+    let coins = count_collected(&mut collected, PowerUp::Coin);
+    if coins > 0 {
+        wallet.coins += coins as u64;
+        run_coins.0 += coins;
+    }
+}
+
+fn reset_run_coins_system(mut run_coins: ResMut<RunCoins>) {
+    run_coins.0 = 0;
+}
+
+fn save_wallet_system(wallet: Res<Wallet>) {
+    wallet.save();
+}
+
+/// Keeps coins picked up in a run that's quit midway.
+fn save_wallet_on_quit_system(
+    mut exit: EventReader<AppExit>,
+    mut close_requested: EventReader<WindowCloseRequested>,
+    wallet: Res<Wallet>,
+) {
+    if exit.iter().count() > 0 || close_requested.iter().count() > 0 {
+        wallet.save();
+    }
+}
+
+fn spawn_coin_text_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        CoinText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 22.,
+                color: COIN_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(40.),
+                left: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+fn update_coin_text_system(
+    run_coins: Res<RunCoins>,
+    state: Res<State<GameState>>,
+    mut query: Query<&mut Text, With<CoinText>>,
+) {
+    if !run_coins.is_changed() && !state.is_changed() {
+        return;
+    }
+
+    let value = match state.0 {
+        GameState::InGame | GameState::GameOver if run_coins.0 > 0 => format!("● {}", run_coins.0),
+        _ => String::new(),
+    };
+    for mut text in query.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
+
+fn coins_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let amount: u64 = args
+        .first()
+        .and_then(|arg| arg.parse().ok())
+        .ok_or("usage: coins <amount>")?;
+    let mut wallet = world.resource_mut::<Wallet>();
+    wallet.coins += amount;
+    wallet.save();
+    Ok(format!("wallet = {}", wallet.coins))
+}
+
+pub struct CoinsPlugin;
+
+impl Plugin for CoinsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Wallet::load())
+            .init_resource::<RunCoins>()
+            .add_console_command("coins", "coins <amount>", coins_command)
+            .add_startup_system(spawn_coin_text_system)
+            .add_systems(
+                (
+                    spawn_coins_system.run_if(endless_run),
+                    collect_coins_system.in_set(GameplaySet::Collision),
+                    add_collected_coins_system.in_set(GameplaySet::Scoring),
+                )
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(reset_run_coins_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(save_wallet_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(save_wallet_system.in_schedule(OnEnter(GameState::LevelComplete)))
+            .add_system(save_wallet_on_quit_system.in_base_set(CoreSet::Last))
+            .add_system(update_coin_text_system);
+    }
+}
@@ -0,0 +1,294 @@
+use bevy::{
+    ecs::system::CommandQueue, input::InputSystem, prelude::*, utils::HashMap,
+    window::ReceivedCharacter,
+};
+
+use crate::{
+    pipe_themes::PipeTheme, pool::PipePool, spawn_pipe_pair, GameState, PipeSpawnX, RunSeed,
+    SpeedScale, WorldDirection, FONT, PIPE_GAP, SPEED,
+};
+
+/// Lines of output kept on screen above the prompt.
+const SHOWN_LINES: usize = 12;
+
+/// Runs a command with its arguments, returning what to print.
+pub type CommandFn = fn(&mut World, &[&str]) -> Result<String, String>;
+
+struct ConsoleCommand {
+    usage: &'static str,
+    run: CommandFn,
+}
+
+/// Every command the console knows, by name.
+#[derive(Resource, Default)]
+struct ConsoleCommands(HashMap<&'static str, ConsoleCommand>);
+
+/// Lets any plugin add its own console commands.
+pub trait AddConsoleCommand {
+    fn add_console_command(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        run: CommandFn,
+    ) -> &mut Self;
+}
+
+impl AddConsoleCommand for App {
+    fn add_console_command(
+        &mut self,
+        name: &'static str,
+        usage: &'static str,
+        run: CommandFn,
+    ) -> &mut Self {
+        self.world
+            .get_resource_or_insert_with(ConsoleCommands::default)
+            .0
+            .insert(name, ConsoleCommand { usage, run });
+        self
+    }
+}
+
+/// The developer console, toggled with the backtick key.
+#[derive(Resource, Default)]
+pub struct Console {
+    open: bool,
+    input: String,
+    output: Vec<String>,
+    /// Lines entered this frame, run once input has been handled.
+    pending: Vec<String>,
+}
+
+/// For input that shouldn't fire while typing into the console.
+pub fn console_closed(console: Res<Console>) -> bool {
+    !console.open
+}
+
+#[derive(Component)]
+struct ConsoleUi;
+
+#[derive(Component)]
+struct ConsoleText;
+
+fn parse<T: std::str::FromStr>(arg: Option<&&str>, what: &str) -> Result<T, String> {
+    let arg = arg.ok_or_else(|| format!("missing {what}"))?;
+    arg.parse().map_err(|_| format!("invalid {what}: {arg}"))
+}
+
+fn help_command(world: &mut World, _: &[&str]) -> Result<String, String> {
+    let commands = world.resource::<ConsoleCommands>();
+    let mut usages = commands
+        .0
+        .values()
+        .map(|command| command.usage)
+        .collect::<Vec<_>>();
+    usages.sort();
+    Ok(usages.join("\n"))
+}
+
+fn set_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    match args.first() {
+        Some(&"speed") => {
+            let speed: f32 = parse(args.get(1), "speed")?;
+            world.resource_mut::<SpeedScale>().0 = speed / SPEED;
+            Ok(format!("speed = {speed}"))
+        }
+        Some(name) => Err(format!("unknown setting: {name}")),
+        None => Err("usage: set speed <pixels per frame>".into()),
+    }
+}
+
+fn spawn_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    if args.first() != Some(&"pipe") {
+        return Err("usage: spawn pipe <gap bottom> [gap]".into());
+    }
+    let gap_bottom: f32 = parse(args.get(1), "gap bottom")?;
+    let gap = match args.get(2) {
+        Some(_) => parse(args.get(2), "gap")?,
+        None => PIPE_GAP,
+    };
+
+    let theme = world.resource::<PipeTheme>().clone();
+    let direction = *world.resource::<WorldDirection>();
+    let spawn_x = world.resource::<PipeSpawnX>().0;
+    let mut queue = CommandQueue::default();
+    world.resource_scope(|world, mut pool: Mut<PipePool>| {
+        let mut commands = Commands::new(&mut queue, world);
+        spawn_pipe_pair(
+            &mut commands,
+            &mut pool,
+            &theme,
+            direction,
+            spawn_x,
+            gap_bottom,
+            gap,
+        );
+    });
+    queue.apply(world);
+    Ok(format!("spawned a pipe with its gap at {gap_bottom}"))
+}
+
+fn state_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let name = args.first().ok_or("usage: state <name>")?;
+    let state = GameState::variants()
+        .find(|state| format!("{state:?}").eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("unknown state: {name}"))?;
+    world.resource_mut::<NextState<GameState>>().set(state);
+    Ok(format!("state = {state:?}"))
+}
+
+fn seed_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let seed: u64 = parse(args.first(), "seed")?;
+    world.resource_mut::<RunSeed>().next = Some(seed);
+    Ok(format!("seed = {seed} (from the next run)"))
+}
+
+fn spawn_console_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn((
+            ConsoleUi,
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    padding: UiRect::all(Val::Px(8.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.8).into(),
+                visibility: Visibility::Hidden,
+                z_index: ZIndex::Global(100),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ConsoleText,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 14.,
+                        color: Color::WHITE,
+                    },
+                ),
+            ));
+        });
+}
+
+/// Types into the console while it's open, keeping the keys from reaching the
+/// game underneath.
+fn console_input_system(
+    mut console: ResMut<Console>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Grave) {
+        console.open = !console.open;
+        keyboard_input.reset_all();
+        characters.clear();
+        return;
+    }
+    if !console.open {
+        characters.clear();
+        return;
+    }
+
+    for character in characters.iter() {
+        if !character.char.is_control() && character.char != '`' {
+            console.input.push(character.char);
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        console.input.pop();
+    }
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        console.open = false;
+    } else if keyboard_input.just_pressed(KeyCode::Return) {
+        let line = std::mem::take(&mut console.input);
+        if !line.trim().is_empty() {
+            console.pending.push(line);
+        }
+    }
+    keyboard_input.reset_all();
+}
+
+fn run_commands_system(world: &mut World) {
+    if world.resource::<Console>().pending.is_empty() {
+        return;
+    }
+    let lines = std::mem::take(&mut world.resource_mut::<Console>().pending);
+
+    for line in lines {
+        let args = line.split_whitespace().collect::<Vec<_>>();
+        let run = world
+            .resource::<ConsoleCommands>()
+            .0
+            .get(args[0])
+            .map(|command| command.run);
+        let result = match run {
+            Some(run) => run(world, &args[1..]),
+            None => Err(format!("unknown command: {} (try help)", args[0])),
+        };
+
+        let mut console = world.resource_mut::<Console>();
+        console.output.push(format!("> {line}"));
+        match result {
+            Ok(output) if output.is_empty() => {}
+            Ok(output) => console.output.extend(output.lines().map(String::from)),
+            Err(err) => console.output.push(format!("error: {err}")),
+        }
+    }
+}
+
+fn update_console_system(
+    console: Res<Console>,
+    mut ui: Query<&mut Visibility, With<ConsoleUi>>,
+    mut text: Query<&mut Text, With<ConsoleText>>,
+) {
+    if !console.is_changed() {
+        return;
+    }
+
+    for mut visibility in ui.iter_mut() {
+        *visibility = if console.open {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+
+    let shown = &console.output[console.output.len().saturating_sub(SHOWN_LINES)..];
+    let mut value = shown.join("\n");
+    if !value.is_empty() {
+        value.push('\n');
+    }
+    value.push_str(&format!("> {}_", console.input));
+    for mut text in text.iter_mut() {
+        text.sections[0].value = value.clone();
+    }
+}
+
+pub struct ConsolePlugin;
+
+impl Plugin for ConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Console>()
+            .init_resource::<ConsoleCommands>()
+            .add_console_command("help", "help", help_command)
+            .add_console_command("set", "set speed <pixels per frame>", set_command)
+            .add_console_command("spawn", "spawn pipe <gap bottom> [gap]", spawn_command)
+            .add_console_command("state", "state <name>", state_command)
+            .add_console_command("seed", "seed <number>", seed_command)
+            .add_startup_system(spawn_console_system)
+            .add_system(
+                console_input_system
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem),
+            )
+            .add_system(
+                run_commands_system
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(console_input_system),
+            )
+            .add_system(update_console_system);
+    }
+}
@@ -0,0 +1,683 @@
+use std::collections::{BTreeSet, HashSet};
+
+use bevy::{prelude::*, sprite::Anchor};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievements::Achievements,
+    checkpoint::CheckpointPipe,
+    pipe_themes::PipeTheme,
+    resolution::WorldScale,
+    settings::Settings,
+    storage,
+    tween::{Ease, Scale, SpriteColor, Tween},
+    Background, GameState, Pipe, Player, RunScoped, WorldDirection, SCALE,
+};
+
+const TRAIL_INTERVAL_SECS: f32 = 0.04;
+const TRAIL_FADE_SECS: f32 = 0.4;
+const TRAIL_SIZE: f32 = 10.;
+/// Thickness of pipe outlines, in the pipe sprite's own pixels.
+const OUTLINE_THICKNESS: f32 = 1.;
+/// How far in front of the bird accessories are drawn.
+const ACCESSORY_Z: f32 = 0.1;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ItemKind {
+    Skin,
+    Trail,
+    PipeTheme,
+    Background,
+    Accessory,
+    Pet,
+}
+
+impl ItemKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            ItemKind::Skin => "Skin",
+            ItemKind::Trail => "Trail",
+            ItemKind::PipeTheme => "Pipes",
+            ItemKind::Background => "Sky",
+            ItemKind::Accessory => "Extra",
+            ItemKind::Pet => "Pet",
+        }
+    }
+}
+
+/// Something the bird, pipes or sky can be dressed up with. Items that cost
+/// nothing are owned from the start, unless they're held back until an
+/// achievement is unlocked.
+pub struct Item {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub kind: ItemKind,
+    pub price: u64,
+    /// Tint for skins, pipe themes, backgrounds and pets, colour of the dots for
+    /// trails and of the cloth for accessories. Pipe themes with their own
+    /// texture in the theme manifest are left white.
+    pub color: Color,
+    /// Glowing outline around pipes, for pipe themes too dark to make out
+    /// against a night sky.
+    pub outline: Option<Color>,
+    /// Id of the achievement that unlocks this item.
+    pub unlock: Option<&'static str>,
+}
+
+pub const ITEMS: [Item; 28] = [
+    Item {
+        id: "skin.classic",
+        name: "Classic",
+        kind: ItemKind::Skin,
+        price: 0,
+        color: Color::WHITE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "skin.ruby",
+        name: "Ruby",
+        kind: ItemKind::Skin,
+        price: 80,
+        color: Color::rgb(1., 0.5, 0.5),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "skin.lime",
+        name: "Lime",
+        kind: ItemKind::Skin,
+        price: 80,
+        color: Color::rgb(0.6, 1., 0.5),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "skin.midnight",
+        name: "Midnight",
+        kind: ItemKind::Skin,
+        price: 150,
+        color: Color::rgb(0.5, 0.55, 1.),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "skin.ghost",
+        name: "Ghost",
+        kind: ItemKind::Skin,
+        price: 200,
+        color: Color::rgba(1., 1., 1., 0.5),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "skin.golden",
+        name: "Golden",
+        kind: ItemKind::Skin,
+        price: 0,
+        color: Color::rgb(1., 0.85, 0.3),
+        outline: None,
+        unlock: Some("score-50"),
+    },
+    Item {
+        id: "trail.none",
+        name: "None",
+        kind: ItemKind::Trail,
+        price: 0,
+        color: Color::NONE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "trail.cloud",
+        name: "Cloud",
+        kind: ItemKind::Trail,
+        price: 30,
+        color: Color::WHITE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "trail.sky",
+        name: "Sky",
+        kind: ItemKind::Trail,
+        price: 60,
+        color: Color::rgb(0.5, 0.8, 1.),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "trail.flame",
+        name: "Flame",
+        kind: ItemKind::Trail,
+        price: 100,
+        color: Color::rgb(1., 0.5, 0.1),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "trail.ember",
+        name: "Ember",
+        kind: ItemKind::Trail,
+        price: 0,
+        color: Color::rgb(1., 0.25, 0.1),
+        outline: None,
+        unlock: Some("fever"),
+    },
+    Item {
+        id: "pipes.green",
+        name: "Green",
+        kind: ItemKind::PipeTheme,
+        price: 0,
+        color: Color::WHITE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "pipes.ice",
+        name: "Ice",
+        kind: ItemKind::PipeTheme,
+        price: 70,
+        color: Color::WHITE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "pipes.lava",
+        name: "Lava",
+        kind: ItemKind::PipeTheme,
+        price: 90,
+        color: Color::rgb(1., 0.45, 0.3),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "pipes.stone",
+        name: "Stone",
+        kind: ItemKind::PipeTheme,
+        price: 90,
+        color: Color::rgb(0.65, 0.65, 0.65),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "pipes.night",
+        name: "Night",
+        kind: ItemKind::PipeTheme,
+        price: 120,
+        color: Color::rgb(0.2, 0.22, 0.35),
+        outline: Some(Color::rgb(0.4, 0.9, 1.)),
+        unlock: None,
+    },
+    Item {
+        id: "pipes.metal",
+        name: "Metal",
+        kind: ItemKind::PipeTheme,
+        price: 110,
+        color: Color::WHITE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "pipes.cactus",
+        name: "Cactus",
+        kind: ItemKind::PipeTheme,
+        price: 110,
+        color: Color::WHITE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "bg.day",
+        name: "Day",
+        kind: ItemKind::Background,
+        price: 0,
+        color: Color::WHITE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "bg.sunset",
+        name: "Sunset",
+        kind: ItemKind::Background,
+        price: 0,
+        color: Color::rgb(1., 0.7, 0.55),
+        outline: None,
+        unlock: Some("games-25"),
+    },
+    Item {
+        id: "bg.night",
+        name: "Night",
+        kind: ItemKind::Background,
+        price: 0,
+        color: Color::rgb(0.35, 0.4, 0.65),
+        outline: None,
+        unlock: Some("daily"),
+    },
+    Item {
+        id: "accessory.none",
+        name: "None",
+        kind: ItemKind::Accessory,
+        price: 0,
+        color: Color::NONE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "accessory.party_hat",
+        name: "Party hat",
+        kind: ItemKind::Accessory,
+        price: 60,
+        color: Color::rgb(0.95, 0.3, 0.6),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "accessory.scarf",
+        name: "Scarf",
+        kind: ItemKind::Accessory,
+        price: 90,
+        color: Color::rgb(0.85, 0.15, 0.15),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "accessory.top_hat",
+        name: "Top hat",
+        kind: ItemKind::Accessory,
+        price: 120,
+        color: Color::rgb(0.15, 0.15, 0.18),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "pet.none",
+        name: "None",
+        kind: ItemKind::Pet,
+        price: 0,
+        color: Color::NONE,
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "pet.chick",
+        name: "Chick",
+        kind: ItemKind::Pet,
+        price: 100,
+        color: Color::rgb(1., 0.95, 0.5),
+        outline: None,
+        unlock: None,
+    },
+    Item {
+        id: "pet.collector",
+        name: "Collector",
+        kind: ItemKind::Pet,
+        price: 250,
+        color: Color::rgb(0.5, 1., 0.8),
+        outline: None,
+        unlock: Some("score-50"),
+    },
+];
+
+pub fn item(id: &str) -> Option<&'static Item> {
+    ITEMS.iter().find(|item| item.id == id)
+}
+
+/// Centre and size of each piece of an accessory, in the `@1x` bird sprite's
+/// own pixels from its centre while facing right.
+fn accessory_pieces(id: &str) -> &'static [(Vec2, Vec2)] {
+    const PARTY_HAT: [(Vec2, Vec2); 3] = [
+        (Vec2::new(3., 8.5), Vec2::new(6., 1.)),
+        (Vec2::new(3., 10.), Vec2::new(4., 2.)),
+        (Vec2::new(3., 12.), Vec2::new(2., 2.)),
+    ];
+    const SCARF: [(Vec2, Vec2); 2] = [
+        (Vec2::new(2., -2.), Vec2::new(10., 2.)),
+        (Vec2::new(-2., -4.5), Vec2::new(2., 4.)),
+    ];
+    const TOP_HAT: [(Vec2, Vec2); 2] = [
+        (Vec2::new(3., 8.5), Vec2::new(10., 1.)),
+        (Vec2::new(3., 12.), Vec2::new(6., 6.)),
+    ];
+
+    match id {
+        "accessory.party_hat" => &PARTY_HAT,
+        "accessory.scarf" => &SCARF,
+        "accessory.top_hat" => &TOP_HAT,
+        _ => &[],
+    }
+}
+
+/// Bought and equipped cosmetics, persisted as RON in the platform's data
+/// directory.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Customization {
+    pub owned: BTreeSet<String>,
+    pub skin: String,
+    pub trail: String,
+    pub pipes: String,
+    pub background: String,
+    pub accessory: String,
+    pub pet: String,
+}
+
+impl Default for Customization {
+    fn default() -> Self {
+        Self {
+            owned: BTreeSet::new(),
+            skin: "skin.classic".into(),
+            trail: "trail.none".into(),
+            pipes: "pipes.green".into(),
+            background: "bg.day".into(),
+            accessory: "accessory.none".into(),
+            pet: "pet.none".into(),
+        }
+    }
+}
+
+impl storage::SaveFile for Customization {
+    const VERSION: u32 = 1;
+}
+
+impl Customization {
+    const FILE: &'static str = "cosmetics.ron";
+
+    pub fn load() -> Self {
+        storage::data_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = storage::data_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+
+    pub fn owns(&self, item: &Item) -> bool {
+        item.price == 0 || self.owned.contains(item.id)
+    }
+
+    /// Whether an item is still waiting on its achievement.
+    pub fn is_locked(item: &Item, achievements: &Achievements) -> bool {
+        item.unlock.is_some_and(|id| !achievements.is_unlocked(id))
+    }
+
+    pub fn equipped(&self, kind: ItemKind) -> &str {
+        match kind {
+            ItemKind::Skin => &self.skin,
+            ItemKind::Trail => &self.trail,
+            ItemKind::PipeTheme => &self.pipes,
+            ItemKind::Background => &self.background,
+            ItemKind::Accessory => &self.accessory,
+            ItemKind::Pet => &self.pet,
+        }
+    }
+
+    pub fn equip(&mut self, item: &Item) {
+        let slot = match item.kind {
+            ItemKind::Skin => &mut self.skin,
+            ItemKind::Trail => &mut self.trail,
+            ItemKind::PipeTheme => &mut self.pipes,
+            ItemKind::Background => &mut self.background,
+            ItemKind::Accessory => &mut self.accessory,
+            ItemKind::Pet => &mut self.pet,
+        };
+        *slot = item.id.into();
+    }
+
+    /// Colour of the equipped item of a kind, falling back to no tint if the
+    /// save names an item that no longer exists.
+    pub fn color(&self, kind: ItemKind) -> Color {
+        item(self.equipped(kind)).map_or(Color::WHITE, |item| item.color)
+    }
+}
+
+#[derive(Resource)]
+struct TrailTimer(Timer);
+
+impl Default for TrailTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            TRAIL_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+fn apply_skin_system(
+    customization: Res<Customization>,
+    mut player: Query<&mut TextureAtlasSprite, With<Player>>,
+) {
+    if customization.is_changed() {
+        for mut sprite in player.iter_mut() {
+            sprite.color = customization.color(ItemKind::Skin);
+        }
+    }
+}
+
+/// Piece of the accessory the bird is wearing. Parented to the bird, so it
+/// tilts and flaps along with it.
+#[derive(Component)]
+struct Accessory;
+
+/// Dresses the bird in the equipped accessory, facing the way it flies.
+fn dress_bird_system(
+    mut commands: Commands,
+    customization: Res<Customization>,
+    direction: Res<WorldDirection>,
+    world_scale: Res<WorldScale>,
+    player: Query<Entity, With<Player>>,
+    new_player: Query<(), Added<Player>>,
+    accessories: Query<Entity, With<Accessory>>,
+) {
+    if !customization.is_changed()
+        && !direction.is_changed()
+        && !world_scale.is_changed()
+        && new_player.is_empty()
+    {
+        return;
+    }
+
+    for entity in accessories.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let id = customization.equipped(ItemKind::Accessory);
+    let color = customization.color(ItemKind::Accessory);
+    for bird in player.iter() {
+        commands.entity(bird).with_children(|parent| {
+            for (center, size) in accessory_pieces(id) {
+                let center =
+                    Vec2::new(center.x * direction.sign(), center.y) * world_scale.density();
+                parent.spawn((
+                    Accessory,
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(*size * world_scale.density()),
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(center.extend(ACCESSORY_Z)),
+                        ..Default::default()
+                    },
+                ));
+            }
+        });
+    }
+}
+
+/// Checkpoint pipes keep their own tint so they still stand out.
+fn tint_pipes_system(
+    customization: Res<Customization>,
+    mut pipes: Query<&mut Sprite, (Added<Pipe>, Without<CheckpointPipe>)>,
+) {
+    let color = customization.color(ItemKind::PipeTheme);
+    for mut sprite in pipes.iter_mut() {
+        sprite.color = color;
+    }
+}
+
+/// One edge of a pipe's outline.
+#[derive(Component)]
+struct PipeOutline {
+    color: Color,
+}
+
+/// Top left corner and size of each edge of the outline of a pipe in `theme`,
+/// in the pipe sprite's own pixels from its top left corner.
+fn outline_edges(theme: &PipeTheme) -> [(Vec2, Vec2); 4] {
+    let size = theme.size / SCALE.truncate();
+    let thickness = OUTLINE_THICKNESS;
+    [
+        (
+            Vec2::new(-thickness, thickness),
+            Vec2::new(thickness, size.y + thickness * 2.),
+        ),
+        (
+            Vec2::new(size.x, thickness),
+            Vec2::new(thickness, size.y + thickness * 2.),
+        ),
+        (Vec2::new(0., thickness), Vec2::new(size.x, thickness)),
+        (Vec2::new(0., -size.y), Vec2::new(size.x, thickness)),
+    ]
+}
+
+/// Outlines new pipes in the glow of the equipped theme, and redoes every pipe
+/// when the theme changes.
+fn outline_pipes_system(
+    mut commands: Commands,
+    customization: Res<Customization>,
+    theme: Res<PipeTheme>,
+    pipes: Query<Entity, With<Pipe>>,
+    new_pipes: Query<Entity, Added<Pipe>>,
+    outlines: Query<(Entity, &Parent), With<PipeOutline>>,
+) {
+    let redo: HashSet<Entity> = if customization.is_changed() || theme.is_changed() {
+        pipes.iter().collect()
+    } else {
+        new_pipes.iter().collect()
+    };
+    if redo.is_empty() {
+        return;
+    }
+
+    for (entity, parent) in outlines.iter() {
+        if redo.contains(&parent.get()) {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+
+    let Some(color) =
+        item(customization.equipped(ItemKind::PipeTheme)).and_then(|item| item.outline)
+    else {
+        return;
+    };
+    for pipe in redo {
+        commands.entity(pipe).with_children(|parent| {
+            for (corner, size) in outline_edges(&theme) {
+                parent.spawn((
+                    PipeOutline { color },
+                    SpriteBundle {
+                        sprite: Sprite {
+                            color,
+                            custom_size: Some(size),
+                            anchor: Anchor::TopLeft,
+                            ..Default::default()
+                        },
+                        transform: Transform::from_translation(corner.extend(0.01)),
+                        ..Default::default()
+                    },
+                ));
+            }
+        });
+    }
+}
+
+fn pulse_outlines_system(time: Res<Time>, mut outlines: Query<(&mut Sprite, &PipeOutline)>) {
+    let alpha = 0.65 + 0.35 * (time.elapsed_seconds() * 3.).sin();
+    for (mut sprite, outline) in outlines.iter_mut() {
+        sprite.color = outline.color.with_a(alpha);
+    }
+}
+
+fn tint_background_system(
+    customization: Res<Customization>,
+    mut backgrounds: Query<&mut Sprite, With<Background>>,
+) {
+    if customization.is_changed() {
+        for mut sprite in backgrounds.iter_mut() {
+            sprite.color = customization.color(ItemKind::Background);
+        }
+    }
+}
+
+fn spawn_trail_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    customization: Res<Customization>,
+    mut timer: ResMut<TrailTimer>,
+    direction: Res<WorldDirection>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let color = customization.color(ItemKind::Trail);
+    if settings.low_spec || color.a() == 0. || !timer.0.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    let player = player.single();
+    commands.spawn((
+        Tween::new(
+            SpriteColor {
+                start: color,
+                end: color.with_a(0.),
+            },
+            TRAIL_FADE_SECS,
+            Ease::Linear,
+        )
+        .despawning(),
+        Tween::new(
+            Scale {
+                start: Vec3::ONE,
+                end: Vec3::ZERO,
+                pivot: Vec3::ZERO,
+            },
+            TRAIL_FADE_SECS,
+            Ease::Linear,
+        ),
+        RunScoped,
+        direction.scroll_velocity(),
+        SpriteBundle {
+            sprite: Sprite {
+                color,
+                custom_size: Some(Vec2::splat(TRAIL_SIZE)),
+                ..Default::default()
+            },
+            // Behind the bird's tail rather than under it.
+            transform: Transform::from_xyz(
+                player.translation.x - 30. * direction.sign(),
+                player.translation.y,
+                0.5,
+            ),
+            ..Default::default()
+        },
+    ));
+}
+
+pub struct CosmeticsPlugin;
+
+impl Plugin for CosmeticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Customization::load())
+            .init_resource::<TrailTimer>()
+            .add_system(apply_skin_system)
+            .add_system(dress_bird_system)
+            .add_system(tint_pipes_system)
+            .add_system(outline_pipes_system)
+            .add_system(pulse_outlines_system)
+            .add_system(tint_background_system)
+            .add_system(spawn_trail_system.in_set(OnUpdate(GameState::InGame)));
+    }
+}
@@ -0,0 +1,323 @@
+use std::{
+    collections::BTreeMap,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    focus::MenuInput,
+    mode::{endless_run, in_mode, GameMode},
+    score::Score,
+    seed_run_system, storage, DespawnOnExit, GameRng, GameState, RunSeed, FONT,
+};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+const PLAYED_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+const UNPLAYED_COLOR: Color = Color::GRAY;
+
+/// Days since the Unix epoch in UTC, so the daily layout rolls over at the same
+/// moment for everyone.
+pub fn today() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| (elapsed.as_secs() / 86_400) as i64)
+}
+
+/// Year, month and day of a day number, after Howard Hinnant's `civil_from_days`.
+pub fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+/// Inverse of [`civil_from_days`].
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = year - i64::from(month <= 2);
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (i64::from(month) + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + i64::from(day) - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Best daily score for every day played, persisted as RON in the platform's
+/// data directory. Kept apart from other scores since every player competes on
+/// the same layout.
+#[derive(Resource, Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct DailyBests {
+    pub bests: BTreeMap<i64, u32>,
+}
+
+impl storage::SaveFile for DailyBests {
+    const VERSION: u32 = 1;
+}
+
+impl DailyBests {
+    const FILE: &'static str = "daily.ron";
+
+    pub fn load() -> Self {
+        storage::data_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = storage::data_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+
+    fn record(&mut self, day: i64, score: u32) {
+        let best = self.bests.entry(day).or_default();
+        *best = (*best).max(score);
+    }
+
+    /// Keeps the better score for every day.
+    pub fn merge(&mut self, other: &DailyBests) {
+        for (day, score) in &other.bests {
+            self.record(*day, *score);
+        }
+    }
+}
+
+/// Day whose layout the current run is playing, so a run that goes past
+/// midnight still counts for the day it started on.
+#[derive(Resource)]
+struct DailyRun {
+    day: i64,
+}
+
+/// Month shown on the calendar screen.
+#[derive(Resource)]
+struct CalendarMonth {
+    year: i64,
+    month: u32,
+}
+
+impl CalendarMonth {
+    fn step(&mut self, step: i64) {
+        let index = self.year * 12 + i64::from(self.month) - 1 + step;
+        self.year = index.div_euclid(12);
+        self.month = index.rem_euclid(12) as u32 + 1;
+    }
+}
+
+#[derive(Component)]
+struct CalendarText;
+
+/// Seeds the pipes from the date. Continuing a run from a checkpoint or a saved
+/// session isn't possible in this mode, so every daily run starts here.
+fn start_daily_system(
+    mut commands: Commands,
+    score: Res<Score>,
+    mut seed: ResMut<RunSeed>,
+    mut rng: ResMut<GameRng>,
+) {
+    if score.0 > 0 {
+        return;
+    }
+
+    let day = today();
+    seed.current = day as u64;
+    rng.0 = ChaCha12Rng::seed_from_u64(seed.current);
+    commands.insert_resource(DailyRun { day });
+}
+
+fn finish_daily_system(
+    score: Res<Score>,
+    run: Option<Res<DailyRun>>,
+    mut bests: ResMut<DailyBests>,
+) {
+    if let Some(run) = run {
+        bests.record(run.day, score.0);
+        bests.save();
+    }
+}
+
+fn stop_daily_system(mut commands: Commands) {
+    commands.remove_resource::<DailyRun>();
+}
+
+fn open_calendar_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::D) {
+        next_state.set(GameState::Calendar);
+    }
+}
+
+fn spawn_calendar_ui_system(mut commands: Commands) {
+    let (year, month, _) = civil_from_days(today());
+    commands.insert_resource(CalendarMonth { year, month });
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Calendar),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                CalendarText,
+                TextBundle::from_sections([]).with_style(Style {
+                    max_size: Size::width(Val::Px(360.)),
+                    ..Default::default()
+                }),
+            ));
+        });
+}
+
+fn remove_calendar_month_system(mut commands: Commands) {
+    commands.remove_resource::<CalendarMonth>();
+}
+
+fn calendar_input_system(
+    menu_input: MenuInput,
+    mut month: ResMut<CalendarMonth>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if menu_input.back() {
+        next_state.set(GameState::Menu);
+    }
+    let step = menu_input.adjustment();
+    if step != 0 {
+        month.step(step as i64);
+    }
+}
+
+/// Lays the month out as a grid of days starting on Monday, with days that have
+/// a daily score highlighted.
+fn update_calendar_text_system(
+    month: Res<CalendarMonth>,
+    asset_server: Res<AssetServer>,
+    bests: Res<DailyBests>,
+    mut query: Query<&mut Text, With<CalendarText>>,
+    added: Query<(), Added<CalendarText>>,
+) {
+    if !month.is_changed() && !bests.is_changed() && added.is_empty() {
+        return;
+    }
+
+    let font = asset_server.load(FONT);
+    let style = |font_size, color| TextStyle {
+        font: font.clone(),
+        font_size,
+        color,
+    };
+    let section =
+        |value: String, font_size, color| TextSection::new(value, style(font_size, color));
+
+    let first = days_from_civil(month.year, month.month, 1);
+    let next_month = if month.month == 12 {
+        days_from_civil(month.year + 1, 1, 1)
+    } else {
+        days_from_civil(month.year, month.month + 1, 1)
+    };
+    // 1970-01-01 was a Thursday.
+    let weekday = (first + 3).rem_euclid(7);
+
+    let mut sections = vec![
+        section(
+            format!(
+                "< {} {} >\n\n",
+                MONTH_NAMES[month.month as usize - 1],
+                month.year
+            ),
+            28.,
+            Color::WHITE,
+        ),
+        section("Mo  Tu  We  Th  Fr  Sa  Su\n".into(), 18., Color::WHITE),
+        section("    ".repeat(weekday as usize), 18., Color::WHITE),
+    ];
+    for day in first..next_month {
+        let (_, _, day_of_month) = civil_from_days(day);
+        let color = if bests.bests.contains_key(&day) {
+            PLAYED_COLOR
+        } else {
+            UNPLAYED_COLOR
+        };
+        let end = if (day + 3).rem_euclid(7) == 6 {
+            "\n"
+        } else {
+            "  "
+        };
+        sections.push(section(format!("{day_of_month:>2}{end}"), 18., color));
+    }
+
+    let today_line = match bests.bests.get(&today()) {
+        Some(best) => format!("Today's best: {best}"),
+        None => "Not played today".into(),
+    };
+    sections.push(section(
+        format!(
+            "\n\n{today_line}\nDays played: {}\n\nLeft/Right: month   Esc: back",
+            bests.bests.len()
+        ),
+        16.,
+        Color::WHITE,
+    ));
+
+    for mut text in query.iter_mut() {
+        text.sections = sections.clone();
+    }
+}
+
+pub struct DailyPlugin;
+
+impl Plugin for DailyPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(DailyBests::load())
+            .add_system(
+                start_daily_system
+                    .after(seed_run_system)
+                    .run_if(in_mode(GameMode::Daily))
+                    .run_if(endless_run)
+                    .in_schedule(OnEnter(GameState::InGame)),
+            )
+            .add_system(finish_daily_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(stop_daily_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(open_calendar_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_calendar_ui_system.in_schedule(OnEnter(GameState::Calendar)))
+            .add_system(remove_calendar_month_system.in_schedule(OnExit(GameState::Calendar)))
+            .add_systems(
+                (calendar_input_system, update_calendar_text_system)
+                    .chain()
+                    .in_set(OnUpdate(GameState::Calendar)),
+            );
+    }
+}
@@ -0,0 +1,236 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::prelude::*;
+
+use crate::{
+    scrolls_horizontally,
+    tween::{Ease, Rotation, Translation, Tween},
+    Crash, CrashCause, DespawnOnExit, GameState, GameplaySet, PipeFace, Player, PlayerDiedEvent,
+    ScrollAxis, WorldDirection, CEILING_Y, FLOOR_HEIGHT, FONT, WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+
+const DIAGRAM_SIZE: f32 = 120.;
+/// Diagram pixels per world pixel.
+const DIAGRAM_SCALE: f32 = 0.4;
+const PIPE_COLOR: Color = Color::rgb(0.45, 0.75, 0.25);
+const FLOOR_COLOR: Color = Color::rgb(0.85, 0.75, 0.5);
+const CEILING_COLOR: Color = Color::rgb(0.2, 0.3, 0.45);
+const BIRD_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+/// How long the bird takes to fall from the top of the screen to the floor.
+const FALL_SECS: f32 = 0.8;
+const NOSE_DIVE_SECS: f32 = 0.25;
+
+/// The crash that ended the last run.
+#[derive(Resource)]
+struct LastCrash(Crash);
+
+fn record_crash_system(mut commands: Commands, mut died: EventReader<PlayerDiedEvent>) {
+    if let Some(PlayerDiedEvent(crash)) = died.iter().last() {
+        commands.insert_resource(LastCrash(*crash));
+    }
+}
+
+fn clear_crash_system(mut commands: Commands) {
+    commands.remove_resource::<LastCrash>();
+}
+
+/// Drops the bird nose first onto the floor, as in the original game.
+fn start_fall_system(
+    mut commands: Commands,
+    direction: Res<WorldDirection>,
+    player: Query<(Entity, &Transform), With<Player>>,
+) {
+    let (entity, transform) = player.single();
+    let start = transform.translation;
+    let end = Vec3::new(start.x, -WINDOW_HEIGHT / 2. + FLOOR_HEIGHT, start.z);
+    // Eased like a fall under gravity, so taking the square root of the height.
+    let height = ((start.y - end.y) / (WINDOW_HEIGHT - FLOOR_HEIGHT)).max(0.);
+
+    commands.entity(entity).insert((
+        Tween::new(
+            Translation { start, end },
+            FALL_SECS * height.sqrt(),
+            Ease::QuadIn,
+        ),
+        Tween::new(
+            Rotation {
+                start: transform.rotation,
+                end: Quat::from_rotation_z(-FRAC_PI_2 * direction.sign()),
+            },
+            NOSE_DIVE_SECS,
+            Ease::QuadOut,
+        ),
+    ));
+}
+
+/// Leaving game over puts the bird back, which a fall still under way would undo.
+fn stop_fall_system(mut commands: Commands, player: Query<Entity, With<Player>>) {
+    for entity in player.iter() {
+        commands
+            .entity(entity)
+            .remove::<(Tween<Translation>, Tween<Rotation>)>();
+    }
+}
+
+/// What the bird ran into, as a hitbox centre and size, and how to draw it.
+fn obstacle(crash: &Crash) -> Option<(Vec2, Vec2, Color)> {
+    match crash.cause {
+        CrashCause::Floor => Some((
+            Vec2::new(crash.player.x, -WINDOW_HEIGHT / 2. + FLOOR_HEIGHT / 2.),
+            Vec2::new(WINDOW_WIDTH, FLOOR_HEIGHT),
+            FLOOR_COLOR,
+        )),
+        CrashCause::Pipe { center, size, .. } => Some((center, size, PIPE_COLOR)),
+        CrashCause::Ceiling => Some((
+            Vec2::new(crash.player.x, CEILING_Y + FLOOR_HEIGHT),
+            Vec2::new(WINDOW_WIDTH, FLOOR_HEIGHT * 2.),
+            CEILING_COLOR,
+        )),
+        CrashCause::Hazard => None,
+    }
+}
+
+fn tip(crash: &Crash, axis: ScrollAxis) -> &'static str {
+    match crash.cause {
+        CrashCause::Floor => "You dropped to the floor. Flap a little sooner.",
+        CrashCause::Hazard => {
+            "Blades turn deadly once they stop flashing, and spikes and cats guard the floor."
+        }
+        CrashCause::Ceiling => "You flew off the top of the screen. Ease off the flapping.",
+        CrashCause::Pipe { .. } if axis == ScrollAxis::Vertical => {
+            "Steer into the gap before the pipes reach you."
+        }
+        CrashCause::Pipe {
+            face: PipeFace::Top,
+            ..
+        } => "You flapped too late and sank onto the bottom pipe.",
+        CrashCause::Pipe {
+            face: PipeFace::Bottom,
+            ..
+        } => "You flapped too early and bumped the top pipe.",
+        CrashCause::Pipe { center, .. } if center.y > crash.player.y => {
+            "You came in too high. Line up with the gap before reaching it."
+        }
+        CrashCause::Pipe { .. } => "You came in too low. Line up with the gap before reaching it.",
+    }
+}
+
+/// A box of `size` diagram pixels with its top left corner at `corner`.
+pub fn diagram_rect(corner: Vec2, size: Vec2, color: Color) -> NodeBundle {
+    NodeBundle {
+        style: Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                left: Val::Px(corner.x),
+                top: Val::Px(corner.y),
+                ..Default::default()
+            },
+            size: Size::new(Val::Px(size.x), Val::Px(size.y)),
+            ..Default::default()
+        },
+        background_color: color.into(),
+        ..Default::default()
+    }
+}
+
+/// Shows the bird next to what it hit, zoomed in around the point of impact,
+/// with a tip on avoiding it next time.
+fn spawn_crash_panel_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    axis: Res<ScrollAxis>,
+    crash: Res<LastCrash>,
+) {
+    let crash = &crash.0;
+    let obstacle = obstacle(crash);
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::GameOver),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(70.),
+                        right: Val::Px(10.),
+                        ..Default::default()
+                    },
+                    max_size: Size::width(Val::Px(200.)),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(6.)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            if let Some((center, size, color)) = obstacle {
+                // Centred between the bird and the nearest point of the obstacle.
+                let nearest = crash.player.clamp(center - size / 2., center + size / 2.);
+                let view = (nearest + crash.player) / 2.;
+                let to_diagram = |point: Vec2| {
+                    Vec2::new(
+                        (point.x - view.x) * DIAGRAM_SCALE + DIAGRAM_SIZE / 2.,
+                        DIAGRAM_SIZE / 2. - (point.y - view.y) * DIAGRAM_SCALE,
+                    )
+                };
+                let bird_size = Vec2::splat(45. * DIAGRAM_SCALE);
+
+                parent
+                    .spawn(NodeBundle {
+                        style: Style {
+                            size: Size::all(Val::Px(DIAGRAM_SIZE)),
+                            overflow: Overflow::Hidden,
+                            ..Default::default()
+                        },
+                        background_color: Color::rgba(0.3, 0.6, 0.8, 0.8).into(),
+                        ..Default::default()
+                    })
+                    .with_children(|diagram| {
+                        diagram.spawn(diagram_rect(
+                            to_diagram(center + Vec2::new(-size.x, size.y) / 2.),
+                            size * DIAGRAM_SCALE,
+                            color,
+                        ));
+                        diagram.spawn(diagram_rect(
+                            to_diagram(crash.player) - bird_size / 2.,
+                            bird_size,
+                            BIRD_COLOR,
+                        ));
+                    });
+            }
+            parent.spawn(TextBundle::from_section(
+                tip(crash, *axis),
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 14.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+pub struct DeathPlugin;
+
+impl Plugin for DeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            record_crash_system
+                .in_set(GameplaySet::Scoring)
+                .in_set(OnUpdate(GameState::InGame)),
+        )
+        .add_system(clear_crash_system.in_schedule(OnEnter(GameState::InGame)))
+        .add_system(
+            start_fall_system
+                .run_if(scrolls_horizontally)
+                .in_schedule(OnEnter(GameState::GameOver)),
+        )
+        .add_system(stop_fall_system.in_schedule(OnExit(GameState::GameOver)))
+        .add_system(
+            spawn_crash_panel_system
+                .run_if(resource_exists::<LastCrash>())
+                .in_schedule(OnEnter(GameState::GameOver)),
+        );
+    }
+}
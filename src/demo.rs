@@ -0,0 +1,202 @@
+use bevy::{
+    input::{touch::Touches, InputSystem},
+    prelude::*,
+};
+use rand::Rng;
+
+use crate::{
+    bot, characters::Character, gravity_system, hits_pipe, movement_system, pipe_due,
+    pipe_themes::PipeTheme, pool::PipePool, resolution::WorldScale, score::ScorePipe,
+    spawn_pipe_pair, GameRng, GameState, GameplaySet, Pipe, PipeDistance, PipeSpawnX, Player,
+    Velocity, WorldDirection, FLAP_SPEED, FLOOR_HEIGHT, MIN_PIPE_OFFSET, PIPE_GAP, WINDOW_HEIGHT,
+};
+
+/// How long the menu has to sit untouched before the demo starts.
+const IDLE_SECS: f32 = 10.;
+
+/// Present while the bot plays a run behind the menu, like an arcade attract
+/// screen.
+#[derive(Resource)]
+pub struct Demo;
+
+/// Counts down to the demo while nobody touches anything on the menu.
+#[derive(Resource)]
+struct MenuIdle(Timer);
+
+impl Default for MenuIdle {
+    fn default() -> Self {
+        Self(Timer::from_seconds(IDLE_SECS, TimerMode::Once))
+    }
+}
+
+fn demo_running(demo: Option<Res<Demo>>) -> bool {
+    demo.is_some()
+}
+
+fn reset_idle_system(mut idle: ResMut<MenuIdle>) {
+    idle.0.reset();
+}
+
+fn start_demo_system(mut commands: Commands, time: Res<Time>, mut idle: ResMut<MenuIdle>) {
+    if idle.0.tick(time.delta()).just_finished() {
+        commands.insert_resource(Demo);
+    }
+}
+
+/// Puts the bird back in the middle and clears the demo's pipes.
+fn reset_demo_world(
+    commands: &mut Commands,
+    pool: &mut PipePool,
+    theme: &PipeTheme,
+    player: &mut Query<(&mut Transform, &mut Velocity), With<Player>>,
+    pipes: &Query<Entity, With<Pipe>>,
+) {
+    let (mut transform, mut velocity) = player.single_mut();
+    transform.translation.y = 0.;
+    velocity.y = 0.;
+    for entity in pipes.iter() {
+        pool.release(commands, entity, theme);
+    }
+}
+
+/// Any key, button or tap ends the demo and starts the countdown over. Runs
+/// before the menu's own systems, so the same press still starts a run or opens
+/// a screen on a clean world.
+#[allow(clippy::too_many_arguments)]
+fn stop_demo_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    touches: Res<Touches>,
+    demo: Option<Res<Demo>>,
+    mut idle: ResMut<MenuIdle>,
+    mut pool: ResMut<PipePool>,
+    theme: Res<PipeTheme>,
+    mut player: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    pipes: Query<Entity, With<Pipe>>,
+) {
+    let pressed = keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || gamepad_input.get_just_pressed().next().is_some()
+        || touches.any_just_pressed();
+    if !pressed {
+        return;
+    }
+
+    idle.0.reset();
+    if demo.is_some() {
+        commands.remove_resource::<Demo>();
+        reset_demo_world(&mut commands, &mut pool, &theme, &mut player, &pipes);
+    }
+}
+
+fn end_demo_system(mut commands: Commands) {
+    commands.remove_resource::<Demo>();
+}
+
+/// Gaps come from [`GameRng`] like a run's. Every run reseeds it when it
+/// starts, or picks up a saved session's state, so the demo played before
+/// doesn't change its layout.
+fn spawn_demo_pipes_system(
+    mut commands: Commands,
+    mut rng: ResMut<GameRng>,
+    theme: Res<PipeTheme>,
+    direction: Res<WorldDirection>,
+    mut pool: ResMut<PipePool>,
+    spawn_x: Res<PipeSpawnX>,
+    mut distance: ResMut<PipeDistance>,
+) {
+    let overshoot = distance.take_spacing();
+    let gap_bottom = rng.gen_range(
+        (-WINDOW_HEIGHT / 2. + MIN_PIPE_OFFSET)..(WINDOW_HEIGHT / 2. - MIN_PIPE_OFFSET - PIPE_GAP),
+    );
+    spawn_pipe_pair(
+        &mut commands,
+        &mut pool,
+        &theme,
+        *direction,
+        spawn_x.0 - overshoot,
+        gap_bottom,
+        PIPE_GAP,
+    );
+}
+
+/// Flaps like the chosen character, since it's the one falling in the demo.
+fn bot_system(
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    character: Res<Character>,
+    mut player: Query<(&Transform, &mut Velocity), With<Player>>,
+    gaps: Query<&Transform, With<ScorePipe>>,
+) {
+    let (transform, mut velocity) = player.single_mut();
+    let gaps = gaps.iter().map(|gap| gap.translation);
+    if bot::should_flap(
+        *direction,
+        transform.translation,
+        velocity.y,
+        theme.width(),
+        gaps,
+    ) {
+        velocity.y = FLAP_SPEED * character.profile.flap;
+    }
+}
+
+/// The bot isn't perfect; when it crashes the demo starts over.
+fn demo_crash_system(
+    mut commands: Commands,
+    theme: Res<PipeTheme>,
+    world_scale: Res<WorldScale>,
+    mut pool: ResMut<PipePool>,
+    mut player: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    pipes: Query<Entity, With<Pipe>>,
+    pipe_transforms: Query<&Transform, (With<Pipe>, Without<Player>)>,
+) {
+    let transform = *player.single().0;
+    let hit_floor = transform.translation.y < -WINDOW_HEIGHT / 2. + FLOOR_HEIGHT;
+    let hit_pipe = pipe_transforms
+        .iter()
+        .any(|pipe| hits_pipe(&transform, *world_scale, pipe, &theme));
+
+    if hit_floor || hit_pipe {
+        reset_demo_world(&mut commands, &mut pool, &theme, &mut player, &pipes);
+    }
+}
+
+pub struct DemoPlugin;
+
+impl Plugin for DemoPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MenuIdle>()
+            .add_system(reset_idle_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(end_demo_system.in_schedule(OnExit(GameState::Menu)))
+            .add_system(
+                stop_demo_system
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem)
+                    .run_if(in_state(GameState::Menu)),
+            )
+            .add_systems(
+                (
+                    start_demo_system.run_if(not(demo_running)),
+                    bot_system.in_set(GameplaySet::Input).run_if(demo_running),
+                    gravity_system
+                        .in_set(GameplaySet::Physics)
+                        .run_if(demo_running),
+                    demo_crash_system
+                        .in_set(GameplaySet::Collision)
+                        .run_if(demo_running),
+                )
+                    .in_set(OnUpdate(GameState::Menu)),
+            )
+            .add_system(
+                spawn_demo_pipes_system
+                    .in_set(GameplaySet::Physics)
+                    .after(movement_system)
+                    .run_if(pipe_due)
+                    .run_if(in_state(GameState::Menu))
+                    .run_if(demo_running),
+            );
+    }
+}
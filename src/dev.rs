@@ -0,0 +1,140 @@
+use bevy::{prelude::*, reflect::GetPath};
+
+use crate::{
+    console::AddConsoleCommand, AnimationIndices, AnimationTimer, DespawnWhenOffscreen,
+    InfiniteScrolling, Mass, Player, SpeedScale, Velocity,
+};
+
+/// Where the values of a registered type are kept.
+enum Target {
+    Component(ReflectComponent),
+    Resource(ReflectResource),
+}
+
+/// The registered type called `name`, like `Velocity`.
+fn target(world: &World, name: &str) -> Result<Target, String> {
+    let registry = world.resource::<AppTypeRegistry>().read();
+    let registration = registry
+        .get_with_short_name(name)
+        .ok_or_else(|| format!("unknown type: {name}"))?;
+    if let Some(component) = registration.data::<ReflectComponent>() {
+        Ok(Target::Component(component.clone()))
+    } else if let Some(resource) = registration.data::<ReflectResource>() {
+        Ok(Target::Resource(resource.clone()))
+    } else {
+        Err(format!("{name} is neither a component nor a resource"))
+    }
+}
+
+/// `value` parsed as the same type as `field`, for the types worth tweaking.
+fn parse_like(field: &dyn Reflect, value: &str) -> Result<Box<dyn Reflect>, String> {
+    fn parse<T: Reflect + std::str::FromStr>(value: &str) -> Result<Box<dyn Reflect>, String> {
+        value
+            .parse::<T>()
+            .map(|value| Box::new(value) as Box<dyn Reflect>)
+            .map_err(|_| format!("invalid value: {value}"))
+    }
+
+    if field.is::<f32>() {
+        parse::<f32>(value)
+    } else if field.is::<u32>() {
+        parse::<u32>(value)
+    } else if field.is::<usize>() {
+        parse::<usize>(value)
+    } else if field.is::<bool>() {
+        parse::<bool>(value)
+    } else {
+        Err(format!("can't set a {}", field.type_name()))
+    }
+}
+
+/// Sets the field at `path` in `target`, or `target` itself when it's empty.
+fn set_field(target: &mut dyn Reflect, path: &str, value: &str) -> Result<(), String> {
+    let field = if path.is_empty() {
+        target
+    } else {
+        target
+            .reflect_path_mut(path)
+            .map_err(|err| err.to_string())?
+    };
+    let value = parse_like(field, value)?;
+    field.set(value).map_err(|_| format!("can't set {path}"))
+}
+
+fn inspect_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let name = args.first().ok_or("usage: inspect <type>")?;
+    match target(world, name)? {
+        Target::Resource(resource) => resource
+            .reflect(world)
+            .map(|value| format!("{value:?}"))
+            .ok_or_else(|| format!("no {name} resource")),
+        Target::Component(component) => {
+            let lines = world
+                .iter_entities()
+                .filter_map(|entity| {
+                    let value = component.reflect(entity)?;
+                    Some(format!("{:?}: {value:?}", entity.id()))
+                })
+                .collect::<Vec<_>>();
+            if lines.is_empty() {
+                Err(format!("no entities with {name}"))
+            } else {
+                Ok(lines.join("\n"))
+            }
+        }
+    }
+}
+
+/// Sets a field on a resource, or on every entity with a component.
+fn tweak_command(world: &mut World, args: &[&str]) -> Result<String, String> {
+    let (Some(path), Some(value)) = (args.first(), args.get(1)) else {
+        return Err("usage: tweak <type>[.<field>] <value>".into());
+    };
+    let (name, field) = path.split_once('.').unwrap_or((path, ""));
+    match target(world, name)? {
+        Target::Resource(resource) => {
+            let mut reflected = resource
+                .reflect_mut(world)
+                .ok_or_else(|| format!("no {name} resource"))?;
+            set_field(&mut *reflected, field, value)?;
+            Ok(format!("{path} = {value}"))
+        }
+        Target::Component(component) => {
+            let entities = world
+                .iter_entities()
+                .filter_map(|entity| component.reflect(entity).map(|_| entity.id()))
+                .collect::<Vec<_>>();
+            for &entity in &entities {
+                let mut entity = world.entity_mut(entity);
+                if let Some(mut reflected) = component.reflect_mut(&mut entity) {
+                    set_field(&mut *reflected, field, value)?;
+                }
+            }
+            Ok(format!("{path} = {value} on {} entities", entities.len()))
+        }
+    }
+}
+
+/// Console commands for looking at and changing the game's own components and
+/// resources while it runs, through reflection.
+///
+/// Stands in for a `bevy-inspector-egui` world inspector. That crate isn't in
+/// the offline registry the game is built against, so `--features dev` couldn't
+/// even be checked with it. The same registered types are what an inspector
+/// would list, so swapping one in later only means adding its plugin here.
+pub struct DevPlugin;
+
+impl Plugin for DevPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Player>()
+            .register_type::<Velocity>()
+            .register_type::<Mass>()
+            .register_type::<AnimationTimer>()
+            .register_type::<AnimationIndices>()
+            .register_type::<InfiniteScrolling>()
+            .register_type::<DespawnWhenOffscreen>()
+            .register_type::<SpeedScale>()
+            .add_console_command("inspect", "inspect <type>", inspect_command)
+            .add_console_command("tweak", "tweak <type>[.<field>] <value>", tweak_command);
+    }
+}
@@ -0,0 +1,264 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    mode::endless_run, score::Score, settings::Settings, DespawnOnExit, GameState, FONT, PIPE_GAP,
+};
+
+/// The gap narrows once every this many points.
+const NARROWING_INTERVAL: u32 = 5;
+/// Runs ending below this score count towards turning the assist on.
+const ASSIST_EARLY_SCORE: u32 = 5;
+/// Early deaths in a row that turn the assist on.
+const ASSIST_AFTER_DEATHS: u32 = 3;
+/// Score at which the assist lets go again.
+const ASSIST_UNTIL_SCORE: u32 = 10;
+/// Pixels the assist adds to the gap.
+const ASSIST_WIDENING: f32 = 20.;
+pub const ASSIST_COLOR: Color = Color::rgb(0.5, 0.9, 0.6);
+
+/// How endless runs get harder as the score goes up.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum GapNarrowing {
+    #[default]
+    Off,
+    /// Shrinks the gap a little every few points.
+    Gradual,
+    /// Shrinks the gap twice as fast, down to a tighter floor.
+    SuddenDeath,
+}
+
+impl GapNarrowing {
+    const ALL: [GapNarrowing; 3] = [
+        GapNarrowing::Off,
+        GapNarrowing::Gradual,
+        GapNarrowing::SuddenDeath,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GapNarrowing::Off => "off",
+            GapNarrowing::Gradual => "gradual",
+            GapNarrowing::SuddenDeath => "sudden death",
+        }
+    }
+
+    pub fn step(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|n| *n == self).unwrap_or(0) as isize;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    /// Pixels taken off the gap per narrowing, and the smallest gap allowed.
+    fn shrink_and_floor(self) -> (f32, f32) {
+        match self {
+            GapNarrowing::Off => (0., PIPE_GAP),
+            GapNarrowing::Gradual => (5., 110.),
+            GapNarrowing::SuddenDeath => (10., 90.),
+        }
+    }
+}
+
+/// How fast the whole game plays. Scrolling, gravity and flaps all speed up
+/// together, so the bird's arcs keep their shape.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum SpeedPreset {
+    Chill,
+    #[default]
+    Classic,
+    Turbo,
+}
+
+impl SpeedPreset {
+    const ALL: [SpeedPreset; 3] = [SpeedPreset::Chill, SpeedPreset::Classic, SpeedPreset::Turbo];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            SpeedPreset::Chill => "chill",
+            SpeedPreset::Classic => "classic",
+            SpeedPreset::Turbo => "turbo",
+        }
+    }
+
+    pub fn step(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0) as isize;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    /// Multiplier on how far everything moves each frame.
+    pub fn scale(self) -> f32 {
+        match self {
+            SpeedPreset::Chill => 0.8,
+            SpeedPreset::Classic => 1.,
+            SpeedPreset::Turbo => 1.25,
+        }
+    }
+}
+
+/// Rubber-band help for players who keep crashing straight away, when
+/// [`Settings::dynamic_assist`] is on. After a few early deaths in a row the gap
+/// widens a little, until a run gets far enough.
+#[derive(Clone, Copy, Default, Debug)]
+pub struct Assist {
+    /// Endless runs in a row that ended below [`ASSIST_EARLY_SCORE`].
+    early_deaths: u32,
+    /// Whether the gap is widened right now.
+    pub active: bool,
+    /// Whether the current run has been helped at all, so its score can be
+    /// flagged.
+    pub used: bool,
+}
+
+/// Parameters of the game that depend on the settings and how far the run has
+/// got.
+#[derive(Resource)]
+pub struct Difficulty {
+    pub pipe_gap: f32,
+    /// From the [`SpeedPreset`]. Movement and gravity are multiplied by it, and
+    /// pipes spawn by distance so they stay as far apart.
+    pub speed: f32,
+    /// Carried over as the rest is worked out again.
+    pub assist: Assist,
+}
+
+impl Default for Difficulty {
+    fn default() -> Self {
+        Self {
+            pipe_gap: PIPE_GAP,
+            speed: 1.,
+            assist: Assist::default(),
+        }
+    }
+}
+
+impl Difficulty {
+    pub fn for_score(
+        narrowing: GapNarrowing,
+        speed: SpeedPreset,
+        score: u32,
+        assist: Assist,
+    ) -> Self {
+        let (shrink, floor) = narrowing.shrink_and_floor();
+        let narrowings = (score / NARROWING_INTERVAL) as f32;
+        let widening = if assist.active { ASSIST_WIDENING } else { 0. };
+
+        Self {
+            pipe_gap: (PIPE_GAP - narrowings * shrink).max(floor) + widening,
+            speed: speed.scale(),
+            assist,
+        }
+    }
+}
+
+/// Marks the HUD label shown while the assist is widening the gap.
+#[derive(Component)]
+struct AssistLabel;
+
+fn update_difficulty_system(
+    score: Res<Score>,
+    settings: Res<Settings>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    if !score.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    let mut assist = difficulty.assist;
+    if !settings.dynamic_assist {
+        assist = Assist::default();
+    } else if assist.active && score.0 >= ASSIST_UNTIL_SCORE {
+        assist.active = false;
+        assist.early_deaths = 0;
+    }
+    *difficulty = Difficulty::for_score(settings.gap_narrowing, settings.speed, score.0, assist);
+}
+
+/// Counts endless runs that end early, turning the assist on once there have
+/// been enough in a row.
+fn count_early_deaths_system(
+    score: Res<Score>,
+    settings: Res<Settings>,
+    mut difficulty: ResMut<Difficulty>,
+) {
+    if !settings.dynamic_assist {
+        return;
+    }
+
+    let assist = &mut difficulty.assist;
+    if score.0 >= ASSIST_EARLY_SCORE {
+        assist.early_deaths = 0;
+        return;
+    }
+    assist.early_deaths += 1;
+    if assist.early_deaths >= ASSIST_AFTER_DEATHS && !assist.active {
+        assist.active = true;
+        let assist = *assist;
+        *difficulty = Difficulty::for_score(settings.gap_narrowing, settings.speed, 0, assist);
+    }
+}
+
+/// Flags fresh runs that start with the assist on. Continued runs keep their
+/// flag.
+fn start_assisted_run_system(score: Res<Score>, mut difficulty: ResMut<Difficulty>) {
+    if score.0 == 0 {
+        difficulty.assist.used = difficulty.assist.active;
+    }
+}
+
+fn spawn_assist_label_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        AssistLabel,
+        DespawnOnExit(GameState::InGame),
+        TextBundle::from_section(
+            format!("Assist on: wider gaps until {ASSIST_UNTIL_SCORE}"),
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 14.,
+                color: ASSIST_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(110.),
+                left: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+fn update_assist_label_system(
+    difficulty: Res<Difficulty>,
+    mut labels: Query<&mut Visibility, With<AssistLabel>>,
+) {
+    for mut visibility in labels.iter_mut() {
+        let shown = if difficulty.assist.active {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *visibility != shown {
+            *visibility = shown;
+        }
+    }
+}
+
+pub struct DifficultyPlugin;
+
+impl Plugin for DifficultyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Difficulty>()
+            .add_system(update_difficulty_system)
+            .add_system(
+                count_early_deaths_system
+                    .run_if(endless_run)
+                    .in_schedule(OnEnter(GameState::GameOver)),
+            )
+            .add_systems(
+                (start_assisted_run_system, spawn_assist_label_system)
+                    .in_schedule(OnEnter(GameState::InGame)),
+            )
+            .add_system(update_assist_label_system.in_set(OnUpdate(GameState::InGame)));
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    io::{self, Read, Write},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::prelude::*;
+use serde_json::json;
+
+use crate::{mode::GameMode, score::Score, stats::Stats, GameState};
+
+/// Id of the Discord application the presence is shown for, set when building.
+const CLIENT_ID: Option<&str> = option_env!("DISCORD_CLIENT_ID");
+
+/// Only every this many points is worth telling Discord about.
+const SCORE_MILESTONE: u32 = 5;
+
+const OP_HANDSHAKE: u32 = 0;
+const OP_FRAME: u32 = 1;
+
+/// What the player is up to, as shown on their Discord profile.
+#[derive(Clone, PartialEq, Eq, Debug)]
+struct Activity {
+    details: String,
+    state: Option<String>,
+}
+
+/// Hands activities to the thread talking to Discord, so a slow or missing
+/// client never stalls a frame.
+#[derive(Resource)]
+struct DiscordPresence {
+    updates: Sender<Activity>,
+}
+
+trait Pipe: Read + Write + Send {}
+
+impl<T: Read + Write + Send> Pipe for T {}
+
+/// Opens the IPC socket of a running Discord client, which may be any of the
+/// first ten.
+#[cfg(unix)]
+fn open_pipe() -> io::Result<Box<dyn Pipe>> {
+    let dir = ["XDG_RUNTIME_DIR", "TMPDIR", "TMP", "TEMP"]
+        .iter()
+        .find_map(std::env::var_os)
+        .unwrap_or_else(|| "/tmp".into());
+    (0..10)
+        .find_map(|index| {
+            std::os::unix::net::UnixStream::connect(
+                std::path::Path::new(&dir).join(format!("discord-ipc-{index}")),
+            )
+            .ok()
+        })
+        .map(|stream| Box::new(stream) as Box<dyn Pipe>)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Discord isn't running"))
+}
+
+#[cfg(windows)]
+fn open_pipe() -> io::Result<Box<dyn Pipe>> {
+    (0..10)
+        .find_map(|index| {
+            std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .open(format!(r"\\.\pipe\discord-ipc-{index}"))
+                .ok()
+        })
+        .map(|file| Box::new(file) as Box<dyn Pipe>)
+        .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "Discord isn't running"))
+}
+
+fn write_frame(pipe: &mut dyn Pipe, opcode: u32, payload: &serde_json::Value) -> io::Result<()> {
+    let payload = payload.to_string();
+    let mut frame = Vec::with_capacity(8 + payload.len());
+    frame.extend_from_slice(&opcode.to_le_bytes());
+    frame.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    frame.extend_from_slice(payload.as_bytes());
+    pipe.write_all(&frame)
+}
+
+/// Reads and discards Discord's reply to the last frame.
+fn skip_frame(pipe: &mut dyn Pipe) -> io::Result<()> {
+    let mut header = [0; 8];
+    pipe.read_exact(&mut header)?;
+    let len = u32::from_le_bytes([header[4], header[5], header[6], header[7]]);
+    io::copy(&mut pipe.take(len as u64), &mut io::sink())?;
+    Ok(())
+}
+
+fn connect(client_id: &str) -> io::Result<Box<dyn Pipe>> {
+    let mut pipe = open_pipe()?;
+    write_frame(
+        pipe.as_mut(),
+        OP_HANDSHAKE,
+        &json!({ "v": 1, "client_id": client_id }),
+    )?;
+    skip_frame(pipe.as_mut())?;
+    Ok(pipe)
+}
+
+fn set_activity(
+    pipe: &mut dyn Pipe,
+    activity: &Activity,
+    started_at: u64,
+    nonce: u64,
+) -> io::Result<()> {
+    let mut fields = json!({
+        "details": activity.details,
+        "timestamps": { "start": started_at },
+    });
+    if let Some(state) = &activity.state {
+        fields["state"] = json!(state);
+    }
+    write_frame(
+        pipe,
+        OP_FRAME,
+        &json!({
+            "cmd": "SET_ACTIVITY",
+            "args": { "pid": std::process::id(), "activity": fields },
+            "nonce": nonce.to_string(),
+        }),
+    )?;
+    skip_frame(pipe)
+}
+
+/// Forwards activities to Discord until the game quits, reconnecting whenever
+/// the client was closed or started late.
+fn presence_thread(client_id: &str, updates: Receiver<Activity>) {
+    let started_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs());
+    let mut pipe = None;
+
+    for (nonce, activity) in updates.iter().enumerate() {
+        // Only the newest of the activities that queued up while Discord was slow matters.
+        let activity = updates.try_iter().last().unwrap_or(activity);
+
+        if pipe.is_none() {
+            pipe = connect(client_id)
+                .map_err(|err| debug!("Not showing Discord presence: {err}"))
+                .ok();
+        }
+        if let Some(connection) = pipe.as_mut() {
+            if let Err(err) = set_activity(connection.as_mut(), &activity, started_at, nonce as u64)
+            {
+                warn!("Lost connection to Discord: {err}");
+                pipe = None;
+            }
+        }
+    }
+}
+
+fn current_activity(state: GameState, score: u32, best_score: u32, mode: GameMode) -> Activity {
+    let milestone = score / SCORE_MILESTONE * SCORE_MILESTONE;
+    match state {
+        GameState::InGame if best_score > 0 && score > best_score => Activity {
+            details: "New high score!".into(),
+            state: Some(format!("{} – score {milestone}", mode.name())),
+        },
+        GameState::InGame => Activity {
+            details: format!("Playing – score {milestone}"),
+            state: Some(mode.name().into()),
+        },
+        GameState::GameOver => Activity {
+            details: "Game over".into(),
+            state: Some(format!("Scored {score}")),
+        },
+        GameState::Editor => Activity {
+            details: "Building a level".into(),
+            state: None,
+        },
+        _ => Activity {
+            details: "In menu".into(),
+            state: None,
+        },
+    }
+}
+
+/// Only sends an update when what's shown would change, which rounding the
+/// score to milestones keeps to a handful per run.
+fn update_presence_system(
+    state: Res<State<GameState>>,
+    score: Res<Score>,
+    stats: Res<Stats>,
+    mode: Res<GameMode>,
+    presence: Res<DiscordPresence>,
+    mut shown: Local<Option<Activity>>,
+) {
+    let activity = current_activity(state.0, score.0, stats.best_score, *mode);
+    if shown.as_ref() != Some(&activity) {
+        // The thread only stops with the app, so there's nothing to do if it's gone.
+        let _ = presence.updates.send(activity.clone());
+        *shown = Some(activity);
+    }
+}
+
+pub struct DiscordPlugin;
+
+impl Plugin for DiscordPlugin {
+    fn build(&self, app: &mut App) {
+        let Some(client_id) = CLIENT_ID else {
+            warn!("Built without DISCORD_CLIENT_ID, not showing Discord presence");
+            return;
+        };
+
+        let (updates, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("discord-presence".into())
+            .spawn(move || presence_thread(client_id, receiver))
+            .expect("failed to spawn Discord presence thread");
+
+        app.insert_resource(DiscordPresence { updates })
+            .add_system(update_presence_system);
+    }
+}
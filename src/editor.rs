@@ -0,0 +1,242 @@
+use std::path::PathBuf;
+
+use bevy::{input::mouse::MouseWheel, prelude::*, window::PrimaryWindow};
+
+use crate::{
+    level::{levels_dir, Level, LevelPlayback, PipeGap, PipeKind, LEVELS_FOLDER},
+    pipe_pair_sprites,
+    pipe_themes::PipeTheme,
+    GameState, FONT, MIN_PIPE_OFFSET, PIPE_SPAWN_X, PLAYER_X, WINDOW_HEIGHT,
+};
+
+const EDITOR_SCROLL_SPEED: f32 = 10.;
+
+const EDITOR_LEVEL_FILE: &str = "custom.level.ron";
+
+fn editor_level_path() -> PathBuf {
+    levels_dir().join(EDITOR_LEVEL_FILE)
+}
+
+fn pipe_kind_color(kind: PipeKind) -> Color {
+    match kind {
+        PipeKind::Normal => Color::WHITE,
+        PipeKind::Narrow => Color::rgb(1., 0.6, 0.6),
+        PipeKind::Moving => Color::rgb(0.6, 0.8, 1.),
+    }
+}
+
+/// The level being edited and the part of its timeline currently on screen.
+#[derive(Resource)]
+struct EditorLevel {
+    level: Level,
+    /// Scrolled distance the screen is showing, as in [`PipeGap::distance`].
+    scroll: f32,
+    /// Kind of the pipes placed with the mouse.
+    kind: PipeKind,
+}
+
+impl EditorLevel {
+    /// Timeline position of a pipe whose left edge is at world `x`.
+    fn distance_at(&self, x: f32) -> f32 {
+        self.scroll + x - PIPE_SPAWN_X
+    }
+
+    fn x_of(&self, gap: &PipeGap) -> f32 {
+        PIPE_SPAWN_X - (self.scroll - gap.distance)
+    }
+}
+
+#[derive(Component)]
+struct EditorUi;
+
+#[derive(Component)]
+struct EditorPipe;
+
+fn open_editor_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::E) {
+        next_state.set(GameState::Editor);
+    }
+}
+
+fn setup_editor_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    let level = Level::load(&editor_level_path()).unwrap_or_else(|_| Level {
+        name: "Custom".into(),
+        pipes: Vec::new(),
+    });
+    commands.insert_resource(EditorLevel {
+        level,
+        // Start with distance 0 lined up with the bird.
+        scroll: PIPE_SPAWN_X - PLAYER_X,
+        kind: PipeKind::Normal,
+    });
+
+    commands.spawn((
+        EditorUi,
+        TextBundle::from_section(
+            "LMB: place   RMB: remove   Left/Right: scroll\n1/2/3: normal/narrow/moving pipe\nS: save   P: save & play   Esc: back",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 16.,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.),
+                left: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+fn cleanup_editor_system(
+    mut commands: Commands,
+    ui: Query<Entity, With<EditorUi>>,
+    pipes: Query<Entity, With<EditorPipe>>,
+) {
+    commands.remove_resource::<EditorLevel>();
+    for entity in ui.iter().chain(pipes.iter()) {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn cursor_world_position(
+    windows: &Query<&Window, With<PrimaryWindow>>,
+    cameras: &Query<(&Camera, &GlobalTransform)>,
+) -> Option<Vec2> {
+    let cursor = windows.get_single().ok()?.cursor_position()?;
+    let (camera, camera_transform) = cameras.get_single().ok()?;
+    camera
+        .viewport_to_world(camera_transform, cursor)
+        .map(|ray| ray.origin.truncate())
+}
+
+#[allow(clippy::too_many_arguments)]
+fn editor_input_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    mut mouse_wheel: EventReader<MouseWheel>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    cameras: Query<(&Camera, &GlobalTransform)>,
+    theme: Res<PipeTheme>,
+    mut editor: ResMut<EditorLevel>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.pressed(KeyCode::Right) {
+        editor.scroll += EDITOR_SCROLL_SPEED;
+    }
+    if keyboard_input.pressed(KeyCode::Left) {
+        editor.scroll -= EDITOR_SCROLL_SPEED;
+    }
+    for event in mouse_wheel.iter() {
+        editor.scroll -= event.y * EDITOR_SCROLL_SPEED * 4.;
+    }
+
+    for (key, kind) in [
+        (KeyCode::Key1, PipeKind::Normal),
+        (KeyCode::Key2, PipeKind::Narrow),
+        (KeyCode::Key3, PipeKind::Moving),
+    ] {
+        if keyboard_input.just_pressed(key) {
+            editor.kind = kind;
+        }
+    }
+
+    if let Some(cursor) = cursor_world_position(&windows, &cameras) {
+        let distance = editor.distance_at(cursor.x - theme.width() / 2.).max(0.);
+
+        if mouse_input.just_pressed(MouseButton::Left) {
+            let kind = editor.kind;
+            let gap_bottom = (cursor.y - kind.gap() / 2.).clamp(
+                -WINDOW_HEIGHT / 2. + MIN_PIPE_OFFSET,
+                WINDOW_HEIGHT / 2. - MIN_PIPE_OFFSET - kind.gap(),
+            );
+            editor.level.insert(PipeGap {
+                distance,
+                gap_bottom,
+                kind,
+            });
+        }
+
+        if mouse_input.just_pressed(MouseButton::Right) {
+            let pipes = &mut editor.level.pipes;
+            if let Some(index) = pipes
+                .iter()
+                .position(|gap| (gap.distance - distance).abs() < theme.width())
+            {
+                pipes.remove(index);
+            }
+        }
+    }
+
+    let save = || {
+        let path = editor_level_path();
+        match editor.level.save(&path) {
+            Ok(()) => {
+                info!("Saved level to {}", path.display());
+                asset_server.reload_asset(format!("{LEVELS_FOLDER}/{EDITOR_LEVEL_FILE}"));
+            }
+            Err(err) => error!("Failed to save level to {}: {err}", path.display()),
+        }
+    };
+
+    if keyboard_input.just_pressed(KeyCode::S) {
+        save();
+    }
+
+    if keyboard_input.just_pressed(KeyCode::P) {
+        save();
+        commands.insert_resource(LevelPlayback::new(editor.level.clone(), None));
+        next_state.set(GameState::InGame);
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn draw_editor_pipes_system(
+    mut commands: Commands,
+    theme: Res<PipeTheme>,
+    editor: Res<EditorLevel>,
+    pipes: Query<Entity, With<EditorPipe>>,
+) {
+    if !editor.is_changed() && !theme.is_changed() {
+        return;
+    }
+
+    for entity in pipes.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    for gap in &editor.level.pipes {
+        let x = editor.x_of(gap);
+        for mut sprite in pipe_pair_sprites(&theme, x, gap.gap_bottom, gap.kind.gap()) {
+            sprite.sprite.color = pipe_kind_color(gap.kind);
+            commands.spawn((EditorPipe, sprite));
+        }
+    }
+}
+
+pub struct EditorPlugin;
+
+impl Plugin for EditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(open_editor_system.run_if(in_state(GameState::Menu)))
+            .add_system(setup_editor_system.in_schedule(OnEnter(GameState::Editor)))
+            .add_system(cleanup_editor_system.in_schedule(OnExit(GameState::Editor)))
+            .add_systems(
+                (editor_input_system, draw_editor_pipes_system)
+                    .chain()
+                    .in_set(OnUpdate(GameState::Editor)),
+            );
+    }
+}
@@ -0,0 +1,115 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{
+    asset::{AssetIo, AssetIoError, FileType, Metadata},
+    utils::BoxedFuture,
+};
+
+/// Every file the game loads, baked into the executable at compile time.
+/// Paths are relative to the `assets` folder, just like with `asset_server.load`.
+const EMBEDDED_ASSETS: &[(&str, &[u8])] = &[
+    ("bird.png", include_bytes!("../assets/bird.png")),
+    ("penguin.png", include_bytes!("../assets/penguin.png")),
+    (
+        "bird.characters.ron",
+        include_bytes!("../assets/bird.characters.ron"),
+    ),
+    ("pipe.png", include_bytes!("../assets/pipe.png")),
+    (
+        "pipe.themes.ron",
+        include_bytes!("../assets/pipe.themes.ron"),
+    ),
+    ("pipes/ice.png", include_bytes!("../assets/pipes/ice.png")),
+    (
+        "pipes/metal.png",
+        include_bytes!("../assets/pipes/metal.png"),
+    ),
+    (
+        "pipes/cactus.png",
+        include_bytes!("../assets/pipes/cactus.png"),
+    ),
+    ("floor.png", include_bytes!("../assets/floor.png")),
+    ("bg.png", include_bytes!("../assets/bg.png")),
+    ("icon.png", include_bytes!("../assets/icon.png")),
+    (
+        "ground_hazards.png",
+        include_bytes!("../assets/ground_hazards.png"),
+    ),
+    (
+        "fonts/DejaVuSansMono-Bold.ttf",
+        include_bytes!("../assets/fonts/DejaVuSansMono-Bold.ttf"),
+    ),
+    (
+        "levels/01-first-flight.level.ron",
+        include_bytes!("../assets/levels/01-first-flight.level.ron"),
+    ),
+    (
+        "levels/02-up-and-down.level.ron",
+        include_bytes!("../assets/levels/02-up-and-down.level.ron"),
+    ),
+    (
+        "levels/03-shifting-walls.level.ron",
+        include_bytes!("../assets/levels/03-shifting-walls.level.ron"),
+    ),
+];
+
+/// Serves assets from [`EMBEDDED_ASSETS`] instead of the filesystem, so the game
+/// can ship as a single executable.
+pub struct EmbeddedAssetIo;
+
+impl EmbeddedAssetIo {
+    fn find(path: &Path) -> Option<&'static [u8]> {
+        EMBEDDED_ASSETS
+            .iter()
+            .find(|(name, _)| Path::new(name) == path)
+            .map(|(_, bytes)| *bytes)
+    }
+
+    fn entries_in(path: &Path) -> impl Iterator<Item = PathBuf> + '_ {
+        EMBEDDED_ASSETS
+            .iter()
+            .map(|(name, _)| Path::new(name))
+            .filter(move |name| name.parent() == Some(path))
+            .map(Path::to_path_buf)
+    }
+}
+
+impl AssetIo for EmbeddedAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        Box::pin(async move {
+            Self::find(path)
+                .map(<[u8]>::to_vec)
+                .ok_or_else(|| AssetIoError::NotFound(path.to_path_buf()))
+        })
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        let entries: Vec<_> = Self::entries_in(path).collect();
+        Ok(Box::new(entries.into_iter()))
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        if Self::find(path).is_some() {
+            Ok(Metadata::new(FileType::File))
+        } else if Self::entries_in(path).next().is_some() {
+            Ok(Metadata::new(FileType::Directory))
+        } else {
+            Err(AssetIoError::NotFound(path.to_path_buf()))
+        }
+    }
+
+    fn watch_path_for_changes(
+        &self,
+        _to_watch: &Path,
+        _to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        Ok(())
+    }
+}
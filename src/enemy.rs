@@ -0,0 +1,202 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    bonus::BonusStage, hazard::Hazard, mode::endless_run, resolution::WorldScale, score::Score,
+    AnimationIndices, AnimationTimer, DespawnWhenOffscreen, GameState, RunScoped, RunSeed,
+    Velocity, WorldDirection, FLOOR_HEIGHT, FONT, SPEED, WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+
+/// Enemies only show up once the run has got going.
+const MIN_SCORE: u32 = 10;
+/// Seconds between enemies, picked at random from this range each time.
+const SPAWN_INTERVAL: (f32, f32) = (3., 7.);
+/// How long the warning at the edge of the screen shows before the enemy arrives.
+const WARNING_SECS: f32 = 0.7;
+const ENEMY_SPEED: f32 = SPEED * 1.8;
+const ENEMY_HITBOX: Vec2 = Vec2::new(40., 30.);
+const ENEMY_COLOR: Color = Color::rgb(1., 0.45, 0.45);
+
+#[derive(Resource)]
+struct EnemyAtlas(Handle<TextureAtlas>);
+
+/// Times and places enemies, kept apart from [`GameRng`](crate::GameRng)
+/// since enemies come on a clock while pipe gaps come by distance. Sharing one
+/// would let frame timing shift every later gap.
+#[derive(Resource)]
+struct EnemyRng(StdRng);
+
+impl Default for EnemyRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+#[derive(Resource)]
+struct EnemySpawner(Timer);
+
+impl EnemySpawner {
+    fn new(rng: &mut impl Rng) -> Self {
+        Self(Timer::new(Self::random_interval(rng), TimerMode::Once))
+    }
+
+    fn random_interval(rng: &mut impl Rng) -> Duration {
+        Duration::from_secs_f32(rng.gen_range(SPAWN_INTERVAL.0..SPAWN_INTERVAL.1))
+    }
+}
+
+impl FromWorld for EnemySpawner {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(&mut world.resource_mut::<EnemyRng>().0)
+    }
+}
+
+#[derive(Component)]
+struct Enemy;
+
+/// Flashes where an enemy is about to fly in.
+#[derive(Component)]
+struct EnemyWarning(Timer);
+
+/// Cut again from the new set whenever the [`WorldScale`] changes. Enemies
+/// already flying keep the atlas they were spawned with, at their old scale.
+fn load_enemy_atlas_system(
+    mut commands: Commands,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+) {
+    let texture_atlas = world_scale.atlas(&asset_server, "bird.png", Vec2::new(20., 20.), 4, 1);
+    commands.insert_resource(EnemyAtlas(texture_atlases.add(texture_atlas)));
+}
+
+fn enemy_spawner_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    score: Res<Score>,
+    asset_server: Res<AssetServer>,
+    direction: Res<WorldDirection>,
+    mut spawner: ResMut<EnemySpawner>,
+    mut rng: ResMut<EnemyRng>,
+) {
+    if score.0 < MIN_SCORE || !spawner.0.tick(time.delta()).finished() {
+        return;
+    }
+
+    spawner
+        .0
+        .set_duration(EnemySpawner::random_interval(&mut rng.0));
+    spawner.0.reset();
+
+    let y = rng.0.gen_range(
+        (-WINDOW_HEIGHT / 2. + FLOOR_HEIGHT + ENEMY_HITBOX.y)
+            ..(WINDOW_HEIGHT / 2. - ENEMY_HITBOX.y),
+    );
+    commands.spawn((
+        EnemyWarning(Timer::from_seconds(WARNING_SECS, TimerMode::Once)),
+        RunScoped,
+        Text2dBundle {
+            text: Text::from_section(
+                "!",
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 40.,
+                    color: ENEMY_COLOR,
+                },
+            ),
+            transform: Transform::from_xyz(direction.mirror_x(WINDOW_WIDTH / 2. - 20., 0.), y, 20.),
+            ..Default::default()
+        },
+    ));
+}
+
+fn enemy_warning_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    atlas: Res<EnemyAtlas>,
+    world_scale: Res<WorldScale>,
+    direction: Res<WorldDirection>,
+    mut warnings: Query<(Entity, &mut EnemyWarning, &Transform, &mut Visibility)>,
+) {
+    for (entity, mut warning, transform, mut visibility) in warnings.iter_mut() {
+        warning.0.tick(time.delta());
+        *visibility = if ((warning.0.elapsed_secs() * 8.) as u32).is_multiple_of(2) {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+
+        if !warning.0.finished() {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        commands.spawn((
+            Enemy,
+            RunScoped,
+            Hazard { size: ENEMY_HITBOX },
+            Velocity {
+                x: -ENEMY_SPEED * direction.sign(),
+                y: 0.,
+            },
+            DespawnWhenOffscreen {
+                margin: ENEMY_HITBOX.x,
+            },
+            SpriteSheetBundle {
+                texture_atlas: atlas.0.clone(),
+                sprite: TextureAtlasSprite {
+                    index: 0,
+                    flip_x: *direction == WorldDirection::Right,
+                    color: ENEMY_COLOR,
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(
+                    direction.mirror_x(WINDOW_WIDTH / 2. + ENEMY_HITBOX.x, 0.),
+                    transform.translation.y,
+                    5.,
+                )
+                .with_scale(world_scale.sprite()),
+                ..Default::default()
+            },
+            AnimationIndices { first: 0, last: 3 },
+            AnimationTimer(Timer::from_seconds(0.08, TimerMode::Repeating)),
+        ));
+    }
+}
+
+/// Starts the enemies over from the run's seed whenever it's picked or
+/// restored, so a seeded run meets the same ones.
+fn seed_enemies_system(
+    seed: Res<RunSeed>,
+    mut rng: ResMut<EnemyRng>,
+    mut spawner: ResMut<EnemySpawner>,
+) {
+    rng.0 = StdRng::seed_from_u64(seed.current);
+    *spawner = EnemySpawner::new(&mut rng.0);
+}
+
+pub struct EnemyPlugin;
+
+impl Plugin for EnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EnemyRng>()
+            .init_resource::<EnemySpawner>()
+            .add_system(load_enemy_atlas_system.run_if(resource_changed::<WorldScale>()))
+            .add_system(
+                seed_enemies_system
+                    .before(enemy_spawner_system)
+                    .run_if(resource_changed::<RunSeed>()),
+            )
+            .add_systems(
+                (
+                    enemy_spawner_system
+                        .run_if(endless_run)
+                        .run_if(not(resource_exists::<BonusStage>())),
+                    enemy_warning_system,
+                )
+                    .in_set(OnUpdate(GameState::InGame)),
+            );
+    }
+}
@@ -0,0 +1,156 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{LineWriter, Write},
+    path::PathBuf,
+};
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    mode::GameMode, score::Score, CrashCause, FlapEvent, GameState, Player, PlayerDiedEvent,
+    RunSeed, Velocity,
+};
+
+/// One line of the log. Times are seconds since the run (re)started.
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum RunEvent {
+    RunStart {
+        mode: GameMode,
+        seed: u64,
+        /// Non-zero when continuing from a checkpoint or a saved session.
+        score: u32,
+    },
+    Flap {
+        time: f32,
+        y: f32,
+        velocity: f32,
+    },
+    Score {
+        time: f32,
+        score: u32,
+    },
+    Death {
+        time: f32,
+        score: u32,
+        cause: &'static str,
+        player_y: f32,
+        pipe_x: Option<f32>,
+        pipe_y: Option<f32>,
+    },
+}
+
+/// File the run events are appended to, one JSON object per line.
+#[derive(Resource)]
+struct EventLog {
+    file: LineWriter<File>,
+    /// When the current run started, in seconds since startup.
+    started: f32,
+    /// Set on entering a run, whose start is logged once its seed is known.
+    start_pending: bool,
+}
+
+impl EventLog {
+    fn write(&mut self, event: RunEvent) {
+        let result = serde_json::to_string(&event)
+            .map_err(|err| err.to_string())
+            .and_then(|line| writeln!(self.file, "{line}").map_err(|err| err.to_string()));
+        if let Err(err) = result {
+            error!("Failed to write run event: {err}");
+        }
+    }
+}
+
+fn start_run_system(time: Res<Time>, mut log: ResMut<EventLog>) {
+    log.started = time.elapsed_seconds();
+    log.start_pending = true;
+}
+
+#[allow(clippy::too_many_arguments)]
+fn log_run_events_system(
+    time: Res<Time>,
+    mode: Res<GameMode>,
+    seed: Res<RunSeed>,
+    score: Res<Score>,
+    mut log: ResMut<EventLog>,
+    mut flapped: EventReader<FlapEvent>,
+    mut died: EventReader<PlayerDiedEvent>,
+    player: Query<(&Transform, &Velocity), With<Player>>,
+) {
+    if log.start_pending {
+        log.start_pending = false;
+        log.write(RunEvent::RunStart {
+            mode: *mode,
+            seed: seed.current,
+            score: score.0,
+        });
+    }
+
+    let time = time.elapsed_seconds() - log.started;
+    let (transform, velocity) = player.single();
+
+    for _ in flapped.iter() {
+        log.write(RunEvent::Flap {
+            time,
+            y: transform.translation.y,
+            velocity: velocity.y,
+        });
+    }
+    if score.is_changed() && score.0 > 0 {
+        log.write(RunEvent::Score {
+            time,
+            score: score.0,
+        });
+    }
+    for PlayerDiedEvent(crash) in died.iter() {
+        let (cause, pipe) = match crash.cause {
+            CrashCause::Floor => ("floor", None),
+            CrashCause::Pipe { center, .. } => ("pipe", Some(center)),
+            CrashCause::Hazard => ("hazard", None),
+            CrashCause::Ceiling => ("ceiling", None),
+        };
+        log.write(RunEvent::Death {
+            time,
+            score: score.0,
+            cause,
+            player_y: crash.player.y,
+            pipe_x: pipe.map(|pipe| pipe.x),
+            pipe_y: pipe.map(|pipe| pipe.y),
+        });
+    }
+}
+
+/// Appends newline-delimited JSON for every run start, flap, point and death to
+/// `path`, for looking into runs after the fact.
+pub struct EventLogPlugin {
+    pub path: PathBuf,
+}
+
+impl Plugin for EventLogPlugin {
+    fn build(&self, app: &mut App) {
+        let file = match OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+        {
+            Ok(file) => file,
+            Err(err) => {
+                error!("Failed to open event log {}: {err}", self.path.display());
+                return;
+            }
+        };
+
+        app.insert_resource(EventLog {
+            file: LineWriter::new(file),
+            started: 0.,
+            start_pending: false,
+        })
+        .add_system(start_run_system.in_schedule(OnEnter(GameState::InGame)))
+        .add_system(
+            log_run_events_system
+                .in_base_set(CoreSet::PostUpdate)
+                .run_if(in_state(GameState::InGame)),
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use bevy::prelude::*;
+
+use crate::{
+    mode::endless_run,
+    score::{GapCrossed, Score, ScoredEvent},
+    GameState, FONT, WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+
+/// Centred passes in a row that set off a fever.
+const STREAK_FOR_FEVER: u32 = 5;
+/// A pass counts as centred if the player is within this fraction of the gap
+/// from its middle.
+const CENTRE_TOLERANCE: f32 = 0.15;
+const FEVER_SECS: f32 = 10.;
+const FEVER_MULTIPLIER: u32 = 2;
+const FEVER_COLOR: Color = Color::rgb(1., 0.4, 0.1);
+
+/// Centred passes since the last off-centre one.
+#[derive(Resource, Default)]
+struct PassStreak(u32);
+
+/// Present while a fever is running and points are multiplied. Music and other
+/// effects can key off this resource to intensify for the duration.
+#[derive(Resource)]
+pub struct Fever {
+    timer: Timer,
+}
+
+impl Fever {
+    pub fn secs_left(&self) -> f32 {
+        self.timer.remaining_secs()
+    }
+}
+
+#[derive(Component)]
+struct FeverTint;
+
+#[derive(Component)]
+struct FeverText;
+
+fn streak_system(
+    mut commands: Commands,
+    mut crossed: EventReader<GapCrossed>,
+    mut streak: ResMut<PassStreak>,
+    fever: Option<Res<Fever>>,
+) {
+    for crossing in crossed.iter() {
+        if crossing.offset.abs() > crossing.gap * CENTRE_TOLERANCE {
+            streak.0 = 0;
+            continue;
+        }
+
+        streak.0 += 1;
+        if streak.0 >= STREAK_FOR_FEVER && fever.is_none() {
+            streak.0 = 0;
+            commands.insert_resource(Fever {
+                timer: Timer::from_seconds(FEVER_SECS, TimerMode::Once),
+            });
+        }
+    }
+}
+
+/// Tops up every point scored during a fever to the multiplied amount.
+fn fever_score_system(
+    mut passed: EventReader<ScoredEvent>,
+    mut score: ResMut<Score>,
+    fever: Option<Res<Fever>>,
+) {
+    let passes = passed.iter().count() as u32;
+    if fever.is_some() && passes > 0 {
+        score.0 += passes * (FEVER_MULTIPLIER - 1);
+    }
+}
+
+fn tick_fever_system(mut commands: Commands, time: Res<Time>, mut fever: ResMut<Fever>) {
+    if fever.timer.tick(time.delta()).finished() {
+        commands.remove_resource::<Fever>();
+    }
+}
+
+fn stop_fever_system(mut commands: Commands, mut streak: ResMut<PassStreak>) {
+    commands.remove_resource::<Fever>();
+    streak.0 = 0;
+}
+
+fn spawn_fever_hud_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        FeverTint,
+        SpriteBundle {
+            sprite: Sprite {
+                color: FEVER_COLOR.with_a(0.),
+                custom_size: Some(Vec2::new(WINDOW_WIDTH, WINDOW_HEIGHT)),
+                ..Default::default()
+            },
+            // Above the pipes and floor, below the UI.
+            transform: Transform::from_xyz(0., 0., 15.),
+            ..Default::default()
+        },
+    ));
+    commands.spawn((
+        FeverText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 22.,
+                color: FEVER_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(90.),
+                right: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+/// Pulses the tint and shows the multiplier with the time left.
+fn update_fever_hud_system(
+    time: Res<Time>,
+    fever: Option<Res<Fever>>,
+    mut tint: Query<&mut Sprite, With<FeverTint>>,
+    mut text: Query<&mut Text, With<FeverText>>,
+) {
+    let alpha = fever
+        .as_ref()
+        .map_or(0., |_| 0.12 + 0.06 * (time.elapsed_seconds() * 6.).sin());
+    for mut sprite in tint.iter_mut() {
+        sprite.color.set_a(alpha);
+    }
+
+    let value = fever.map_or(String::new(), |fever| {
+        format!("FEVER x{FEVER_MULTIPLIER} {:.1}s", fever.secs_left())
+    });
+    for mut text in text.iter_mut() {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+pub struct FeverPlugin;
+
+impl Plugin for FeverPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PassStreak>()
+            .add_startup_system(spawn_fever_hud_system)
+            .add_systems(
+                (streak_system, fever_score_system)
+                    .distributive_run_if(endless_run)
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(
+                tick_fever_system
+                    .run_if(resource_exists::<Fever>())
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(stop_fever_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(stop_fever_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(update_fever_hud_system);
+    }
+}
@@ -0,0 +1,226 @@
+use bevy::{ecs::system::SystemParam, input::InputSystem, prelude::*};
+
+use crate::console::console_closed;
+
+const FOCUSED_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+
+/// Moving focus and activating the focused row, ahead of the menus reading
+/// them in the same frame.
+#[derive(SystemSet, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct FocusSet;
+
+/// Which row of the open menu has focus. Menus insert one along with their
+/// rows, remove it when they close, and read it instead of keeping track of a
+/// selection themselves.
+///
+/// Down and Tab move to the next row and Up and Shift+Tab to the previous one,
+/// or the D-pad on a gamepad. Enter or South activates the focused row.
+#[derive(Resource, Debug)]
+pub struct Focus {
+    pub index: usize,
+    len: usize,
+    activated: bool,
+}
+
+impl Focus {
+    pub fn new(len: usize) -> Self {
+        Self::at(0, len)
+    }
+
+    pub fn at(index: usize, len: usize) -> Self {
+        Self {
+            index: index.min(len.saturating_sub(1)),
+            len,
+            activated: false,
+        }
+    }
+
+    /// For menus whose rows come and go while open.
+    pub fn set_len(&mut self, len: usize) {
+        self.len = len;
+        self.index = self.index.min(len.saturating_sub(1));
+    }
+
+    /// Whether the focused row was activated this frame.
+    pub fn activated(&self) -> bool {
+        self.activated
+    }
+
+    fn step(&mut self, step: isize) {
+        if self.len > 0 {
+            self.index = (self.index as isize + step).rem_euclid(self.len as isize) as usize;
+        }
+    }
+}
+
+/// A row of a menu, highlighted while it's the [`Focus`]ed one.
+#[derive(Component)]
+pub struct Focusable(pub usize);
+
+/// Key that does what the row is for. Activating the row presses it, so menus
+/// whose rows stand for shortcuts don't need to handle activation twice.
+#[derive(Component)]
+pub struct Shortcut(pub KeyCode);
+
+/// The parts of menu input that are up to each menu: changing the value on the
+/// focused row and going back.
+#[derive(SystemParam)]
+pub struct MenuInput<'w> {
+    keyboard_input: Res<'w, Input<KeyCode>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, Input<GamepadButton>>,
+}
+
+impl MenuInput<'_> {
+    fn gamepad_just_pressed(&self, button: GamepadButtonType) -> bool {
+        self.gamepads.iter().any(|gamepad| {
+            self.gamepad_buttons
+                .just_pressed(GamepadButton::new(gamepad, button))
+        })
+    }
+
+    /// 1 for Right, -1 for Left and 0 for neither.
+    pub fn adjustment(&self) -> isize {
+        if self.keyboard_input.just_pressed(KeyCode::Right)
+            || self.gamepad_just_pressed(GamepadButtonType::DPadRight)
+        {
+            1
+        } else if self.keyboard_input.just_pressed(KeyCode::Left)
+            || self.gamepad_just_pressed(GamepadButtonType::DPadLeft)
+        {
+            -1
+        } else {
+            0
+        }
+    }
+
+    /// Esc, or East on a gamepad.
+    pub fn back(&self) -> bool {
+        self.keyboard_input.just_pressed(KeyCode::Escape)
+            || self.gamepad_just_pressed(GamepadButtonType::East)
+    }
+}
+
+pub fn remove_focus_system(mut commands: Commands) {
+    commands.remove_resource::<Focus>();
+}
+
+/// Moves focus and activates the focused row. South is used up when it
+/// activates a row, so it doesn't also count as a flap.
+fn update_focus_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    gamepads: Res<Gamepads>,
+    mut gamepad_buttons: ResMut<Input<GamepadButton>>,
+    mut focus: ResMut<Focus>,
+) {
+    let mut gamepad_just_pressed = |button, consume| {
+        gamepads.iter().any(|gamepad| {
+            let button = GamepadButton::new(gamepad, button);
+            if consume {
+                gamepad_buttons.clear_just_pressed(button)
+            } else {
+                gamepad_buttons.just_pressed(button)
+            }
+        })
+    };
+
+    let shift = keyboard_input.any_pressed([KeyCode::LShift, KeyCode::RShift]);
+    let tab = keyboard_input.just_pressed(KeyCode::Tab);
+    if keyboard_input.just_pressed(KeyCode::Down)
+        || tab && !shift
+        || gamepad_just_pressed(GamepadButtonType::DPadDown, false)
+    {
+        focus.step(1);
+    } else if keyboard_input.just_pressed(KeyCode::Up)
+        || tab && shift
+        || gamepad_just_pressed(GamepadButtonType::DPadUp, false)
+    {
+        focus.step(-1);
+    }
+
+    let activated = keyboard_input.any_just_pressed([KeyCode::Return, KeyCode::NumpadEnter])
+        || gamepad_just_pressed(GamepadButtonType::South, true);
+    if focus.activated != activated {
+        // Only a change of row is worth redrawing menus for.
+        focus.bypass_change_detection().activated = activated;
+    }
+}
+
+/// Shortcut pressed for an activated row, let go of on the next frame.
+#[derive(Resource, Default)]
+struct PressedShortcut(Option<KeyCode>);
+
+/// Runs whether or not a menu is still open, since activating a row often
+/// closes it.
+fn release_shortcut_system(
+    mut pressed: ResMut<PressedShortcut>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+) {
+    // Reset rather than released, so it doesn't count as letting go of the key.
+    if let Some(key) = pressed.0.take() {
+        keyboard_input.reset(key);
+    }
+}
+
+fn press_shortcut_system(
+    focus: Res<Focus>,
+    rows: Query<(&Focusable, &Shortcut)>,
+    mut pressed: ResMut<PressedShortcut>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+) {
+    if !focus.activated() {
+        return;
+    }
+    let shortcut = rows
+        .iter()
+        .find(|(row, _)| row.0 == focus.index)
+        .map(|(_, shortcut)| shortcut.0);
+    if let Some(key) = shortcut {
+        keyboard_input.press(key);
+        pressed.0 = Some(key);
+    }
+}
+
+/// Colours the focused row, also after a menu rewrites its rows' text.
+fn highlight_focus_system(focus: Res<Focus>, mut rows: Query<(&Focusable, &mut Text)>) {
+    for (row, mut text) in rows.iter_mut() {
+        let color = if row.0 == focus.index {
+            FOCUSED_COLOR
+        } else {
+            Color::WHITE
+        };
+        if text
+            .sections
+            .iter()
+            .any(|section| section.style.color != color)
+        {
+            for section in text.sections.iter_mut() {
+                section.style.color = color;
+            }
+        }
+    }
+}
+
+/// Keyboard and gamepad navigation shared by the menus: moving focus between
+/// rows, highlighting the focused one and activating it.
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PressedShortcut>()
+            .configure_set(FocusSet.in_base_set(CoreSet::PreUpdate).after(InputSystem))
+            .add_system(
+                release_shortcut_system
+                    .in_set(FocusSet)
+                    .before(update_focus_system),
+            )
+            .add_systems(
+                (update_focus_system, press_shortcut_system)
+                    .chain()
+                    .in_set(FocusSet)
+                    .distributive_run_if(resource_exists::<Focus>())
+                    .distributive_run_if(console_closed),
+            )
+            .add_system(highlight_focus_system.run_if(resource_exists::<Focus>()));
+    }
+}
@@ -0,0 +1,125 @@
+use bevy::{
+    prelude::*,
+    render::render_resource::{Extent3d, TextureDimension, TextureFormat},
+};
+
+use crate::{
+    modifiers::{ActiveModifiers, Modifier},
+    settings::Settings,
+    GameState, GameplaySet, Player,
+};
+
+/// Size of the generated hole texture.
+const HOLE_TEXELS: u32 = 128;
+/// Fraction of the radius over which the edge of the hole fades out.
+const SOFT_EDGE: f32 = 0.25;
+/// How far the darkness reaches past the hole, in radii. Enough to cover any
+/// window from anywhere the bird can be, even with the smallest radius.
+const REACH: f32 = 100.;
+/// Above the world and the bird, below screen tints and the UI.
+const MASK_Z: f32 = 14.;
+
+/// Darkness covering everything but a circle with a radius of one, scaled to the
+/// radius in [`Settings::fog_of_war_radius`] and kept centred on the bird.
+#[derive(Component)]
+struct FogOfWarMask;
+
+/// Black with a round, soft-edged hole that fills the texture.
+fn hole_image() -> Image {
+    let half = HOLE_TEXELS as f32 / 2.;
+    let mut data = Vec::with_capacity((HOLE_TEXELS * HOLE_TEXELS * 4) as usize);
+    for y in 0..HOLE_TEXELS {
+        for x in 0..HOLE_TEXELS {
+            let offset = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - half;
+            let edge = (offset.length() / half - (1. - SOFT_EDGE)) / SOFT_EDGE;
+            let alpha = edge.clamp(0., 1.);
+            data.extend_from_slice(&[0, 0, 0, (alpha * 255.) as u8]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: HOLE_TEXELS,
+            height: HOLE_TEXELS,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+    )
+}
+
+fn spawn_mask_system(mut commands: Commands, mut images: ResMut<Assets<Image>>) {
+    let darkness = |center: Vec2, size: Vec2| SpriteBundle {
+        sprite: Sprite {
+            color: Color::BLACK,
+            custom_size: Some(size),
+            ..Default::default()
+        },
+        transform: Transform::from_translation(center.extend(0.)),
+        ..Default::default()
+    };
+    let side = 1. + REACH / 2.;
+
+    commands
+        .spawn((
+            FogOfWarMask,
+            SpriteBundle {
+                texture: images.add(hole_image()),
+                sprite: Sprite {
+                    custom_size: Some(Vec2::splat(2.)),
+                    ..Default::default()
+                },
+                transform: Transform::from_xyz(0., 0., MASK_Z),
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(darkness(Vec2::new(-side, 0.), Vec2::new(REACH, 2.)));
+            parent.spawn(darkness(Vec2::new(side, 0.), Vec2::new(REACH, 2.)));
+            parent.spawn(darkness(
+                Vec2::new(0., side),
+                Vec2::new(2. + REACH * 2., REACH),
+            ));
+            parent.spawn(darkness(
+                Vec2::new(0., -side),
+                Vec2::new(2. + REACH * 2., REACH),
+            ));
+        });
+}
+
+/// Shows the mask over runs played with [`Modifier::FogOfWar`], following the
+/// bird until the run is over.
+fn follow_bird_system(
+    modifiers: Res<ActiveModifiers>,
+    settings: Res<Settings>,
+    state: Res<State<GameState>>,
+    player: Query<&Transform, (With<Player>, Without<FogOfWarMask>)>,
+    mut mask: Query<(&mut Transform, &mut Visibility), With<FogOfWarMask>>,
+) {
+    let shown = modifiers.is_active(Modifier::FogOfWar)
+        && matches!(state.0, GameState::InGame | GameState::GameOver);
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+
+    for (mut transform, mut visibility) in mask.iter_mut() {
+        *visibility = if shown {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        transform.translation = player.translation.truncate().extend(MASK_Z);
+        transform.scale = Vec3::new(settings.fog_of_war_radius, settings.fog_of_war_radius, 1.);
+    }
+}
+
+pub struct FogOfWarPlugin;
+
+impl Plugin for FogOfWarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_mask_system)
+            .add_system(follow_bird_system.in_set(GameplaySet::Presentation));
+    }
+}
@@ -0,0 +1,63 @@
+use std::time::Instant;
+
+use bevy::{
+    prelude::*,
+    window::{PresentMode, PrimaryWindow},
+};
+use flappy_core::settings::Settings;
+
+pub fn present_mode(vsync: bool) -> PresentMode {
+    if vsync {
+        PresentMode::AutoVsync
+    } else {
+        PresentMode::AutoNoVsync
+    }
+}
+
+fn apply_vsync_system(
+    settings: Res<Settings>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !settings.is_changed() {
+        return;
+    }
+
+    let mode = present_mode(settings.vsync);
+    for mut window in windows.iter_mut() {
+        if window.present_mode != mode {
+            window.present_mode = mode;
+        }
+    }
+}
+
+/// Sleeps off what's left of each frame's share of a second once everything
+/// else has run. Frames are timed against a steady deadline rather than from
+/// the end of the last sleep, so oversleeping doesn't lower the frame rate.
+fn limit_frame_rate_system(settings: Res<Settings>, mut deadline: Local<Option<Instant>>) {
+    let Some(frame_time) = settings.fps_cap.frame_time() else {
+        *deadline = None;
+        return;
+    };
+
+    let now = Instant::now();
+    let next = match *deadline {
+        Some(next) if next > now => {
+            std::thread::sleep(next - now);
+            next
+        }
+        // A slow frame, or the first one: start timing afresh.
+        _ => now,
+    };
+    *deadline = Some(next + frame_time);
+}
+
+/// Vsync and an optional cap on the frame rate. Gameplay is timed by the
+/// frame's delta, so it plays the same at any cap.
+pub struct FrameLimitPlugin;
+
+impl Plugin for FrameLimitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(apply_vsync_system)
+            .add_system(limit_frame_rate_system.in_base_set(CoreSet::Last));
+    }
+}
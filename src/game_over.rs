@@ -0,0 +1,139 @@
+use bevy::prelude::*;
+
+use crate::{
+    checkpoint::Checkpoints,
+    difficulty::{Difficulty, ASSIST_COLOR},
+    modifiers::ActiveModifiers,
+    score::Score,
+    speedrun::{format_time, Speedrun},
+    DespawnOnExit, GameState, FONT,
+};
+
+/// Only the most recent splits fit on the screen.
+const SHOWN_SPLITS: usize = 5;
+
+/// Keeps a flap that was mashed right before dying from skipping the screen.
+const INPUT_DELAY_SECS: f32 = 0.5;
+
+/// When the game over screen was opened, in seconds since startup.
+#[derive(Resource)]
+struct GameOverSince(f32);
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_game_over_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    score: Res<Score>,
+    checkpoints: Res<Checkpoints>,
+    modifiers: Res<ActiveModifiers>,
+    difficulty: Res<Difficulty>,
+    speedrun: Option<Res<Speedrun>>,
+) {
+    commands.insert_resource(GameOverSince(time.elapsed_seconds()));
+
+    let font = asset_server.load(FONT);
+    let style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::GameOver),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(12.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.4).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Game over", style(40.)));
+            parent.spawn(TextBundle::from_section(
+                format!("Score: {}", score.0),
+                style(24.),
+            ));
+            if modifiers.any() {
+                parent.spawn(TextBundle::from_section(
+                    format!("Modified run: {}", modifiers.describe()),
+                    TextStyle {
+                        color: Color::YELLOW,
+                        ..style(16.)
+                    },
+                ));
+            }
+            if difficulty.assist.used {
+                parent.spawn(TextBundle::from_section(
+                    "Assisted run: the gap was widened",
+                    TextStyle {
+                        color: ASSIST_COLOR,
+                        ..style(16.)
+                    },
+                ));
+            }
+            if let Some(speedrun) = speedrun.filter(|speedrun| !speedrun.splits.is_empty()) {
+                let skipped = speedrun.splits.len().saturating_sub(SHOWN_SPLITS);
+                let splits = speedrun
+                    .splits
+                    .iter()
+                    .skip(skipped)
+                    .map(|split| {
+                        let delta = split
+                            .delta
+                            .map_or("PB".to_string(), |delta| format!("{delta:+.2}"));
+                        format!(
+                            "{:>4}  {}  {delta:>6}",
+                            split.points,
+                            format_time(split.time)
+                        )
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n");
+                parent.spawn(TextBundle::from_section(splits, style(18.)));
+            }
+            if let Some(checkpoint) = checkpoints.resumable() {
+                parent.spawn(TextBundle::from_section(
+                    format!("C: continue from {checkpoint}"),
+                    style(18.),
+                ));
+            }
+            parent.spawn(TextBundle::from_section("Space: menu", style(18.)));
+        });
+}
+
+fn remove_game_over_since_system(mut commands: Commands) {
+    commands.remove_resource::<GameOverSince>();
+}
+
+fn game_over_input_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    time: Res<Time>,
+    since: Res<GameOverSince>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if time.elapsed_seconds() - since.0 < INPUT_DELAY_SECS {
+        return;
+    }
+
+    if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::Return]) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+pub struct GameOverPlugin;
+
+impl Plugin for GameOverPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(spawn_game_over_ui_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(remove_game_over_since_system.in_schedule(OnExit(GameState::GameOver)))
+            .add_system(game_over_input_system.in_set(OnUpdate(GameState::GameOver)));
+    }
+}
@@ -0,0 +1,117 @@
+use bevy::prelude::*;
+
+use crate::{
+    score::GapCrossed,
+    tween::{Ease, Translation, Tween},
+    GameState, GameplaySet, Player, RunScoped, FONT,
+};
+
+/// Passes within these fractions of the gap from its centre grade as perfect
+/// and good. Anything else is only OK.
+const PERFECT_TOLERANCE: f32 = 0.05;
+const GOOD_TOLERANCE: f32 = 0.15;
+const POPUP_SECS: f32 = 0.6;
+/// How far above the bird popups appear, and how much further they float up.
+const POPUP_OFFSET: f32 = 40.;
+const POPUP_RISE: f32 = 40.;
+
+/// How close to the centre of a gap it was flown through.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PassGrade {
+    Perfect,
+    Good,
+    Ok,
+}
+
+impl PassGrade {
+    fn of(crossing: &GapCrossed) -> Self {
+        let off_centre = crossing.offset.abs() / crossing.gap;
+        if off_centre <= PERFECT_TOLERANCE {
+            PassGrade::Perfect
+        } else if off_centre <= GOOD_TOLERANCE {
+            PassGrade::Good
+        } else {
+            PassGrade::Ok
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            PassGrade::Perfect => "Perfect!",
+            PassGrade::Good => "Good",
+            PassGrade::Ok => "OK",
+        }
+    }
+
+    fn color(self) -> Color {
+        match self {
+            PassGrade::Perfect => Color::rgb(1., 0.85, 0.2),
+            PassGrade::Good => Color::rgb(0.5, 1., 0.5),
+            PassGrade::Ok => Color::WHITE,
+        }
+    }
+}
+
+/// Sent with the grade of every gap flown through.
+pub struct PassGraded(pub PassGrade);
+
+fn grade_passes_system(mut crossed: EventReader<GapCrossed>, mut graded: EventWriter<PassGraded>) {
+    for crossing in crossed.iter() {
+        graded.send(PassGraded(PassGrade::of(crossing)));
+    }
+}
+
+/// Floats each grade up from just above the bird.
+fn spawn_popup_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut graded: EventReader<PassGraded>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+
+    for PassGraded(grade) in graded.iter() {
+        let start = player.translation.truncate().extend(20.) + Vec3::Y * POPUP_OFFSET;
+        commands.spawn((
+            RunScoped,
+            Tween::new(
+                Translation {
+                    start,
+                    end: start + Vec3::Y * POPUP_RISE,
+                },
+                POPUP_SECS,
+                Ease::QuadOut,
+            )
+            .despawning(),
+            Text2dBundle {
+                text: Text::from_section(
+                    grade.label(),
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 24.,
+                        color: grade.color(),
+                    },
+                ),
+                transform: Transform::from_translation(start),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+/// Grades every pass by how close to the centre of the gap it was.
+pub struct GradingPlugin;
+
+impl Plugin for GradingPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<PassGraded>().add_systems(
+            (
+                grade_passes_system.in_set(GameplaySet::Scoring),
+                spawn_popup_system.in_set(GameplaySet::Presentation),
+            )
+                .in_set(OnUpdate(GameState::InGame)),
+        );
+    }
+}
@@ -0,0 +1,196 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    bonus::BonusStage, hazard::Hazard, mode::endless_run, resolution::WorldScale, score::Score,
+    scrolls_horizontally, AnimationIndices, AnimationTimer, DespawnWhenOffscreen, GameState,
+    RunScoped, RunSeed, Velocity, WorldDirection, FLOOR_HEIGHT, SCALE, SPEED, WINDOW_HEIGHT,
+    WINDOW_WIDTH,
+};
+
+/// Ground hazards only show up once the run has got going.
+const MIN_SCORE: u32 = 5;
+/// Seconds between ground hazards, picked at random from this range each time.
+const SPAWN_INTERVAL: (f32, f32) = (4., 9.);
+/// Chance of a cat rather than spikes.
+const CAT_CHANCE: f64 = 1. / 3.;
+/// How much faster than the floor a cat walks towards the bird.
+const CAT_WALK_SPEED: f32 = SPEED * 0.3;
+/// Size of a frame in `ground_hazards.png`, which holds the spikes and then two
+/// frames of a walking cat.
+const FRAME_SIZE: Vec2 = Vec2::new(16., 12.);
+/// A little smaller than the sprites, so only flying right into one counts.
+const SPIKES_HITBOX: Vec2 = Vec2::new(44., 26.);
+const CAT_HITBOX: Vec2 = Vec2::new(40., 28.);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum GroundHazard {
+    Spikes,
+    /// Walks along the floor towards the bird, a little faster than it scrolls.
+    Cat,
+}
+
+impl GroundHazard {
+    fn hitbox(self) -> Vec2 {
+        match self {
+            GroundHazard::Spikes => SPIKES_HITBOX,
+            GroundHazard::Cat => CAT_HITBOX,
+        }
+    }
+
+    fn frames(self) -> AnimationIndices {
+        match self {
+            GroundHazard::Spikes => AnimationIndices { first: 0, last: 0 },
+            GroundHazard::Cat => AnimationIndices { first: 1, last: 2 },
+        }
+    }
+
+    fn velocity(self, direction: WorldDirection) -> Velocity {
+        let walk = match self {
+            GroundHazard::Spikes => 0.,
+            GroundHazard::Cat => CAT_WALK_SPEED,
+        };
+        Velocity {
+            x: -(SPEED + walk) * direction.sign(),
+            y: 0.,
+        }
+    }
+}
+
+#[derive(Resource)]
+struct GroundHazardAtlas(Handle<TextureAtlas>);
+
+/// Times and picks ground hazards, on a clock like the enemies and so kept
+/// apart from [`GameRng`](crate::GameRng) the same way.
+#[derive(Resource)]
+struct GroundHazardRng(StdRng);
+
+impl Default for GroundHazardRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+#[derive(Resource)]
+struct GroundHazardSpawner(Timer);
+
+impl GroundHazardSpawner {
+    fn new(rng: &mut impl Rng) -> Self {
+        Self(Timer::new(Self::random_interval(rng), TimerMode::Once))
+    }
+
+    fn random_interval(rng: &mut impl Rng) -> Duration {
+        Duration::from_secs_f32(rng.gen_range(SPAWN_INTERVAL.0..SPAWN_INTERVAL.1))
+    }
+}
+
+impl FromWorld for GroundHazardSpawner {
+    fn from_world(world: &mut World) -> Self {
+        Self::new(&mut world.resource_mut::<GroundHazardRng>().0)
+    }
+}
+
+/// Cut again from the new set whenever the [`WorldScale`] changes, like the
+/// enemies' atlas.
+fn load_ground_hazard_atlas_system(
+    mut commands: Commands,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+) {
+    let texture_atlas = world_scale.atlas(&asset_server, "ground_hazards.png", FRAME_SIZE, 3, 1);
+    commands.insert_resource(GroundHazardAtlas(texture_atlases.add(texture_atlas)));
+}
+
+/// Sends spikes or a cat along the floor every so often, standing on it so
+/// skimming the bottom of the screen is no longer safe.
+#[allow(clippy::too_many_arguments)]
+fn ground_hazard_spawner_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    score: Res<Score>,
+    atlas: Res<GroundHazardAtlas>,
+    world_scale: Res<WorldScale>,
+    direction: Res<WorldDirection>,
+    mut spawner: ResMut<GroundHazardSpawner>,
+    mut rng: ResMut<GroundHazardRng>,
+) {
+    if score.0 < MIN_SCORE || !spawner.0.tick(time.delta()).finished() {
+        return;
+    }
+
+    spawner
+        .0
+        .set_duration(GroundHazardSpawner::random_interval(&mut rng.0));
+    spawner.0.reset();
+
+    let hazard = if rng.0.gen_bool(CAT_CHANCE) {
+        GroundHazard::Cat
+    } else {
+        GroundHazard::Spikes
+    };
+    let size = FRAME_SIZE * SCALE.truncate();
+    let frames = hazard.frames();
+    commands.spawn((
+        RunScoped,
+        Hazard {
+            size: hazard.hitbox(),
+        },
+        hazard.velocity(*direction),
+        DespawnWhenOffscreen { margin: size.x },
+        SpriteSheetBundle {
+            texture_atlas: atlas.0.clone(),
+            sprite: TextureAtlasSprite {
+                index: frames.first,
+                flip_x: *direction == WorldDirection::Left,
+                ..Default::default()
+            },
+            // The hitbox stands on the floor, and the bottom of the sprite tucks
+            // in behind it.
+            transform: Transform::from_xyz(
+                direction.mirror_x(WINDOW_WIDTH / 2. + size.x, 0.),
+                -WINDOW_HEIGHT / 2. + FLOOR_HEIGHT + hazard.hitbox().y / 2.,
+                5.,
+            )
+            .with_scale(world_scale.sprite()),
+            ..Default::default()
+        },
+        frames,
+        AnimationTimer(Timer::from_seconds(0.2, TimerMode::Repeating)),
+    ));
+}
+
+/// Starts the ground hazards over from the run's seed whenever it's picked or
+/// restored.
+fn seed_ground_hazards_system(
+    seed: Res<RunSeed>,
+    mut rng: ResMut<GroundHazardRng>,
+    mut spawner: ResMut<GroundHazardSpawner>,
+) {
+    rng.0 = StdRng::seed_from_u64(seed.current);
+    *spawner = GroundHazardSpawner::new(&mut rng.0);
+}
+
+pub struct GroundHazardPlugin;
+
+impl Plugin for GroundHazardPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GroundHazardRng>()
+            .init_resource::<GroundHazardSpawner>()
+            .add_system(load_ground_hazard_atlas_system.run_if(resource_changed::<WorldScale>()))
+            .add_system(
+                seed_ground_hazards_system
+                    .before(ground_hazard_spawner_system)
+                    .run_if(resource_changed::<RunSeed>()),
+            )
+            .add_system(
+                ground_hazard_spawner_system
+                    .run_if(endless_run)
+                    .run_if(scrolls_horizontally)
+                    .run_if(not(resource_exists::<BonusStage>()))
+                    .in_set(OnUpdate(GameState::InGame)),
+            );
+    }
+}
@@ -0,0 +1,28 @@
+use bevy::prelude::*;
+
+/// Anything besides pipes and the floor that costs a life on contact. The box
+/// is `size` centred on the entity's translation and turns with its rotation.
+#[derive(Component)]
+pub struct Hazard {
+    pub size: Vec2,
+}
+
+impl Hazard {
+    /// Separating axis test between this hazard and an axis-aligned box.
+    pub fn overlaps(&self, transform: &Transform, center: Vec3, size: Vec2) -> bool {
+        let offset = (transform.translation - center).truncate();
+        let half = size / 2.;
+        let hazard_half = self.size / 2.;
+        let u = transform.rotation.mul_vec3(Vec3::X).truncate();
+        let v = transform.rotation.mul_vec3(Vec3::Y).truncate();
+
+        [Vec2::X, Vec2::Y, u, v].into_iter().all(|axis| {
+            let distance = offset.dot(axis).abs();
+            let radius = half.x * axis.x.abs()
+                + half.y * axis.y.abs()
+                + hazard_half.x * u.dot(axis).abs()
+                + hazard_half.y * v.dot(axis).abs();
+            distance <= radius
+        })
+    }
+}
@@ -0,0 +1,235 @@
+use std::{collections::VecDeque, fs::File, path::PathBuf, thread::JoinHandle};
+
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    screenshot::{
+        capture_frame, screenshots_dir, spawn_toast, timestamp, CapturePurpose, FrameCaptured,
+    },
+    DespawnOnExit, GameState, FONT,
+};
+
+const FRAMES_PER_SEC: u32 = 10;
+const HIGHLIGHT_SECS: u32 = 5;
+/// Frames are stored at a fraction of the window size to keep the buffer small.
+const DOWNSCALE: u32 = 2;
+
+struct HighlightFrame {
+    width: u32,
+    height: u32,
+    data: Vec<u8>,
+}
+
+impl HighlightFrame {
+    fn downscaled(frame: &FrameCaptured) -> Self {
+        let width = frame.width / DOWNSCALE;
+        let height = frame.height / DOWNSCALE;
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .flat_map(|(x, y)| {
+                let index = ((y * DOWNSCALE * frame.width + x * DOWNSCALE) * 4) as usize;
+                frame.data[index..index + 4].iter().copied()
+            })
+            .collect();
+
+        Self {
+            width,
+            height,
+            data,
+        }
+    }
+}
+
+/// Ring buffer of the last few seconds of the current run.
+#[derive(Resource)]
+struct Highlight {
+    frames: VecDeque<HighlightFrame>,
+    timer: Timer,
+}
+
+impl Default for Highlight {
+    fn default() -> Self {
+        Self {
+            frames: VecDeque::new(),
+            timer: Timer::from_seconds(1. / FRAMES_PER_SEC as f32, TimerMode::Repeating),
+        }
+    }
+}
+
+/// GIF being encoded in the background.
+#[derive(Resource)]
+struct HighlightExport(Option<JoinHandle<Result<PathBuf, String>>>);
+
+#[derive(Component)]
+struct HighlightHint;
+
+fn record_highlight_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut highlight: ResMut<Highlight>,
+    mut images: ResMut<Assets<Image>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    if !highlight.timer.tick(time.delta()).just_finished() {
+        return;
+    }
+
+    if let Ok(window) = windows.get_single() {
+        capture_frame(
+            &mut commands,
+            &mut images,
+            window,
+            CapturePurpose::Highlight,
+        );
+    }
+}
+
+fn store_highlight_frames_system(
+    mut captured: EventReader<FrameCaptured>,
+    mut highlight: ResMut<Highlight>,
+) {
+    for frame in captured.iter() {
+        if !matches!(frame.purpose, CapturePurpose::Highlight) {
+            continue;
+        }
+
+        highlight
+            .frames
+            .push_back(HighlightFrame::downscaled(frame));
+        if highlight.frames.len() > (FRAMES_PER_SEC * HIGHLIGHT_SECS) as usize {
+            highlight.frames.pop_front();
+        }
+    }
+}
+
+fn clear_highlight_system(mut highlight: ResMut<Highlight>) {
+    highlight.frames.clear();
+}
+
+fn encode_gif(frames: Vec<HighlightFrame>, path: PathBuf) -> Result<PathBuf, String> {
+    let (width, height) = frames
+        .first()
+        .map(|frame| (frame.width as u16, frame.height as u16))
+        .ok_or("no frames recorded")?;
+
+    std::fs::create_dir_all(screenshots_dir()).map_err(|err| err.to_string())?;
+    let file = File::create(&path).map_err(|err| err.to_string())?;
+    let mut encoder = gif::Encoder::new(file, width, height, &[]).map_err(|err| err.to_string())?;
+    encoder
+        .set_repeat(gif::Repeat::Infinite)
+        .map_err(|err| err.to_string())?;
+
+    for mut frame in frames {
+        let mut gif_frame = gif::Frame::from_rgba_speed(width, height, &mut frame.data, 10);
+        gif_frame.delay = (100 / FRAMES_PER_SEC) as u16;
+        encoder
+            .write_frame(&gif_frame)
+            .map_err(|err| err.to_string())?;
+    }
+
+    Ok(path)
+}
+
+fn spawn_highlight_hint_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    highlight: Res<Highlight>,
+) {
+    if highlight.frames.is_empty() {
+        return;
+    }
+
+    commands
+        .spawn((
+            HighlightHint,
+            DespawnOnExit(GameState::GameOver),
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::bottom(Val::Px(120.)),
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "G: save highlight GIF",
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 18.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+fn export_highlight_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut highlight: ResMut<Highlight>,
+    export: Option<Res<HighlightExport>>,
+    hints: Query<Entity, With<HighlightHint>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::G) || export.is_some() || highlight.frames.is_empty() {
+        return;
+    }
+
+    let frames = highlight.frames.drain(..).collect();
+    let path = screenshots_dir().join(format!("highlight-{}.gif", timestamp()));
+    commands.insert_resource(HighlightExport(Some(std::thread::spawn(move || {
+        encode_gif(frames, path)
+    }))));
+
+    for entity in hints.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn finish_highlight_export_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut export: ResMut<HighlightExport>,
+) {
+    if !export.0.as_ref().is_some_and(|handle| handle.is_finished()) {
+        return;
+    }
+
+    let result = export.0.take().map(|handle| {
+        handle
+            .join()
+            .unwrap_or_else(|_| Err("encoder panicked".into()))
+    });
+    commands.remove_resource::<HighlightExport>();
+
+    let message = match result {
+        Some(Ok(path)) => {
+            let name = path.file_name().unwrap_or_default();
+            format!("Saved {}", name.to_string_lossy())
+        }
+        Some(Err(err)) => {
+            error!("Failed to export highlight: {err}");
+            "Highlight export failed".to_string()
+        }
+        None => return,
+    };
+    spawn_toast(&mut commands, &asset_server, message);
+}
+
+pub struct HighlightPlugin;
+
+impl Plugin for HighlightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Highlight>()
+            .add_system(record_highlight_system.in_set(OnUpdate(GameState::InGame)))
+            .add_system(store_highlight_frames_system)
+            .add_system(clear_highlight_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(spawn_highlight_hint_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(export_highlight_system.in_set(OnUpdate(GameState::GameOver)))
+            .add_system(
+                finish_highlight_export_system.run_if(resource_exists::<HighlightExport>()),
+            );
+    }
+}
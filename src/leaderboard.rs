@@ -0,0 +1,477 @@
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    daily::{civil_from_days, today},
+    difficulty::Difficulty,
+    focus::MenuInput,
+    marathon::marathon_seed,
+    mode::{endless_run, GameMode},
+    modifiers::ActiveModifiers,
+    score::Score,
+    share::seed_code,
+    storage,
+    tournament::Tournament,
+    DespawnOnExit, GameState, RunSeed, FONT,
+};
+
+/// Scores kept per category.
+const MAX_ENTRIES: usize = 10;
+const HIGHLIGHT_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+const WARNING_COLOR: Color = Color::rgb(1., 0.45, 0.35);
+/// Mixed into every entry's checksum, so a score edited in the file can't be
+/// made to match with a checksum tool alone.
+const CHECKSUM_KEY: &[u8] = b"flappy-leaderboard-7c1e";
+
+/// Groups of runs whose scores are ranked against each other.
+#[derive(Serialize, Deserialize, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum ScoreCategory {
+    Classic,
+    Daily,
+    Marathon,
+    Hardcore,
+}
+
+impl ScoreCategory {
+    const ALL: [ScoreCategory; 4] = [
+        ScoreCategory::Classic,
+        ScoreCategory::Daily,
+        ScoreCategory::Marathon,
+        ScoreCategory::Hardcore,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ScoreCategory::Classic => "Classic",
+            ScoreCategory::Daily => "Daily",
+            ScoreCategory::Marathon => "Marathon",
+            ScoreCategory::Hardcore => "Hardcore",
+        }
+    }
+
+    /// Where endless runs of `mode` are ranked, if anywhere.
+    pub fn for_mode(mode: GameMode) -> Option<Self> {
+        match mode {
+            GameMode::Classic => Some(ScoreCategory::Classic),
+            GameMode::Daily => Some(ScoreCategory::Daily),
+            GameMode::Marathon => Some(ScoreCategory::Marathon),
+            GameMode::Hardcore => Some(ScoreCategory::Hardcore),
+            GameMode::Lives | GameMode::Arcade | GameMode::Climb => None,
+        }
+    }
+
+    /// The seed every run in the category is played on today, for categories
+    /// where everyone gets the same layout.
+    fn shared_seed(self, day: i64) -> Option<u64> {
+        match self {
+            ScoreCategory::Daily => Some(day as u64),
+            ScoreCategory::Marathon => Some(marathon_seed(day)),
+            ScoreCategory::Classic | ScoreCategory::Hardcore => None,
+        }
+    }
+
+    fn step(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|category| *category == self);
+        let len = Self::ALL.len() as isize;
+        Self::ALL[(index.unwrap_or(0) as isize + step).rem_euclid(len) as usize]
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderboardEntry {
+    pub score: u32,
+    /// Day the run ended, counted as in [`today`].
+    pub day: i64,
+    pub seed: u64,
+    /// Names of the cheat modifiers the run was played with, if any.
+    #[serde(default)]
+    pub modifiers: String,
+    /// Whether the dynamic assist widened the gap during the run.
+    #[serde(default)]
+    pub assisted: bool,
+    /// From [`LeaderboardEntry::checksum`], so entries edited by hand can be
+    /// told apart.
+    #[serde(default)]
+    pub checksum: u64,
+}
+
+impl LeaderboardEntry {
+    fn new(
+        category: ScoreCategory,
+        score: u32,
+        day: i64,
+        seed: u64,
+        modifiers: String,
+        assisted: bool,
+    ) -> Self {
+        let mut entry = Self {
+            score,
+            day,
+            seed,
+            modifiers,
+            assisted,
+            checksum: 0,
+        };
+        entry.checksum = entry.checksum(category);
+        entry
+    }
+
+    /// FNV-1a of the entry's fields and its category, each prefixed with its
+    /// length so no two entries run together the same way, and keyed with
+    /// [`CHECKSUM_KEY`]. Not meant to hold off anyone determined, only to stop
+    /// casual edits to the file from showing up as scores.
+    fn checksum(&self, category: ScoreCategory) -> u64 {
+        let fields: [&[u8]; 7] = [
+            CHECKSUM_KEY,
+            category.name().as_bytes(),
+            &self.score.to_le_bytes(),
+            &self.day.to_le_bytes(),
+            &self.seed.to_le_bytes(),
+            self.modifiers.as_bytes(),
+            &[u8::from(self.assisted)],
+        ];
+        fnv1a(fields.iter().flat_map(|field| {
+            (field.len() as u64)
+                .to_le_bytes()
+                .into_iter()
+                .chain(field.iter().copied())
+        }))
+    }
+
+    /// The checksum as version 1 files had it, with the fields run together.
+    fn legacy_checksum(&self, category: ScoreCategory) -> u64 {
+        let assisted: &[u8] = if self.assisted { b"assisted" } else { b"" };
+        let fields: [&[u8]; 7] = [
+            CHECKSUM_KEY,
+            category.name().as_bytes(),
+            &self.score.to_le_bytes(),
+            &self.day.to_le_bytes(),
+            &self.seed.to_le_bytes(),
+            self.modifiers.as_bytes(),
+            assisted,
+        ];
+        fnv1a(fields.iter().flat_map(|field| field.iter().copied()))
+    }
+}
+
+fn fnv1a(bytes: impl Iterator<Item = u8>) -> u64 {
+    bytes.fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Best scores in each category, persisted as RON in the platform's data
+/// directory. Categories played on a shared seed only keep scores from the
+/// current one.
+#[derive(Resource, Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct Leaderboard {
+    pub categories: BTreeMap<ScoreCategory, Vec<LeaderboardEntry>>,
+    /// Entries dropped on loading since their checksum didn't match.
+    #[serde(skip)]
+    pub tampered: usize,
+}
+
+impl storage::SaveFile for Leaderboard {
+    const VERSION: u32 = 2;
+
+    /// Version 1 entries were checksummed the old way, or not at all if saved
+    /// before checksums were added. Those that check out are stamped with the
+    /// current checksum.
+    fn migrate(&mut self, version: u32) {
+        if version >= 2 {
+            return;
+        }
+        for (category, entries) in self.categories.iter_mut() {
+            let before = entries.len();
+            entries.retain_mut(|entry| {
+                let unedited =
+                    entry.checksum == 0 || entry.checksum == entry.legacy_checksum(*category);
+                entry.checksum = entry.checksum(*category);
+                unedited
+            });
+            self.tampered += before - entries.len();
+        }
+    }
+}
+
+impl Leaderboard {
+    const FILE: &'static str = "leaderboard.ron";
+
+    /// Loads the leaderboard, leaving out entries that have been edited.
+    pub fn load() -> Self {
+        let mut leaderboard: Self = storage::data_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default();
+
+        for (category, entries) in leaderboard.categories.iter_mut() {
+            let before = entries.len();
+            entries.retain(|entry| entry.checksum == entry.checksum(*category));
+            leaderboard.tampered += before - entries.len();
+        }
+        if leaderboard.tampered > 0 {
+            warn!(
+                "Ignoring {} leaderboard entries that have been edited",
+                leaderboard.tampered
+            );
+        }
+        leaderboard
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = storage::data_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+
+    /// Scores to show for `category` on `day`, best first.
+    pub fn entries(&self, category: ScoreCategory, day: i64) -> Vec<&LeaderboardEntry> {
+        let seed = category.shared_seed(day);
+        self.categories
+            .get(&category)
+            .into_iter()
+            .flatten()
+            .filter(|entry| seed.is_none_or(|seed| entry.seed == seed))
+            .collect()
+    }
+
+    /// Adds the entries of `other` that aren't here already, returning how many
+    /// were left out for having been edited. Entries from shared seeds other
+    /// than today's are left out too, since they'd push today's off the board.
+    pub fn merge(&mut self, other: &Leaderboard) -> usize {
+        let mut tampered = other.tampered;
+        for (category, entries) in &other.categories {
+            let shared_seed = category.shared_seed(today());
+            for entry in entries {
+                if entry.checksum != entry.checksum(*category) {
+                    tampered += 1;
+                    continue;
+                }
+                let stale = shared_seed.is_some_and(|seed| seed != entry.seed);
+                let known = self
+                    .categories
+                    .get(category)
+                    .is_some_and(|kept| kept.iter().any(|kept| kept.checksum == entry.checksum));
+                if !stale && !known {
+                    self.record(*category, entry.clone());
+                }
+            }
+        }
+        tampered
+    }
+
+    fn record(&mut self, category: ScoreCategory, entry: LeaderboardEntry) {
+        let entries = self.categories.entry(category).or_default();
+        if category.shared_seed(entry.day).is_some() {
+            entries.retain(|kept| kept.seed == entry.seed);
+        }
+        let rank = entries.partition_point(|kept| kept.score >= entry.score);
+        entries.insert(rank, entry);
+        entries.truncate(MAX_ENTRIES);
+    }
+}
+
+/// Category shown on the leaderboard screen.
+#[derive(Resource)]
+struct ShownCategory(ScoreCategory);
+
+#[derive(Component)]
+struct LeaderboardText;
+
+/// Ranks the finished run in its mode's category. Hot-seat tournament runs are
+/// left off, since they're played by whoever's turn it is.
+fn record_score_system(
+    mode: Res<GameMode>,
+    score: Res<Score>,
+    seed: Res<RunSeed>,
+    modifiers: Res<ActiveModifiers>,
+    difficulty: Res<Difficulty>,
+    mut leaderboard: ResMut<Leaderboard>,
+) {
+    let Some(category) = ScoreCategory::for_mode(*mode) else {
+        return;
+    };
+    if score.0 == 0 {
+        return;
+    }
+
+    leaderboard.record(
+        category,
+        LeaderboardEntry::new(
+            category,
+            score.0,
+            today(),
+            seed.current,
+            modifiers.describe(),
+            difficulty.assist.used,
+        ),
+    );
+    leaderboard.save();
+}
+
+fn open_leaderboard_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::K) {
+        next_state.set(GameState::Leaderboard);
+    }
+}
+
+fn spawn_leaderboard_ui_system(mut commands: Commands, mode: Res<GameMode>) {
+    let category = ScoreCategory::for_mode(*mode).unwrap_or(ScoreCategory::Classic);
+    commands.insert_resource(ShownCategory(category));
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Leaderboard),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                LeaderboardText,
+                TextBundle::from_sections([]).with_style(Style {
+                    max_size: Size::width(Val::Px(400.)),
+                    ..Default::default()
+                }),
+            ));
+        });
+}
+
+fn remove_shown_category_system(mut commands: Commands) {
+    commands.remove_resource::<ShownCategory>();
+}
+
+fn leaderboard_input_system(
+    menu_input: MenuInput,
+    mut shown: ResMut<ShownCategory>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if menu_input.back() {
+        next_state.set(GameState::Menu);
+    }
+    let step = menu_input.adjustment();
+    if step != 0 {
+        shown.0 = shown.0.step(step);
+    }
+}
+
+/// Lists the shown category's scores with the date and any modifiers of each,
+/// along with the seed everyone's playing on for shared seed categories.
+fn update_leaderboard_text_system(
+    shown: Res<ShownCategory>,
+    asset_server: Res<AssetServer>,
+    leaderboard: Res<Leaderboard>,
+    mut query: Query<&mut Text, With<LeaderboardText>>,
+    added: Query<(), Added<LeaderboardText>>,
+) {
+    if !shown.is_changed() && !leaderboard.is_changed() && added.is_empty() {
+        return;
+    }
+
+    let font = asset_server.load(FONT);
+    let section = |value: String, font_size, color| {
+        TextSection::new(
+            value,
+            TextStyle {
+                font: font.clone(),
+                font_size,
+                color,
+            },
+        )
+    };
+
+    let day = today();
+    let mut sections = vec![section(
+        format!("< {} >\n", shown.0.name()),
+        28.,
+        Color::WHITE,
+    )];
+    if let Some(seed) = shown.0.shared_seed(day) {
+        sections.push(section(
+            format!("Seed code: {}\n", seed_code(seed)),
+            16.,
+            HIGHLIGHT_COLOR,
+        ));
+    }
+    sections.push(section("\n".into(), 16., Color::WHITE));
+
+    let entries = leaderboard.entries(shown.0, day);
+    if entries.is_empty() {
+        sections.push(section("No scores yet\n".into(), 18., Color::GRAY));
+    }
+    for (rank, entry) in entries.iter().enumerate() {
+        let (year, month, day_of_month) = civil_from_days(entry.day);
+        let modifiers = if entry.modifiers.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", entry.modifiers)
+        };
+        let assisted = if entry.assisted { "  [assist]" } else { "" };
+        sections.push(section(
+            format!(
+                "{:>2}. {:>4}   {year}-{month:02}-{day_of_month:02}{modifiers}{assisted}\n",
+                rank + 1,
+                entry.score
+            ),
+            18.,
+            if rank == 0 {
+                HIGHLIGHT_COLOR
+            } else {
+                Color::WHITE
+            },
+        ));
+    }
+    if leaderboard.tampered > 0 {
+        sections.push(section(
+            format!(
+                "\nIgnored {} edited score(s) in the leaderboard file\n",
+                leaderboard.tampered
+            ),
+            16.,
+            WARNING_COLOR,
+        ));
+    }
+    sections.push(section(
+        "\nLeft/Right: category   Esc: back".into(),
+        16.,
+        Color::WHITE,
+    ));
+
+    for mut text in query.iter_mut() {
+        text.sections = sections.clone();
+    }
+}
+
+pub struct LeaderboardPlugin;
+
+impl Plugin for LeaderboardPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Leaderboard::load())
+            .add_system(
+                record_score_system
+                    .run_if(endless_run)
+                    .run_if(not(resource_exists::<Tournament>()))
+                    .in_schedule(OnEnter(GameState::GameOver)),
+            )
+            .add_system(open_leaderboard_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_leaderboard_ui_system.in_schedule(OnEnter(GameState::Leaderboard)))
+            .add_system(remove_shown_category_system.in_schedule(OnExit(GameState::Leaderboard)))
+            .add_systems(
+                (leaderboard_input_system, update_leaderboard_text_system)
+                    .chain()
+                    .in_set(OnUpdate(GameState::Leaderboard)),
+            );
+    }
+}
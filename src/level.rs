@@ -0,0 +1,247 @@
+use std::{fs, path::PathBuf};
+
+use bevy::{
+    asset::{AssetLoader, FileAssetIo, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    difficulty::Difficulty, frame_step, pipe_themes::PipeTheme, pool::PipePool, spawn_pipe_pair,
+    GameState, Pipe, PipeSpawnX, WorldDirection, PIPE_GAP, PIPE_SPAWN_X, SPEED,
+};
+
+/// Folder inside `assets/` that level files are loaded from.
+pub const LEVELS_FOLDER: &str = "levels";
+
+const MOVING_PIPE_AMPLITUDE: f32 = 60.;
+const MOVING_PIPE_FREQUENCY: f32 = 1.5;
+
+/// A finite, handcrafted sequence of pipes, stored as a `.level.ron` asset.
+#[derive(Serialize, Deserialize, TypeUuid, Clone, Default, Debug)]
+#[uuid = "81aee1c2-6109-43d3-a94c-3c0c924ef1b9"]
+pub struct Level {
+    pub name: String,
+    /// Sorted by [`PipeGap::distance`].
+    pub pipes: Vec<PipeGap>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Debug)]
+pub struct PipeGap {
+    /// How far the world has scrolled since the start of the level when the pipe
+    /// enters at `PIPE_SPAWN_X`.
+    pub distance: f32,
+    /// Height of the gap's lower edge.
+    pub gap_bottom: f32,
+    #[serde(default)]
+    pub kind: PipeKind,
+}
+
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum PipeKind {
+    #[default]
+    Normal,
+    /// A gap a quarter smaller than usual.
+    Narrow,
+    /// Bobs up and down around `gap_bottom`.
+    Moving,
+}
+
+impl PipeKind {
+    pub fn gap(self) -> f32 {
+        match self {
+            PipeKind::Narrow => PIPE_GAP * 0.75,
+            PipeKind::Normal | PipeKind::Moving => PIPE_GAP,
+        }
+    }
+}
+
+impl Level {
+    pub fn load(path: &PathBuf) -> Result<Self, String> {
+        let contents = fs::read_to_string(path).map_err(|err| err.to_string())?;
+        ron::from_str(&contents).map_err(|err| err.to_string())
+    }
+
+    pub fn save(&self, path: &PathBuf) -> Result<(), String> {
+        let contents =
+            ron::ser::to_string_pretty(self, Default::default()).map_err(|err| err.to_string())?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+        }
+        fs::write(path, contents).map_err(|err| err.to_string())
+    }
+
+    pub fn insert(&mut self, gap: PipeGap) {
+        let index = self
+            .pipes
+            .partition_point(|other| other.distance < gap.distance);
+        self.pipes.insert(index, gap);
+    }
+}
+
+pub fn levels_dir() -> PathBuf {
+    FileAssetIo::get_base_path()
+        .join("assets")
+        .join(LEVELS_FOLDER)
+}
+
+#[derive(Default)]
+struct LevelLoader;
+
+impl AssetLoader for LevelLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let level: Level = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(level));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.ron"]
+    }
+}
+
+/// Every level in [`LEVELS_FOLDER`], ordered by file name.
+#[derive(Resource, Default)]
+pub struct LevelLibrary {
+    pub levels: Vec<Handle<Level>>,
+}
+
+impl LevelLibrary {
+    pub fn refresh(&mut self, asset_server: &AssetServer) {
+        let mut levels: Vec<Handle<Level>> = match asset_server.load_folder(LEVELS_FOLDER) {
+            Ok(handles) => handles.into_iter().map(HandleUntyped::typed).collect(),
+            Err(err) => {
+                warn!("Failed to load levels: {err}");
+                Vec::new()
+            }
+        };
+        levels.sort_by_cached_key(|handle| {
+            asset_server
+                .get_handle_path(handle)
+                .map(|path| path.path().to_path_buf())
+        });
+
+        self.levels = levels;
+    }
+}
+
+/// Present while a [`Level`] is being played instead of the endless mode.
+#[derive(Resource)]
+pub struct LevelPlayback {
+    pub level: Level,
+    /// Index into [`LevelLibrary::levels`], if the level came from there.
+    pub source: Option<usize>,
+    distance: f32,
+    next_pipe: usize,
+}
+
+impl LevelPlayback {
+    pub fn new(level: Level, source: Option<usize>) -> Self {
+        Self {
+            level,
+            source,
+            distance: 0.,
+            next_pipe: 0,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct MovingPipe {
+    base_y: f32,
+    elapsed: f32,
+}
+
+#[allow(clippy::too_many_arguments)]
+fn level_playback_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    theme: Res<PipeTheme>,
+    direction: Res<WorldDirection>,
+    difficulty: Res<Difficulty>,
+    mut playback: ResMut<LevelPlayback>,
+    mut pool: ResMut<PipePool>,
+    pipes: Query<(), With<Pipe>>,
+    spawn_x: Res<PipeSpawnX>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Checked before spawning, since pipes spawned this frame don't show up in the query yet.
+    if playback.next_pipe == playback.level.pipes.len() && pipes.is_empty() {
+        next_state.set(GameState::LevelComplete);
+        return;
+    }
+
+    playback.distance += SPEED * difficulty.speed * frame_step(&time);
+
+    // Pipes that enter further out than `PIPE_SPAWN_X` spawn that much sooner,
+    // so they still reach the bird at the same distance.
+    let lead = spawn_x.0 - PIPE_SPAWN_X;
+    while let Some(gap) = playback.level.pipes.get(playback.next_pipe).copied() {
+        if gap.distance > playback.distance + lead {
+            break;
+        }
+
+        // Account for the distance scrolled past the pipe's spawn point this frame.
+        let x = PIPE_SPAWN_X - (playback.distance - gap.distance);
+        let gap_size = gap.kind.gap();
+        let pipes = spawn_pipe_pair(
+            &mut commands,
+            &mut pool,
+            &theme,
+            *direction,
+            x,
+            gap.gap_bottom,
+            gap_size,
+        );
+
+        if gap.kind == PipeKind::Moving {
+            let base_heights = [gap.gap_bottom + gap_size + theme.size.y, gap.gap_bottom];
+            for (entity, base_y) in pipes.into_iter().zip(base_heights) {
+                commands.entity(entity).insert(MovingPipe {
+                    base_y,
+                    elapsed: 0.,
+                });
+            }
+        }
+
+        playback.next_pipe += 1;
+    }
+}
+
+fn moving_pipes_system(time: Res<Time>, mut query: Query<(&mut Transform, &mut MovingPipe)>) {
+    for (mut transform, mut moving) in query.iter_mut() {
+        moving.elapsed += time.delta_seconds();
+        transform.translation.y =
+            moving.base_y + MOVING_PIPE_AMPLITUDE * (moving.elapsed * MOVING_PIPE_FREQUENCY).sin();
+    }
+}
+
+fn stop_level_playback_system(mut commands: Commands) {
+    commands.remove_resource::<LevelPlayback>();
+}
+
+pub struct LevelPlugin;
+
+impl Plugin for LevelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<Level>()
+            .init_asset_loader::<LevelLoader>()
+            .init_resource::<LevelLibrary>()
+            .add_systems(
+                (
+                    level_playback_system.run_if(resource_exists::<LevelPlayback>()),
+                    moving_pipes_system,
+                )
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(stop_level_playback_system.in_schedule(OnEnter(GameState::Menu)));
+    }
+}
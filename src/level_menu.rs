@@ -0,0 +1,241 @@
+use bevy::prelude::*;
+
+use crate::{
+    focus::{remove_focus_system, Focus, MenuInput},
+    level::{Level, LevelLibrary, LevelPlayback},
+    DespawnOnExit, GameState, FONT,
+};
+
+const SELECTED_COLOR: Color = Color::rgb(1., 0.85, 0.2);
+
+/// Index into [`LevelLibrary::levels`] last focused on the level select
+/// screen, so it opens where it was left.
+#[derive(Resource, Default)]
+struct LevelSelection(usize);
+
+#[derive(Component)]
+struct LevelList;
+
+fn text_style(asset_server: &AssetServer, font_size: f32) -> TextStyle {
+    TextStyle {
+        font: asset_server.load(FONT),
+        font_size,
+        color: Color::WHITE,
+    }
+}
+
+/// Full screen panel that goes away when leaving `state`.
+fn spawn_screen(
+    commands: &mut Commands,
+    state: GameState,
+    children: impl FnOnce(&mut ChildBuilder),
+) {
+    commands
+        .spawn((
+            DespawnOnExit(state),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(12.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(children);
+}
+
+fn open_level_select_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::L) {
+        next_state.set(GameState::LevelSelect);
+    }
+}
+
+fn spawn_level_select_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    selection: Res<LevelSelection>,
+    mut library: ResMut<LevelLibrary>,
+) {
+    library.refresh(&asset_server);
+    commands.insert_resource(Focus::at(selection.0, library.levels.len()));
+
+    spawn_screen(&mut commands, GameState::LevelSelect, |parent| {
+        parent.spawn(TextBundle::from_section(
+            "Levels",
+            text_style(&asset_server, 40.),
+        ));
+        parent.spawn((
+            LevelList,
+            TextBundle::from_section("", text_style(&asset_server, 22.))
+                .with_text_alignment(TextAlignment::Center),
+        ));
+        parent.spawn(TextBundle::from_section(
+            "Up/Down: choose   Enter: play   Esc: back",
+            text_style(&asset_server, 16.),
+        ));
+    });
+}
+
+fn level_select_input_system(
+    mut commands: Commands,
+    menu_input: MenuInput,
+    library: Res<LevelLibrary>,
+    levels: Res<Assets<Level>>,
+    mut focus: ResMut<Focus>,
+    mut selection: ResMut<LevelSelection>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if menu_input.back() {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    if library.is_changed() {
+        focus.set_len(library.levels.len());
+    }
+    if selection.0 != focus.index {
+        selection.0 = focus.index;
+    }
+
+    if focus.activated() {
+        let level = library
+            .levels
+            .get(focus.index)
+            .and_then(|handle| levels.get(handle));
+        if let Some(level) = level {
+            commands.insert_resource(LevelPlayback::new(level.clone(), Some(focus.index)));
+            next_state.set(GameState::InGame);
+        }
+    }
+}
+
+fn update_level_list_system(
+    asset_server: Res<AssetServer>,
+    library: Res<LevelLibrary>,
+    levels: Res<Assets<Level>>,
+    focus: Res<Focus>,
+    mut level_events: EventReader<AssetEvent<Level>>,
+    mut query: Query<&mut Text, With<LevelList>>,
+) {
+    let levels_changed = level_events.iter().count() > 0;
+    if !levels_changed && !focus.is_changed() && !library.is_changed() {
+        return;
+    }
+
+    let style = text_style(&asset_server, 22.);
+    let sections: Vec<_> = library
+        .levels
+        .iter()
+        .enumerate()
+        .map(|(index, handle)| {
+            let name = levels
+                .get(handle)
+                .map_or("Loading...", |level| level.name.as_str());
+            let color = if index == focus.index {
+                SELECTED_COLOR
+            } else {
+                Color::WHITE
+            };
+            TextSection::new(
+                format!("{name}\n"),
+                TextStyle {
+                    color,
+                    ..style.clone()
+                },
+            )
+        })
+        .collect();
+
+    for mut text in query.iter_mut() {
+        text.sections = if sections.is_empty() {
+            vec![TextSection::new("No levels found", style.clone())]
+        } else {
+            sections.clone()
+        };
+    }
+}
+
+fn spawn_level_complete_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    playback: Res<LevelPlayback>,
+    library: Res<LevelLibrary>,
+) {
+    let has_next = playback
+        .source
+        .is_some_and(|index| index + 1 < library.levels.len());
+    let hint = if has_next {
+        "Enter: next level   Esc: menu"
+    } else {
+        "Enter: levels   Esc: menu"
+    };
+
+    spawn_screen(&mut commands, GameState::LevelComplete, |parent| {
+        parent.spawn(TextBundle::from_section(
+            "Level complete!",
+            text_style(&asset_server, 36.),
+        ));
+        parent.spawn(TextBundle::from_section(
+            playback.level.name.clone(),
+            text_style(&asset_server, 24.),
+        ));
+        parent.spawn(TextBundle::from_section(
+            hint,
+            text_style(&asset_server, 16.),
+        ));
+    });
+}
+
+fn level_complete_input_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    playback: Res<LevelPlayback>,
+    library: Res<LevelLibrary>,
+    levels: Res<Assets<Level>>,
+    mut selection: ResMut<LevelSelection>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    if !keyboard_input.just_pressed(KeyCode::Return) {
+        return;
+    }
+
+    let next = playback.source.map(|index| index + 1);
+    match next.and_then(|index| Some((index, levels.get(library.levels.get(index)?)?))) {
+        Some((index, level)) => {
+            selection.0 = index;
+            commands.insert_resource(LevelPlayback::new(level.clone(), Some(index)));
+            next_state.set(GameState::InGame);
+        }
+        None => next_state.set(GameState::LevelSelect),
+    }
+}
+
+pub struct LevelMenuPlugin;
+
+impl Plugin for LevelMenuPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelSelection>()
+            .add_system(open_level_select_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_level_select_system.in_schedule(OnEnter(GameState::LevelSelect)))
+            .add_system(remove_focus_system.in_schedule(OnExit(GameState::LevelSelect)))
+            .add_systems(
+                (level_select_input_system, update_level_list_system)
+                    .in_set(OnUpdate(GameState::LevelSelect)),
+            )
+            .add_system(spawn_level_complete_system.in_schedule(OnEnter(GameState::LevelComplete)))
+            .add_system(level_complete_input_system.in_set(OnUpdate(GameState::LevelComplete)));
+    }
+}
@@ -0,0 +1,1514 @@
+use std::{f32::consts::PI, path::PathBuf};
+
+use achievements::AchievementsPlugin;
+use arcade::ArcadePlugin;
+use audio::AudioManagerPlugin;
+use best_score::BestScorePlugin;
+use bevy::{
+    prelude::*,
+    sprite::{
+        collide_aabb::{collide, Collision},
+        Anchor,
+    },
+    window::{PrimaryWindow, WindowResized},
+};
+use bird_animation::{BirdAnimationPlugin, BirdAnimator};
+use blade::BladePlugin;
+use bonus::{BonusPlugin, BonusStage};
+use bullet_time::BulletTimePlugin;
+use characters::{Character, CharactersPlugin};
+use charge_flap::{ChargeFlapPlugin, FlapCharge, FlapControl};
+use checkpoint::{CheckpointPipe, CheckpointPlugin, Checkpoints};
+use climb::ClimbPlugin;
+use coins::CoinsPlugin;
+use console::ConsolePlugin;
+use cosmetics::{CosmeticsPlugin, Customization, ItemKind};
+use daily::DailyPlugin;
+use death::DeathPlugin;
+use demo::DemoPlugin;
+use difficulty::{Difficulty, DifficultyPlugin};
+use editor::EditorPlugin;
+use enemy::EnemyPlugin;
+use event_log::EventLogPlugin;
+use fever::FeverPlugin;
+use focus::{remove_focus_system, Focus, FocusPlugin, Focusable, Shortcut};
+use fog_of_war::FogOfWarPlugin;
+use game_over::GameOverPlugin;
+use grading::GradingPlugin;
+use ground_hazard::GroundHazardPlugin;
+use hazard::Hazard;
+use leaderboard::LeaderboardPlugin;
+use level::LevelPlugin;
+use level_menu::LevelMenuPlugin;
+use lives::{Lives, LivesPlugin};
+use low_spec::LowSpecPlugin;
+use magnet::MagnetPlugin;
+use marathon::MarathonPlugin;
+use mode::{endless_run, Ceiling, GameMode, ModePlugin};
+use modifiers::{ActiveModifiers, Modifier, ModifiersPlugin, LOW_GRAVITY};
+use music::MusicPlugin;
+use pause::{not_paused, PausePlugin};
+use pet::PetPlugin;
+use photo::PhotoPlugin;
+use pipe_themes::{PipeTheme, PipeThemesPlugin};
+use pool::{PipePool, PoolPlugin};
+use practice::PracticePlugin;
+use profiles::{Profiles, ProfilesPlugin};
+use quit::QuitPlugin;
+use rainbow::RainbowPlugin;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha12Rng;
+use resolution::{ResolutionPlugin, WorldScale};
+use roulette::{Roulette, RoulettePlugin};
+use rumble::RumblePlugin;
+use savegame::SavegamePlugin;
+use score::{spawn_score_zone, Score, ScorePipe, ScorePlugin};
+use screenshot::ScreenshotPlugin;
+use session::SessionPlugin;
+use settings::{Settings, SettingsPlugin};
+use share::SharePlugin;
+use shop::ShopPlugin;
+use skyline::SkylinePlugin;
+use speedrun::SpeedrunPlugin;
+use stats::StatsPlugin;
+use timeline::TimelinePlugin;
+use touch::{FlapInput, SafeArea};
+use tournament::TournamentPlugin;
+use transition::{transition_done, TransitionPlugin};
+use tween::TweenPlugin;
+use whoosh::WhooshPlugin;
+
+pub mod achievements;
+pub mod arcade;
+pub mod audio;
+pub mod best_score;
+pub mod bird_animation;
+pub mod blade;
+pub mod bonus;
+pub mod bot;
+pub mod bullet_time;
+pub mod characters;
+pub mod charge_flap;
+pub mod checkpoint;
+pub mod climb;
+pub mod clipboard;
+#[cfg(feature = "cloud-sync")]
+pub mod cloud_sync;
+pub mod coins;
+pub mod console;
+pub mod cosmetics;
+pub mod daily;
+pub mod death;
+pub mod demo;
+#[cfg(feature = "dev")]
+pub mod dev;
+pub mod difficulty;
+#[cfg(feature = "discord")]
+pub mod discord;
+pub mod editor;
+#[cfg(feature = "embedded-assets")]
+pub mod embedded_assets;
+pub mod enemy;
+pub mod event_log;
+pub mod fever;
+pub mod focus;
+pub mod fog_of_war;
+pub mod game_over;
+pub mod grading;
+pub mod ground_hazard;
+pub mod hazard;
+#[cfg(feature = "highlight-gif")]
+pub mod highlight;
+pub mod leaderboard;
+pub mod level;
+pub mod level_menu;
+pub mod lives;
+pub mod low_spec;
+pub mod magnet;
+pub mod marathon;
+pub mod mode;
+pub mod modifiers;
+#[cfg(feature = "motd")]
+pub mod motd;
+pub mod music;
+#[cfg(debug_assertions)]
+pub mod no_clip;
+pub mod pause;
+pub mod persistence;
+pub mod pet;
+pub mod photo;
+pub mod pipe_themes;
+pub mod pool;
+pub mod practice;
+pub mod profiles;
+pub mod quit;
+pub mod rainbow;
+pub mod resolution;
+pub mod roulette;
+pub mod rumble;
+pub mod savegame;
+pub mod score;
+pub mod screenshot;
+pub mod session;
+pub mod settings;
+pub mod share;
+pub mod shop;
+pub mod skyline;
+pub mod speedrun;
+pub mod stats;
+#[cfg(feature = "steam")]
+pub mod steam;
+pub mod storage;
+pub mod synth;
+pub mod texture_packs;
+pub mod timeline;
+pub mod touch;
+pub mod tournament;
+pub mod transition;
+pub mod tween;
+pub mod whoosh;
+
+/// World pixels to a pixel of the `@1x` art.
+pub const SCALE: Vec3 = Vec3::new(3., 3., 3.);
+pub const SPEED: f32 = 4.5;
+/// How far apart pipe pairs are in endless runs, in pixels, whatever the speed.
+pub const PIPE_SPACING: f32 = 270.;
+pub const PIPE_GAP: f32 = 150.;
+pub const PIPE_HEIGHT: f32 = 160. * 3.;
+pub const FLAP_SPEED: f32 = 4.5;
+pub const WINDOW_WIDTH: f32 = 400.;
+pub const WINDOW_HEIGHT: f32 = 700.;
+pub const MIN_PIPE_OFFSET: f32 = 100.;
+pub const PLAYER_X: f32 = -150.;
+pub const PIPE_SPAWN_X: f32 = 400.;
+pub const PIPE_WIDTH: f32 = 26. * 3.;
+pub const FLOOR_SEGMENT_WIDTH: f32 = 168. * 3.;
+pub const FLOOR_HEIGHT: f32 = 50.;
+/// Highest the bird's centre can go, at the top edge of the screen.
+pub const CEILING_Y: f32 = WINDOW_HEIGHT / 2.;
+pub const BACKGROUND_SEGMENT_WIDTH: f32 = 144. * 3.;
+pub const FONT: &str = "fonts/DejaVuSansMono-Bold.ttf";
+
+#[derive(Clone, Copy, Default, Eq, PartialEq, Debug, Hash, States)]
+pub enum GameState {
+    #[default]
+    Menu,
+    InGame,
+    GameOver,
+    Settings,
+    Editor,
+    LevelSelect,
+    LevelComplete,
+    Stats,
+    Practice,
+    Calendar,
+    Shop,
+    Profiles,
+    TournamentSetup,
+    Podium,
+    Leaderboard,
+    /// Showing the session summary on the way out.
+    Quitting,
+}
+
+/// Stages of a gameplay frame, run in this order so every system sees the
+/// effects of the ones before it in the same frame.
+#[derive(SystemSet, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum GameplaySet {
+    /// Turning flaps and other controls into velocities and events.
+    Input,
+    /// Gravity and moving everything by its velocity.
+    Physics,
+    /// Crashes, pickups and pipes being hit.
+    Collision,
+    /// Points and pickups being applied.
+    Scoring,
+    /// Sprites and HUD catching up with the frame.
+    Presentation,
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Player;
+
+#[derive(Component)]
+pub struct Pipe;
+
+/// Despawned once it has moved more than `margin` past the edge of the screen
+/// it's heading towards. Pipes go back to the [`PipePool`] instead.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct DespawnWhenOffscreen {
+    margin: f32,
+}
+
+#[derive(Component)]
+pub struct MainCamera;
+
+/// Spawned during a run and cleared along with the pipes when the world resets.
+#[derive(Component)]
+pub struct RunScoped;
+
+/// The source of randomness for a run's pipe gaps and what comes along with
+/// them, like pickups, so a seeded run always plays out the same. Anything
+/// drawn on a clock has its own, seeded from the [`RunSeed`]. The same generator as
+/// `StdRng`, but one that can say where it's up to for a saved session.
+#[derive(Resource, Deref, DerefMut)]
+pub struct GameRng(ChaCha12Rng);
+
+impl Default for GameRng {
+    fn default() -> Self {
+        Self(ChaCha12Rng::from_entropy())
+    }
+}
+
+/// Present when started with `--bot`, letting the demo bot fly every run.
+#[derive(Resource)]
+pub struct Autopilot;
+
+/// What the current run's [`GameRng`] was seeded with, so its layout can be
+/// played again.
+#[derive(Resource, Default)]
+pub struct RunSeed {
+    current: u64,
+    /// Seed for the next run to start from instead of a random one.
+    next: Option<u64>,
+}
+
+/// Sent whenever the player flaps.
+pub struct FlapEvent;
+
+/// Which face of a pipe the bird ran into.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PipeFace {
+    /// The upward-facing end, as on a bottom pipe.
+    Top,
+    /// The downward-facing end, as on a top pipe.
+    Bottom,
+    /// Either long side.
+    Side,
+}
+
+impl From<Collision> for PipeFace {
+    fn from(collision: Collision) -> Self {
+        match collision {
+            Collision::Top => PipeFace::Top,
+            Collision::Bottom => PipeFace::Bottom,
+            Collision::Left | Collision::Right | Collision::Inside => PipeFace::Side,
+        }
+    }
+}
+
+/// What the player crashed into.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum CrashCause {
+    Floor,
+    /// A pipe, with its hitbox and the face of it the bird hit.
+    Pipe {
+        center: Vec2,
+        size: Vec2,
+        face: PipeFace,
+    },
+    Hazard,
+    /// The top of the screen, in modes where it's solid.
+    Ceiling,
+}
+
+/// Where and how the player crashed.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Crash {
+    pub cause: CrashCause,
+    pub player: Vec2,
+    pub velocity_y: f32,
+}
+
+/// Sent whenever the player hits the floor, a pipe, a hazard or a solid
+/// ceiling, whether or not it ends the run.
+pub struct Crashed(pub Crash);
+
+/// Sent when a crash ends the run.
+pub struct PlayerDiedEvent(pub Crash);
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PowerUp {
+    Coin,
+    Heart,
+    Magnet,
+    Ammo,
+    Star,
+}
+
+/// Sent whenever the player picks something up. Each kind's effect is applied
+/// by whoever owns it, so anything else can react to pickups too.
+pub struct PowerUpCollectedEvent(pub PowerUp);
+
+/// How many power-ups of `kind` were collected since `collected` last read.
+fn count_collected(collected: &mut EventReader<PowerUpCollectedEvent>, kind: PowerUp) -> u32 {
+    collected
+        .iter()
+        .filter(|PowerUpCollectedEvent(power_up)| *power_up == kind)
+        .count() as u32
+}
+
+/// Multiplies how fast everything moves sideways, for tuning from the console.
+#[derive(Resource, Reflect)]
+#[reflect(Resource)]
+pub struct SpeedScale(f32);
+
+impl Default for SpeedScale {
+    fn default() -> Self {
+        Self(1.)
+    }
+}
+
+/// Left edge of newly spawned pipes when flying right. Wider windows than the
+/// default would show pipes appearing at [`PIPE_SPAWN_X`], so there they start
+/// just past the edge and slide in instead.
+#[derive(Resource)]
+pub struct PipeSpawnX(pub f32);
+
+impl Default for PipeSpawnX {
+    fn default() -> Self {
+        Self(PIPE_SPAWN_X)
+    }
+}
+
+fn update_pipe_spawn_x_system(
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut spawn_x: ResMut<PipeSpawnX>,
+) {
+    if let Ok(window) = windows.get_single() {
+        let x = (window.width() / 2.).max(PIPE_SPAWN_X);
+        if spawn_x.0 != x {
+            spawn_x.0 = x;
+        }
+    }
+}
+
+/// Which way the world scrolls past the bird.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum ScrollAxis {
+    /// Pipes come in from the right, as in the original game.
+    #[default]
+    Horizontal,
+    /// Pipes lie on their side and come down from the top.
+    Vertical,
+}
+
+impl ScrollAxis {
+    /// Velocity of anything moving along with the world.
+    fn velocity(self, direction: WorldDirection) -> Velocity {
+        match self {
+            ScrollAxis::Horizontal => direction.scroll_velocity(),
+            ScrollAxis::Vertical => Velocity { x: 0., y: -SPEED },
+        }
+    }
+}
+
+/// Which way the bird flies along a horizontally scrolling world.
+#[derive(Resource, Clone, Copy, PartialEq, Eq, Default, Debug)]
+pub enum WorldDirection {
+    /// Pipes come in from the right, as in the original game.
+    #[default]
+    Right,
+    /// Mirrored: the bird sits on the right and pipes come in from the left.
+    Left,
+}
+
+impl WorldDirection {
+    /// 1 when flying right, -1 when flying left.
+    fn sign(self) -> f32 {
+        match self {
+            WorldDirection::Right => 1.,
+            WorldDirection::Left => -1.,
+        }
+    }
+
+    fn player_x(self) -> f32 {
+        PLAYER_X * self.sign()
+    }
+
+    /// Left edge of something `width` wide whose left edge would be at `x` when
+    /// flying right. With a `width` of 0 this mirrors centers.
+    fn mirror_x(self, x: f32, width: f32) -> f32 {
+        match self {
+            WorldDirection::Right => x,
+            WorldDirection::Left => -x - width,
+        }
+    }
+
+    fn pipe_spawn_x(self, spawn_x: &PipeSpawnX, width: f32) -> f32 {
+        self.mirror_x(spawn_x.0, width)
+    }
+
+    /// `offset` further along the bird's way than the side of a pipe `width`
+    /// wide it reaches first. The pipe's left edge is at `pipe_x`.
+    fn past_pipe(self, pipe_x: f32, width: f32, offset: f32) -> f32 {
+        pipe_x + width / 2. + (offset - width / 2.) * self.sign()
+    }
+
+    /// Whether the bird at `player_x` has flown past `x`.
+    fn passed(self, x: f32, player_x: f32) -> bool {
+        (player_x - x) * self.sign() > 0.
+    }
+
+    /// Velocity of anything scrolling past along with the world.
+    fn scroll_velocity(self) -> Velocity {
+        Velocity {
+            x: -SPEED * self.sign(),
+            y: 0.,
+        }
+    }
+}
+
+/// How far the world has scrolled since the last pipe pair spawned, in runs
+/// and the menu's demo alike.
+#[derive(Resource, Default)]
+pub struct PipeDistance(f32);
+
+impl PipeDistance {
+    /// Uses up [`PIPE_SPACING`] for a new pair, returning how far it should
+    /// already have scrolled past its spawn point. Distance that built up while
+    /// no pipes could spawn, like during a bonus stage, is more than a frame
+    /// scrolls and is dropped rather than caught up on with a wall of pipes.
+    fn take_spacing(&mut self) -> f32 {
+        let overshoot = self.0 - PIPE_SPACING;
+        if overshoot < PIPE_SPACING / 2. {
+            self.0 = overshoot;
+            overshoot
+        } else {
+            self.0 = 0.;
+            0.
+        }
+    }
+}
+
+/// True once the last pair has scrolled [`PIPE_SPACING`], so pipes stay the
+/// same distance apart at any speed and frame rate.
+fn pipe_due(distance: Res<PipeDistance>) -> bool {
+    distance.0 >= PIPE_SPACING
+}
+
+fn reset_pipe_distance_system(mut distance: ResMut<PipeDistance>) {
+    distance.0 = 0.;
+}
+
+/// Frame rate that per-frame speeds like [`Velocity`] are tuned for.
+const BASE_FPS: f32 = 60.;
+/// Frames' worth of movement a single slow frame can catch up on, so a hitch
+/// can't carry the bird straight through a pipe.
+const MAX_FRAME_STEP: f32 = 4.;
+
+/// How many frames at [`BASE_FPS`] the last frame was worth, for moving things
+/// by a per-frame speed just as fast at any frame rate. Slowed down time is
+/// already part of it.
+fn frame_step(time: &Time) -> f32 {
+    (time.delta_seconds() * BASE_FPS).min(MAX_FRAME_STEP)
+}
+
+fn scrolls_horizontally(axis: Res<ScrollAxis>) -> bool {
+    *axis == ScrollAxis::Horizontal
+}
+
+#[derive(Component)]
+pub struct Floor;
+
+#[derive(Component)]
+pub struct Background;
+
+/// Despawned along with its children when leaving the given state.
+#[derive(Component)]
+pub struct DespawnOnExit(GameState);
+
+#[derive(Component, Reflect, Default, Clone, Copy)]
+#[reflect(Component)]
+pub struct Velocity {
+    pub x: f32,
+    pub y: f32,
+}
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct Mass;
+
+#[derive(Component, Deref, DerefMut, Reflect, Default)]
+#[reflect(Component)]
+pub struct AnimationTimer(Timer);
+
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct AnimationIndices {
+    first: usize,
+    last: usize,
+}
+
+/// One of `segments` side by side that take turns scrolling across the window,
+/// each jumping to the back of the line once it's gone past the edge.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component)]
+pub struct InfiniteScrolling {
+    pub segment_width: f32,
+    pub segments: usize,
+    pub speed: f32,
+}
+
+fn spawn_player(
+    mut commands: Commands,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+    customization: Res<Customization>,
+) {
+    let initial_position = Transform::from_xyz(PLAYER_X, 0., 0.).with_scale(world_scale.sprite());
+
+    let texture_atlas = world_scale.atlas(&asset_server, "bird.png", Vec2::new(20., 20.), 4, 1);
+
+    let texture_atlas_handle = texture_atlases.add(texture_atlas);
+
+    commands.spawn((
+        Player,
+        Velocity { x: 0., y: 0. },
+        Mass,
+        SpriteSheetBundle {
+            texture_atlas: texture_atlas_handle,
+            sprite: TextureAtlasSprite {
+                color: customization.color(ItemKind::Skin),
+                ..TextureAtlasSprite::new(0)
+            },
+            transform: initial_position,
+            ..Default::default()
+        },
+        BirdAnimator::default(),
+    ));
+}
+
+/// Flaps on a press, or with [`FlapControl::Charge`] times how long the flap
+/// button is held and flaps on its release, harder the longer it was held. The
+/// bot always flaps like a tap. The character sets how strong flaps are, and
+/// how much stronger a quick second flap is.
+#[allow(clippy::too_many_arguments)]
+fn flap_system(
+    time: Res<Time>,
+    flap_input: FlapInput,
+    settings: Res<Settings>,
+    character: Res<Character>,
+    mut charge: ResMut<FlapCharge>,
+    mut since_last_flap: Local<Option<f32>>,
+    autopilot: Option<Res<Autopilot>>,
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    mut flapped: EventWriter<FlapEvent>,
+    mut query: Query<(&Transform, &mut Velocity), With<Player>>,
+    gaps: Query<&Transform, With<ScorePipe>>,
+) {
+    let (transform, mut player_vel) = query.single_mut();
+    let bot_flaps = autopilot.is_some()
+        && bot::should_flap(
+            *direction,
+            transform.translation,
+            player_vel.y,
+            theme.width(),
+            gaps.iter().map(|gap| gap.translation),
+        );
+
+    let strength = match settings.flap_control {
+        _ if bot_flaps => Some(1.),
+        FlapControl::Tap => flap_input.just_pressed().then_some(1.),
+        FlapControl::Charge => {
+            charge.tick(time.delta_seconds());
+            if flap_input.just_pressed() {
+                charge.start();
+            }
+            if flap_input.just_released() {
+                charge.release()
+            } else {
+                None
+            }
+        }
+    };
+    if let Some(since) = since_last_flap.as_mut() {
+        *since += time.delta_seconds();
+    }
+    if let Some(strength) = strength {
+        let since = since_last_flap.unwrap_or(f32::INFINITY);
+        let (multiplier, double) = character.profile.flap_strength(since);
+        // A third quick flap starts a new pair rather than doubling again.
+        *since_last_flap = if double { None } else { Some(0.) };
+        player_vel.y = FLAP_SPEED * strength * multiplier;
+        flapped.send(FlapEvent);
+    }
+}
+
+fn animate_sprite_system(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut query: Query<(
+        &mut TextureAtlasSprite,
+        &mut AnimationTimer,
+        &AnimationIndices,
+    )>,
+) {
+    let delta = low_spec::animation_delta(&time, &settings);
+    for (mut sprite, mut timer, indices) in query.iter_mut() {
+        timer.tick(delta);
+        if timer.finished() {
+            sprite.index = if sprite.index == indices.last {
+                indices.first
+            } else {
+                sprite.index + 1
+            }
+        }
+    }
+}
+
+fn tilt_with_vel_system(
+    direction: Res<WorldDirection>,
+    mut query: Query<(&mut Transform, &Velocity), With<Player>>,
+) {
+    for (mut transform, velocity) in query.iter_mut() {
+        let angle = velocity.y / 5. * PI / 4. * direction.sign();
+        transform.rotation = Quat::from_rotation_z(angle);
+    }
+}
+/// Velocities are in pixels per frame at [`BASE_FPS`], scaled here by how long
+/// the frame actually took.
+fn movement_system(
+    time: Res<Time>,
+    scale: Res<SpeedScale>,
+    difficulty: Res<Difficulty>,
+    roulette: Res<Roulette>,
+    mut pipe_distance: ResMut<PipeDistance>,
+    mut query: Query<(&mut Transform, &Velocity)>,
+) {
+    let speed = difficulty.speed * frame_step(&time);
+    let scroll = scale.0 * roulette.scroll_scale() * speed;
+    pipe_distance.0 += SPEED * scroll;
+    for (mut transform, velocity) in query.iter_mut() {
+        transform.translation.x += velocity.x * scroll;
+        transform.translation.y += velocity.y * speed;
+    }
+}
+
+/// Sprite for a single pipe in `theme` with its top left corner at (`x`, `y`).
+/// Top pipes are flipped so their opening faces down. Pipes are stretched to
+/// their size rather than drawn at their texture's, so they stay at [`SCALE`]
+/// whichever [`WorldScale`] their texture is from.
+fn pipe_sprite(theme: &PipeTheme, x: f32, y: f32, top: bool) -> SpriteBundle {
+    SpriteBundle {
+        texture: theme.texture.clone(),
+        transform: Transform::from_xyz(x, y, 0.).with_scale(SCALE),
+        sprite: Sprite {
+            flip_y: top,
+            anchor: Anchor::TopLeft,
+            custom_size: Some(theme.size / SCALE.truncate()),
+            ..Default::default()
+        },
+        ..Default::default()
+    }
+}
+
+/// Sprites for a top and bottom pipe with their left edge at `x`, leaving a gap of
+/// `gap` above `gap_bottom`.
+fn pipe_pair_sprites(theme: &PipeTheme, x: f32, gap_bottom: f32, gap: f32) -> [SpriteBundle; 2] {
+    let gap_top = gap_bottom + gap;
+
+    [
+        pipe_sprite(theme, x, gap_top + theme.size.y, true),
+        pipe_sprite(theme, x, gap_bottom, false),
+    ]
+}
+
+/// Spawns a pair of pipes with their left edge at `x` when flying right, or
+/// mirrored to the other side of the screen when flying left.
+pub fn spawn_pipe_pair(
+    commands: &mut Commands,
+    pool: &mut PipePool,
+    theme: &PipeTheme,
+    direction: WorldDirection,
+    x: f32,
+    gap_bottom: f32,
+    gap: f32,
+) -> [Entity; 2] {
+    let x = direction.mirror_x(x, theme.width());
+    let pipes = pipe_pair_sprites(theme, x, gap_bottom, gap)
+        .map(|sprite| pool.spawn(commands, sprite, direction.scroll_velocity()));
+    commands.entity(pipes[1]).insert(ScorePipe);
+    spawn_score_zone(commands, pipes[1], direction, theme.width(), gap);
+    pipes
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_pipes_system(
+    mut commands: Commands,
+    theme: Res<PipeTheme>,
+    mut checkpoints: ResMut<Checkpoints>,
+    difficulty: Res<Difficulty>,
+    direction: Res<WorldDirection>,
+    mut rng: ResMut<GameRng>,
+    mut pool: ResMut<PipePool>,
+    spawn_x: Res<PipeSpawnX>,
+    mut distance: ResMut<PipeDistance>,
+) {
+    let overshoot = distance.take_spacing();
+    let gap = difficulty.pipe_gap;
+    let gap_bottom = rng.gen_range(
+        (-WINDOW_HEIGHT / 2. + MIN_PIPE_OFFSET)..(WINDOW_HEIGHT / 2. - MIN_PIPE_OFFSET - gap),
+    );
+
+    let pipes = spawn_pipe_pair(
+        &mut commands,
+        &mut pool,
+        &theme,
+        *direction,
+        spawn_x.0 - overshoot,
+        gap_bottom,
+        gap,
+    );
+    if checkpoints.next_pipe_is_checkpoint() {
+        for pipe in pipes {
+            commands.entity(pipe).insert(CheckpointPipe);
+        }
+    }
+}
+
+fn despawn_offscreen_system(
+    mut commands: Commands,
+    mut pool: ResMut<PipePool>,
+    theme: Res<PipeTheme>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera: Query<&GlobalTransform, With<MainCamera>>,
+    query: Query<(Entity, &Transform, &Velocity, &DespawnWhenOffscreen)>,
+    pipes: Query<(), With<Pipe>>,
+) {
+    let (Ok(window), Ok(camera)) = (windows.get_single(), camera.get_single()) else {
+        return;
+    };
+    let center = camera.translation().truncate();
+    let half_size = Vec2::new(window.width(), window.height()) / 2.;
+
+    for (entity, transform, velocity, offscreen) in query.iter() {
+        let min = center - half_size - offscreen.margin;
+        let max = center + half_size + offscreen.margin;
+        let position = transform.translation;
+        let gone = (velocity.x < 0. && position.x < min.x)
+            || (velocity.x > 0. && position.x > max.x)
+            || (velocity.y < 0. && position.y < min.y)
+            || (velocity.y > 0. && position.y > max.y);
+        if !gone {
+            continue;
+        }
+
+        if pipes.contains(entity) {
+            pool.release(&mut commands, entity, &theme);
+        } else {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Scenery tiled across the window and scrolled along with the world.
+#[derive(Clone, Copy)]
+enum Scenery {
+    Floor,
+    Background,
+}
+
+impl Scenery {
+    fn texture(self) -> &'static str {
+        match self {
+            Scenery::Floor => "floor.png",
+            Scenery::Background => "bg.png",
+        }
+    }
+
+    fn segment_width(self) -> f32 {
+        match self {
+            Scenery::Floor => FLOOR_SEGMENT_WIDTH,
+            Scenery::Background => BACKGROUND_SEGMENT_WIDTH,
+        }
+    }
+
+    fn speed(self) -> f32 {
+        match self {
+            Scenery::Floor => -SPEED,
+            Scenery::Background => -SPEED * 0.2,
+        }
+    }
+
+    /// Top edge and depth of the segments. The background sits just behind
+    /// everything else, leaving room for the skyline in front of it while
+    /// staying in view of the camera.
+    fn top(self) -> Vec2 {
+        match self {
+            Scenery::Floor => Vec2::new(-WINDOW_HEIGHT / 2. + FLOOR_HEIGHT, 10.),
+            Scenery::Background => Vec2::new(WINDOW_HEIGHT / 2., -0.05),
+        }
+    }
+}
+
+/// Segments of `segment_width` needed to cover a window `width` wide, with one
+/// more to scroll in as another scrolls out.
+fn segments_to_cover(width: f32, segment_width: f32) -> usize {
+    (width / segment_width).ceil() as usize + 1
+}
+
+/// Tiles `scenery` across a window `width` wide. Segments line up with `phase`
+/// so re-tiling carries on from where the old ones had scrolled to.
+fn spawn_scenery(
+    commands: &mut Commands,
+    asset: &AssetServer,
+    world_scale: WorldScale,
+    scenery: Scenery,
+    color: Color,
+    width: f32,
+    phase: f32,
+) {
+    let segment_width = scenery.segment_width();
+    let segments = segments_to_cover(width, segment_width);
+    let left = -width / 2. - segment_width;
+    let first = left + (phase - left).rem_euclid(segment_width);
+    let top = scenery.top();
+
+    for i in 0..segments {
+        let mut segment = commands.spawn((
+            InfiniteScrolling {
+                segment_width,
+                segments,
+                speed: scenery.speed(),
+            },
+            SpriteBundle {
+                texture: world_scale.load(asset, scenery.texture()),
+                transform: Transform::from_xyz(first + i as f32 * segment_width, top.x, top.y)
+                    .with_scale(world_scale.sprite()),
+                sprite: Sprite {
+                    color,
+                    anchor: Anchor::TopLeft,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ));
+        match scenery {
+            Scenery::Floor => segment.insert(Floor),
+            Scenery::Background => segment.insert(Background),
+        };
+    }
+}
+
+/// Lays the floor and background out across the window, and again whenever it's
+/// resized so wide windows aren't left with gaps, or the [`WorldScale`] changes.
+#[allow(clippy::too_many_arguments)]
+fn tile_scenery_system(
+    mut commands: Commands,
+    asset: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+    customization: Res<Customization>,
+    mut resized: EventReader<WindowResized>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    floor: Query<(Entity, &Transform), With<Floor>>,
+    background: Query<(Entity, &Transform), With<Background>>,
+) {
+    let resized = resized.iter().count() > 0;
+    if !resized && !floor.is_empty() && !world_scale.is_changed() {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let positions = |(entity, transform): (Entity, &Transform)| (entity, transform.translation.x);
+    let layers: [(Scenery, Color, Vec<_>); 2] = [
+        (
+            Scenery::Floor,
+            Color::WHITE,
+            floor.iter().map(positions).collect(),
+        ),
+        (
+            Scenery::Background,
+            customization.color(ItemKind::Background),
+            background.iter().map(positions).collect(),
+        ),
+    ];
+    for (scenery, color, segments) in layers {
+        let phase = segments.first().map_or(-window.width() / 2., |(_, x)| *x);
+        for (entity, _) in segments {
+            commands.entity(entity).despawn_recursive();
+        }
+        spawn_scenery(
+            &mut commands,
+            &asset,
+            *world_scale,
+            scenery,
+            color,
+            window.width(),
+            phase,
+        );
+    }
+}
+
+fn infinite_scrolling_system(
+    time: Res<Time>,
+    scale: Res<SpeedScale>,
+    difficulty: Res<Difficulty>,
+    roulette: Res<Roulette>,
+    direction: Res<WorldDirection>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut query: Query<(&mut Transform, &InfiniteScrolling)>,
+) {
+    let half_width = windows
+        .get_single()
+        .map_or(WINDOW_WIDTH, |window| window.width())
+        / 2.;
+
+    for (
+        mut transform,
+        InfiniteScrolling {
+            segment_width,
+            segments,
+            speed,
+        },
+    ) in query.iter_mut()
+    {
+        let step = speed
+            * scale.0
+            * difficulty.speed
+            * roulette.scroll_scale()
+            * frame_step(&time)
+            * direction.sign();
+        let span = segment_width * *segments as f32;
+        transform.translation.x += step;
+        if step < 0. && transform.translation.x < -half_width - segment_width {
+            transform.translation.x += span;
+        } else if step > 0. && transform.translation.x > half_width {
+            transform.translation.x -= span;
+        }
+    }
+}
+
+fn gravity_system(
+    time: Res<Time>,
+    modifiers: Res<ActiveModifiers>,
+    difficulty: Res<Difficulty>,
+    character: Res<Character>,
+    mut query: Query<(&mut Velocity, &Mass)>,
+) {
+    let mut acceleration =
+        9.8 * time.delta_seconds() * difficulty.speed * character.profile.gravity;
+    if modifiers.is_active(Modifier::LowGravity) {
+        acceleration *= LOW_GRAVITY;
+    }
+
+    for (mut velocity, ..) in query.iter_mut() {
+        velocity.y -= acceleration;
+    }
+}
+
+/// Center and size of a pipe `size` big upright, anchored at its top left
+/// corner. Pipes lying on their side are turned a quarter around that corner.
+fn pipe_bounds(pipe: &Transform, size: Vec2) -> (Vec3, Vec2) {
+    let center = pipe.translation + pipe.rotation * (size * Vec2::new(0.5, -0.5)).extend(0.);
+    let size = (pipe.rotation * size.extend(0.)).truncate().abs();
+    (center, size)
+}
+
+/// Size of the player's hitbox. It shrinks along with a smaller bird, but
+/// doesn't grow with [`Modifier::BigHead`].
+fn player_hitbox(player: &Transform, world_scale: WorldScale) -> Vec2 {
+    Vec2::splat(45. * (player.scale.x / world_scale.sprite().x).min(1.))
+}
+
+/// Which side of `pipe`, `pipe_size` big upright, the player at `player` with a
+/// hitbox of `player_size` overlaps, if any. Top pipes reach up past the
+/// ceiling, however low they hang, so they can't be flown over.
+fn pipe_collision(
+    player: Vec3,
+    player_size: Vec2,
+    pipe: &Transform,
+    pipe_size: Vec2,
+    top: bool,
+) -> Option<Collision> {
+    let (mut center, mut size) = pipe_bounds(pipe, pipe_size);
+    if top {
+        let bottom = center.y - size.y / 2.;
+        let reach = (center.y + size.y / 2.).max(CEILING_Y + PIPE_WIDTH);
+        center.y = (bottom + reach) / 2.;
+        size.y = reach - bottom;
+    }
+    collide(player, player_size, center, size)
+}
+
+/// Whether the player's hitbox overlaps `pipe` in `theme`.
+fn hits_pipe(
+    player: &Transform,
+    world_scale: WorldScale,
+    pipe: &Transform,
+    theme: &PipeTheme,
+) -> bool {
+    let hitbox = player_hitbox(player, world_scale);
+    pipe_collision(player.translation, hitbox, pipe, theme.size, false).is_some()
+}
+
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn game_over_system(
+    mut crashed: EventWriter<Crashed>,
+    mut died: EventWriter<PlayerDiedEvent>,
+    lives: Option<ResMut<Lives>>,
+    mode: Res<GameMode>,
+    theme: Res<PipeTheme>,
+    world_scale: Res<WorldScale>,
+    mut player_query: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    pipes_query: Query<(&Transform, &Sprite), (With<Pipe>, Without<Player>)>,
+    hazards_query: Query<(&Transform, &Hazard), Without<Player>>,
+    #[cfg(debug_assertions)] no_clip: Option<Res<no_clip::NoClip>>,
+) {
+    let (mut transform, mut velocity) = player_query.single_mut();
+
+    let hit_floor = transform.translation.y < -WINDOW_HEIGHT / 2. + FLOOR_HEIGHT;
+    #[cfg(debug_assertions)]
+    if no_clip.is_some() {
+        if hit_floor {
+            transform.translation.y = -WINDOW_HEIGHT / 2. + FLOOR_HEIGHT;
+            velocity.y = FLAP_SPEED;
+        }
+        return;
+    }
+    let above_ceiling = transform.translation.y > CEILING_Y;
+    if above_ceiling && mode.ceiling() == Ceiling::Clamped {
+        transform.translation.y = CEILING_Y;
+        velocity.y = velocity.y.min(0.);
+    }
+    let hit_ceiling = above_ceiling && mode.ceiling() == Ceiling::Solid;
+    let invulnerable = lives.as_ref().is_some_and(|lives| lives.is_invulnerable());
+    let hitbox = player_hitbox(&transform, *world_scale);
+
+    let hit_pipe = pipes_query
+        .iter()
+        .filter(|_| !invulnerable)
+        .find_map(|(pipe, sprite)| {
+            Some((
+                pipe,
+                pipe_collision(
+                    transform.translation,
+                    hitbox,
+                    pipe,
+                    theme.size,
+                    sprite.flip_y,
+                )?,
+            ))
+        });
+    let hit_hazard = !invulnerable
+        && hazards_query.iter().any(|(hazard_transform, hazard)| {
+            hazard.overlaps(hazard_transform, transform.translation, hitbox)
+        });
+
+    let cause = if let Some((pipe, collision)) = hit_pipe {
+        let (center, size) = pipe_bounds(pipe, theme.size);
+        CrashCause::Pipe {
+            center: center.truncate(),
+            size,
+            face: collision.into(),
+        }
+    } else if hit_hazard {
+        CrashCause::Hazard
+    } else if hit_ceiling {
+        CrashCause::Ceiling
+    } else if hit_floor {
+        CrashCause::Floor
+    } else {
+        return;
+    };
+    let crash = Crash {
+        cause,
+        player: transform.translation.truncate(),
+        velocity_y: velocity.y,
+    };
+    crashed.send(Crashed(crash));
+
+    let survived = lives.is_some_and(|mut lives| lives.take_hit());
+    if !survived {
+        died.send(PlayerDiedEvent(crash));
+    } else if hit_floor {
+        // Bounce off the floor instead of sinking through it.
+        transform.translation.y = -WINDOW_HEIGHT / 2. + FLOOR_HEIGHT;
+        velocity.y = FLAP_SPEED;
+    } else if hit_ceiling {
+        transform.translation.y = CEILING_Y;
+        velocity.y = 0.;
+    }
+}
+
+fn player_died_system(
+    mut died: EventReader<PlayerDiedEvent>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if died.iter().count() > 0 {
+        next_state.set(GameState::GameOver);
+    }
+}
+
+/// Reseeds [`GameRng`] for every fresh run, so each one has a seed to share.
+/// Runs continued from a checkpoint or a saved session keep the one they had.
+fn seed_run_system(score: Res<Score>, mut seed: ResMut<RunSeed>, mut rng: ResMut<GameRng>) {
+    if score.0 > 0 {
+        return;
+    }
+
+    seed.current = seed.next.take().unwrap_or_else(rand::random);
+    rng.0 = ChaCha12Rng::seed_from_u64(seed.current);
+}
+
+fn reset_world_system(
+    direction: Res<WorldDirection>,
+    pipes: Query<Entity, (With<Pipe>, Without<Player>)>,
+    run_scoped: Query<Entity, With<RunScoped>>,
+    mut player: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    mut pool: ResMut<PipePool>,
+    theme: Res<PipeTheme>,
+    mut commands: Commands,
+) {
+    let (mut transform, mut velocity) = player.single_mut();
+    *velocity = Velocity { x: 0., y: 0. };
+    transform.translation.x = direction.player_x();
+    transform.translation.y = 0.;
+
+    for entity in pipes.iter() {
+        pool.release(&mut commands, entity, &theme);
+    }
+    for entity in run_scoped.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Rows of the main menu, laid out in two columns, with the key that does what
+/// each is for.
+const MENU_ROWS: [(&str, KeyCode); 14] = [
+    ("Space/tap: play", KeyCode::Space),
+    ("L: levels", KeyCode::L),
+    ("P: practice", KeyCode::P),
+    ("D: daily", KeyCode::D),
+    ("H: tournament", KeyCode::H),
+    ("V: enter seed code", KeyCode::V),
+    ("E: editor", KeyCode::E),
+    ("S: settings", KeyCode::S),
+    ("B: shop", KeyCode::B),
+    ("U: profile", KeyCode::U),
+    ("T: stats", KeyCode::T),
+    ("K: leaderboard", KeyCode::K),
+    ("M: mute", KeyCode::M),
+    ("Q: quit", KeyCode::Q),
+];
+
+fn spawn_menu_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    safe_area: Res<SafeArea>,
+) {
+    commands.insert_resource(Focus::new(MENU_ROWS.len()));
+
+    let style = TextStyle {
+        font: asset_server.load(FONT),
+        font_size: 20.,
+        color: Color::WHITE,
+    };
+    let column = NodeBundle {
+        style: Style {
+            flex_direction: FlexDirection::Column,
+            gap: Size::height(Val::Px(4.)),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Menu),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        bottom: Val::Px(safe_area.bottom + FLOOR_HEIGHT + 20.),
+                        left: Val::Px(safe_area.left + 10.),
+                        ..Default::default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    gap: Size::height(Val::Px(8.)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        gap: Size::width(Val::Px(30.)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|parent| {
+                    let half = MENU_ROWS.len().div_ceil(2);
+                    for rows in [0..half, half..MENU_ROWS.len()] {
+                        parent.spawn(column.clone()).with_children(|parent| {
+                            for row in rows {
+                                let (label, key) = MENU_ROWS[row];
+                                parent.spawn((
+                                    Focusable(row),
+                                    Shortcut(key),
+                                    TextBundle::from_section(label, style.clone()),
+                                ));
+                            }
+                        });
+                    }
+                });
+            parent.spawn(TextBundle::from_section(
+                "Up/Down: select   Enter: open   Left/Right: mode",
+                TextStyle {
+                    font_size: 16.,
+                    ..style.clone()
+                },
+            ));
+        });
+}
+
+fn despawn_on_exit_system(
+    state: GameState,
+) -> impl FnMut(Commands, Query<(Entity, &DespawnOnExit)>) {
+    move |mut commands, query| {
+        for (entity, DespawnOnExit(scope)) in query.iter() {
+            if *scope == state {
+                commands.entity(entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+fn start_game_system(mut next_state: ResMut<NextState<GameState>>, flap_input: FlapInput) {
+    if flap_input.just_pressed() {
+        next_state.set(GameState::InGame);
+    }
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((MainCamera, Camera2dBundle::default()));
+}
+
+/// How the game is set up, filled in from the command line by the binary or by
+/// hand by anything else that runs it.
+#[derive(Clone, Default, Debug)]
+pub struct GameConfig {
+    /// Seed for the first run's pipe layout.
+    pub seed: Option<u64>,
+    /// Mode to start with selected on the menu.
+    pub mode: Option<GameMode>,
+    /// Lets the demo bot fly every run.
+    pub bot: bool,
+    /// File to append newline-delimited JSON to for every run start, flap,
+    /// point and death.
+    pub event_log: Option<PathBuf>,
+}
+
+/// The game itself: its state, systems and every feature's plugin, set up from
+/// a [`GameConfig`]. Adds no windowing of its own, leaving the window's title,
+/// icon and frame rate to whatever runs it, and keeps to default settings in
+/// memory unless a [`PersistencePlugin`](persistence::PersistencePlugin)
+/// loads the player's.
+#[derive(Default)]
+pub struct GamePlugin {
+    pub config: GameConfig,
+}
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        let config = &self.config;
+        app.init_resource::<Profiles>()
+            .init_resource::<Settings>()
+            .init_resource::<GameRng>()
+            .insert_resource(RunSeed {
+                current: 0,
+                next: config.seed,
+            })
+            .insert_resource(config.mode.unwrap_or_default())
+            .init_resource::<SafeArea>()
+            .init_resource::<SpeedScale>()
+            .init_resource::<PipeSpawnX>()
+            .init_resource::<PipeDistance>()
+            .init_resource::<ScrollAxis>()
+            .init_resource::<WorldDirection>()
+            .add_event::<FlapEvent>()
+            .add_event::<Crashed>()
+            .add_event::<PlayerDiedEvent>()
+            .add_event::<PowerUpCollectedEvent>()
+            .add_state::<GameState>()
+            .add_plugin(ResolutionPlugin)
+            .add_plugin(SettingsPlugin)
+            .add_plugin(LevelPlugin)
+            .add_plugin(LevelMenuPlugin)
+            .add_plugin(EditorPlugin)
+            .add_plugin(ScorePlugin)
+            .add_plugin(BestScorePlugin)
+            .add_plugin(CheckpointPlugin)
+            .add_plugin(GameOverPlugin)
+            .add_plugin(DeathPlugin)
+            .add_plugin(TimelinePlugin)
+            .add_plugin(ModePlugin)
+            .add_plugin(ClimbPlugin)
+            .add_plugin(ModifiersPlugin)
+            .add_plugin(RoulettePlugin)
+            .add_plugin(FocusPlugin)
+            .add_plugin(FogOfWarPlugin)
+            .add_plugin(LivesPlugin)
+            .add_plugin(SessionPlugin)
+            .add_plugin(StatsPlugin)
+            .add_plugin(QuitPlugin)
+            .add_plugin(LowSpecPlugin)
+            .add_plugin(GradingPlugin)
+            .add_plugin(ScreenshotPlugin)
+            .add_plugin(SharePlugin)
+            .add_plugin(SpeedrunPlugin)
+            .add_plugin(PracticePlugin)
+            .add_plugin(TournamentPlugin)
+            .add_plugin(DifficultyPlugin)
+            .add_plugin(BladePlugin)
+            .add_plugin(EnemyPlugin)
+            .add_plugin(GroundHazardPlugin)
+            .add_plugin(ArcadePlugin)
+            .add_plugin(BonusPlugin)
+            .add_plugin(DailyPlugin)
+            .add_plugin(FeverPlugin)
+            .add_plugin(CoinsPlugin)
+            .add_plugin(MagnetPlugin)
+            .add_plugin(CosmeticsPlugin)
+            .add_plugin(ShopPlugin)
+            .add_plugin(PetPlugin)
+            .add_plugin(PipeThemesPlugin)
+            .add_plugin(SkylinePlugin)
+            .add_plugin(RainbowPlugin)
+            .add_plugin(BulletTimePlugin)
+            .add_plugin(ChargeFlapPlugin)
+            .add_plugin(CharactersPlugin)
+            .add_plugin(MarathonPlugin)
+            .add_plugin(LeaderboardPlugin)
+            .add_plugin(BirdAnimationPlugin)
+            .add_plugin(SavegamePlugin)
+            .add_plugin(AchievementsPlugin)
+            .add_plugin(ProfilesPlugin)
+            .add_plugin(PausePlugin)
+            .add_plugin(RumblePlugin)
+            .add_plugin(AudioManagerPlugin)
+            .add_plugin(MusicPlugin)
+            .add_plugin(WhooshPlugin)
+            .add_plugin(DemoPlugin)
+            .add_plugin(PoolPlugin)
+            .add_plugin(TweenPlugin)
+            .add_plugin(TransitionPlugin)
+            .add_plugin(PhotoPlugin)
+            .add_plugin(ConsolePlugin);
+
+        if config.bot {
+            app.insert_resource(Autopilot);
+        }
+        if let Some(path) = &config.event_log {
+            app.add_plugin(EventLogPlugin { path: path.clone() });
+        }
+        #[cfg(debug_assertions)]
+        app.add_plugin(no_clip::NoClipPlugin);
+        #[cfg(feature = "dev")]
+        app.add_plugin(dev::DevPlugin);
+        #[cfg(feature = "highlight-gif")]
+        app.add_plugin(highlight::HighlightPlugin);
+        #[cfg(feature = "cloud-sync")]
+        app.add_plugin(cloud_sync::CloudSyncPlugin);
+        #[cfg(feature = "discord")]
+        app.add_plugin(discord::DiscordPlugin);
+        #[cfg(feature = "motd")]
+        app.add_plugin(motd::MotdPlugin);
+        #[cfg(feature = "steam")]
+        app.add_plugin(steam::SteamPlugin);
+
+        for state in GameState::variants() {
+            app.add_system(despawn_on_exit_system(state).in_schedule(OnExit(state)));
+        }
+
+        app.add_startup_system(setup)
+            .add_startup_system(spawn_player)
+            .configure_sets(
+                (
+                    GameplaySet::Input,
+                    GameplaySet::Physics,
+                    GameplaySet::Collision,
+                    GameplaySet::Scoring,
+                    GameplaySet::Presentation,
+                )
+                    .chain(),
+            )
+            .add_system(tile_scenery_system.before(infinite_scrolling_system))
+            .add_system(
+                infinite_scrolling_system
+                    .in_set(GameplaySet::Physics)
+                    .run_if(scrolls_horizontally)
+                    .run_if(not_paused),
+            )
+            .add_system(start_game_system.run_if(in_state(GameState::Menu)))
+            .add_system(seed_run_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(reset_pipe_distance_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(reset_world_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(reset_world_system.in_schedule(OnEnter(GameState::LevelComplete)))
+            .add_system(reset_world_system.in_schedule(OnExit(GameState::GameOver)))
+            .add_system(spawn_menu_ui_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(remove_focus_system.in_schedule(OnExit(GameState::Menu)))
+            .add_systems((
+                spawn_pipes_system
+                    .in_set(GameplaySet::Physics)
+                    .after(movement_system)
+                    .run_if(pipe_due)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(endless_run)
+                    .run_if(scrolls_horizontally)
+                    .run_if(not(resource_exists::<BonusStage>()))
+                    .run_if(transition_done),
+                flap_system
+                    .in_set(GameplaySet::Input)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(not_paused)
+                    .run_if(transition_done),
+                gravity_system
+                    .in_set(GameplaySet::Physics)
+                    .before(movement_system)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(not_paused)
+                    .run_if(transition_done),
+                game_over_system
+                    .in_set(GameplaySet::Collision)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(not_paused)
+                    .run_if(transition_done),
+            ))
+            .add_system(
+                player_died_system
+                    .in_set(GameplaySet::Scoring)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_system(update_pipe_spawn_x_system.in_base_set(CoreSet::First))
+            // Photo mode moves the camera while paused, which shouldn't clear pipes.
+            .add_system(
+                despawn_offscreen_system
+                    .in_set(GameplaySet::Collision)
+                    .run_if(not_paused),
+            )
+            // Game over has the bird nose-diving to the floor instead.
+            .add_system(
+                tilt_with_vel_system
+                    .in_set(GameplaySet::Presentation)
+                    .run_if(not(in_state(GameState::GameOver))),
+            )
+            .add_system(
+                movement_system
+                    .in_set(GameplaySet::Physics)
+                    .run_if(not(in_state(GameState::GameOver)))
+                    .run_if(not_paused)
+                    .run_if(transition_done),
+            )
+            .add_system(animate_sprite_system.in_set(GameplaySet::Presentation));
+    }
+}
@@ -0,0 +1,239 @@
+use bevy::{prelude::*, sprite::collide_aabb::collide};
+use rand::Rng;
+
+use crate::{
+    count_collected,
+    difficulty::Difficulty,
+    mode::{endless_run, in_mode, GameMode},
+    pipe_themes::PipeTheme,
+    score::ScorePipe,
+    DespawnWhenOffscreen, GameRng, GameState, GameplaySet, Player, PowerUp, PowerUpCollectedEvent,
+    WorldDirection, FONT, PIPE_WIDTH,
+};
+
+const MAX_LIVES: u32 = 3;
+const INVULNERABILITY_SECS: f32 = 2.;
+const BLINK_SECS: f32 = 0.1;
+/// Chance of a heart spawning in the gap of a new pipe pair.
+const HEART_CHANCE: f64 = 1. / 15.;
+const HEART_COLOR: Color = Color::rgb(0.9, 0.15, 0.2);
+
+/// Remaining lives in a [`GameMode::Lives`] run. Only present during such runs.
+#[derive(Resource)]
+pub struct Lives {
+    remaining: u32,
+    invulnerability: Timer,
+}
+
+impl Default for Lives {
+    fn default() -> Self {
+        let mut invulnerability = Timer::from_seconds(INVULNERABILITY_SECS, TimerMode::Once);
+        invulnerability.tick(invulnerability.duration());
+
+        Self {
+            remaining: MAX_LIVES,
+            invulnerability,
+        }
+    }
+}
+
+impl Lives {
+    pub fn with_remaining(remaining: u32) -> Self {
+        Self {
+            remaining,
+            ..Default::default()
+        }
+    }
+
+    pub fn remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerability.finished()
+    }
+
+    /// Registers a collision, returning whether the player survives it.
+    pub fn take_hit(&mut self) -> bool {
+        if self.is_invulnerable() {
+            return true;
+        }
+
+        self.remaining = self.remaining.saturating_sub(1);
+        self.invulnerability.reset();
+        self.remaining > 0
+    }
+}
+
+#[derive(Component)]
+struct HeartPickup;
+
+#[derive(Component)]
+struct HeartsText;
+
+fn start_lives_system(mut commands: Commands, lives: Option<Res<Lives>>) {
+    if lives.is_none_or(|lives| lives.remaining == 0) {
+        commands.insert_resource(Lives::default());
+    }
+}
+
+fn stop_lives_system(mut commands: Commands, pickups: Query<Entity, With<HeartPickup>>) {
+    commands.remove_resource::<Lives>();
+    for entity in pickups.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn invulnerability_system(
+    time: Res<Time>,
+    mut lives: ResMut<Lives>,
+    mut player: Query<&mut Visibility, With<Player>>,
+) {
+    lives.invulnerability.tick(time.delta());
+
+    let elapsed = lives.invulnerability.elapsed_secs();
+    let blink_off = lives.is_invulnerable() && (elapsed / BLINK_SECS) as u32 % 2 == 1;
+    for mut visibility in player.iter_mut() {
+        *visibility = if blink_off {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+    }
+}
+
+fn spawn_heart_pickups_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    difficulty: Res<Difficulty>,
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    mut rng: ResMut<GameRng>,
+    new_pipes: Query<&Transform, Added<ScorePipe>>,
+) {
+    for pipe in new_pipes.iter() {
+        if !rng.gen_bool(HEART_CHANCE) {
+            continue;
+        }
+
+        commands.spawn((
+            HeartPickup,
+            direction.scroll_velocity(),
+            DespawnWhenOffscreen { margin: PIPE_WIDTH },
+            Text2dBundle {
+                text: Text::from_section(
+                    "♥",
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 36.,
+                        color: HEART_COLOR,
+                    },
+                ),
+                transform: Transform::from_xyz(
+                    pipe.translation.x + theme.width() / 2.,
+                    pipe.translation.y + difficulty.pipe_gap / 2.,
+                    5.,
+                ),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn collect_heart_pickups_system(
+    mut commands: Commands,
+    mut collected: EventWriter<PowerUpCollectedEvent>,
+    player: Query<&Transform, With<Player>>,
+    pickups: Query<(Entity, &Transform), With<HeartPickup>>,
+) {
+    let player = player.single();
+
+    for (entity, transform) in pickups.iter() {
+        let hit = collide(
+            player.translation,
+            Vec2::new(45., 45.),
+            transform.translation,
+            Vec2::new(30., 30.),
+        )
+        .is_some();
+
+        if hit {
+            collected.send(PowerUpCollectedEvent(PowerUp::Heart));
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn spawn_hearts_text_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        HeartsText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 30.,
+                color: HEART_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.),
+                left: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+fn update_hearts_text_system(
+    lives: Option<Res<Lives>>,
+    mut query: Query<&mut Text, With<HeartsText>>,
+) {
+    let hearts = lives.map_or(String::new(), |lives| {
+        "♥".repeat(lives.remaining as usize) + &"♡".repeat((MAX_LIVES - lives.remaining) as usize)
+    });
+
+    for mut text in query.iter_mut() {
+        if text.sections[0].value != hearts {
+            text.sections[0].value = hearts.clone();
+        }
+    }
+}
+
+pub struct LivesPlugin;
+
+fn add_collected_hearts_system(
+    mut collected: EventReader<PowerUpCollectedEvent>,
+    mut lives: ResMut<Lives>,
+) {
+    let hearts = count_collected(&mut collected, PowerUp::Heart);
+    if hearts > 0 {
+        lives.remaining = (lives.remaining + hearts).min(MAX_LIVES);
+    }
+}
+
+impl Plugin for LivesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_hearts_text_system)
+            .add_system(
+                start_lives_system
+                    .run_if(in_mode(GameMode::Lives))
+                    .run_if(endless_run)
+                    .in_schedule(OnEnter(GameState::InGame)),
+            )
+            .add_system(stop_lives_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_systems(
+                (
+                    invulnerability_system,
+                    spawn_heart_pickups_system,
+                    collect_heart_pickups_system.in_set(GameplaySet::Collision),
+                    add_collected_hearts_system.in_set(GameplaySet::Scoring),
+                )
+                    .distributive_run_if(resource_exists::<Lives>())
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(update_hearts_text_system);
+    }
+}
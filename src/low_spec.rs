@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+
+use crate::{screenshot::spawn_toast, settings::Settings};
+
+/// How fast sprites animate in low-spec mode, as a fraction of the usual rate.
+const ANIMATION_RATE: f32 = 0.5;
+/// Frames slower than this on average count as struggling. That's below 25
+/// FPS, comfortably under what the 30 FPS cap gives.
+const SLOW_FRAME_SECS: f32 = 0.04;
+/// Seconds in a row of slow frames before low-spec mode is suggested.
+const SLOW_SECS_BEFORE_SUGGESTING: u32 = 5;
+
+/// How far animations move on this frame, slowed down in low-spec mode.
+pub fn animation_delta(time: &Time, settings: &Settings) -> Duration {
+    if settings.low_spec {
+        time.delta().mul_f32(ANIMATION_RATE)
+    } else {
+        time.delta()
+    }
+}
+
+/// Frame times over the current second.
+#[derive(Default)]
+struct FrameTimes {
+    frames: u32,
+    secs: f32,
+    /// Seconds in a row that averaged slower than [`SLOW_FRAME_SECS`].
+    slow_secs: u32,
+    suggested: bool,
+}
+
+/// Points towards low-spec mode, once a session, when the game has been
+/// struggling for several seconds.
+fn suggest_low_spec_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    mut frame_times: Local<FrameTimes>,
+) {
+    if settings.low_spec || frame_times.suggested {
+        return;
+    }
+
+    frame_times.frames += 1;
+    frame_times.secs += time.raw_delta_seconds();
+    if frame_times.secs < 1. {
+        return;
+    }
+
+    let average = frame_times.secs / frame_times.frames as f32;
+    frame_times.slow_secs = if average > SLOW_FRAME_SECS {
+        frame_times.slow_secs + 1
+    } else {
+        0
+    };
+    frame_times.frames = 0;
+    frame_times.secs = 0.;
+
+    if frame_times.slow_secs >= SLOW_SECS_BEFORE_SUGGESTING {
+        frame_times.suggested = true;
+        spawn_toast(
+            &mut commands,
+            &asset_server,
+            "Running slowly? Try low-spec mode in the settings".into(),
+        );
+    }
+}
+
+/// A preset for slower machines that drops the trail and the skyline and
+/// animates sprites at a lower rate, suggested when frames run slow.
+pub struct LowSpecPlugin;
+
+impl Plugin for LowSpecPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(suggest_low_spec_system);
+    }
+}
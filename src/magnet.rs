@@ -0,0 +1,239 @@
+use bevy::{
+    prelude::*,
+    sprite::{collide_aabb::collide, MaterialMesh2dBundle},
+};
+use rand::Rng;
+
+use crate::{
+    coins::Coin, count_collected, difficulty::Difficulty, mode::endless_run,
+    pipe_themes::PipeTheme, score::ScorePipe, DespawnWhenOffscreen, GameRng, GameState,
+    GameplaySet, Player, PowerUp, PowerUpCollectedEvent, RunScoped, Velocity, WorldDirection, FONT,
+    PIPE_WIDTH,
+};
+
+/// Chance of a magnet floating between a new pipe pair and the next one.
+const MAGNET_CHANCE: f64 = 1. / 15.;
+/// Short of where coins float, so the two don't overlap.
+const MAGNET_OFFSET_X: f32 = PIPE_WIDTH + 45.;
+const MAGNET_SIZE: Vec2 = Vec2::new(28., 28.);
+const MAGNET_SECS: f32 = 8.;
+/// Coins closer than this are pulled in.
+const PULL_RADIUS: f32 = 180.;
+/// Pixels per frame a coin moves towards the bird at the edge of the radius,
+/// rising as it gets closer.
+const PULL_SPEED: f32 = 6.;
+const MAGNET_COLOR: Color = Color::rgb(0.4, 0.7, 1.);
+
+/// Present while a magnet power-up is pulling coins in.
+#[derive(Resource)]
+pub struct Magnet {
+    timer: Timer,
+}
+
+#[derive(Component)]
+struct MagnetPickup;
+
+/// Glow around the bird while a magnet is active.
+#[derive(Component)]
+struct MagnetAura;
+
+#[derive(Component)]
+struct MagnetText;
+
+fn spawn_magnet_pickups_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    difficulty: Res<Difficulty>,
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    mut rng: ResMut<GameRng>,
+    new_pipes: Query<&Transform, Added<ScorePipe>>,
+) {
+    for pipe in new_pipes.iter() {
+        if !rng.gen_bool(MAGNET_CHANCE) {
+            continue;
+        }
+
+        commands.spawn((
+            MagnetPickup,
+            RunScoped,
+            direction.scroll_velocity(),
+            DespawnWhenOffscreen {
+                margin: MAGNET_SIZE.x,
+            },
+            Text2dBundle {
+                text: Text::from_section(
+                    "U",
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 32.,
+                        color: MAGNET_COLOR,
+                    },
+                ),
+                // Upside down, like a horseshoe magnet.
+                transform: Transform::from_xyz(
+                    direction.past_pipe(pipe.translation.x, theme.width(), MAGNET_OFFSET_X),
+                    pipe.translation.y + difficulty.pipe_gap / 2.,
+                    5.,
+                )
+                .with_rotation(Quat::from_rotation_z(std::f32::consts::PI)),
+                ..Default::default()
+            },
+        ));
+    }
+}
+
+fn collect_magnet_pickups_system(
+    mut commands: Commands,
+    mut collected: EventWriter<PowerUpCollectedEvent>,
+    player: Query<&Transform, With<Player>>,
+    pickups: Query<(Entity, &Transform), With<MagnetPickup>>,
+) {
+    let player = player.single();
+
+    for (entity, transform) in pickups.iter() {
+        let hit = collide(
+            player.translation,
+            Vec2::new(45., 45.),
+            transform.translation,
+            MAGNET_SIZE,
+        )
+        .is_some();
+
+        if hit {
+            collected.send(PowerUpCollectedEvent(PowerUp::Magnet));
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+/// Steers coins in range towards the bird and lets the rest drift with the
+/// pipes again, so coins stop mid-pull when the magnet runs out.
+fn attract_coins_system(
+    magnet: Option<Res<Magnet>>,
+    direction: Res<WorldDirection>,
+    player: Query<&Transform, With<Player>>,
+    mut coins: Query<(&Transform, &mut Velocity), With<Coin>>,
+) {
+    let player = player.single().translation.truncate();
+
+    for (transform, mut velocity) in coins.iter_mut() {
+        let to_player = player - transform.translation.truncate();
+        let distance = to_player.length();
+
+        let pull = if magnet.is_some() && distance < PULL_RADIUS {
+            to_player.normalize_or_zero() * PULL_SPEED * (2. - distance / PULL_RADIUS)
+        } else {
+            let drift = direction.scroll_velocity();
+            Vec2::new(drift.x, drift.y)
+        };
+        velocity.x = pull.x;
+        velocity.y = pull.y;
+    }
+}
+
+fn tick_magnet_system(mut commands: Commands, time: Res<Time>, mut magnet: ResMut<Magnet>) {
+    if magnet.timer.tick(time.delta()).finished() {
+        commands.remove_resource::<Magnet>();
+    }
+}
+
+fn stop_magnet_system(mut commands: Commands) {
+    commands.remove_resource::<Magnet>();
+}
+
+fn spawn_magnet_hud_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<ColorMaterial>>,
+) {
+    commands.spawn((
+        MagnetAura,
+        MaterialMesh2dBundle {
+            mesh: meshes.add(shape::Circle::new(40.).into()).into(),
+            material: materials.add(ColorMaterial::from(MAGNET_COLOR.with_a(0.25))),
+            visibility: Visibility::Hidden,
+            ..Default::default()
+        },
+    ));
+    commands.spawn((
+        MagnetText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 22.,
+                color: MAGNET_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(120.),
+                right: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+/// Keeps the aura on the bird, pulsing, and shows the time left.
+fn update_magnet_hud_system(
+    time: Res<Time>,
+    magnet: Option<Res<Magnet>>,
+    player: Query<&Transform, (With<Player>, Without<MagnetAura>)>,
+    mut aura: Query<(&mut Transform, &mut Visibility), With<MagnetAura>>,
+    mut text: Query<&mut Text, With<MagnetText>>,
+) {
+    let player = player.single().translation;
+    for (mut transform, mut visibility) in aura.iter_mut() {
+        *visibility = if magnet.is_some() {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        transform.translation = player.truncate().extend(4.);
+        transform.scale = Vec3::splat(1. + 0.08 * (time.elapsed_seconds() * 5.).sin());
+    }
+
+    let value = magnet.map_or(String::new(), |magnet| {
+        format!("MAGNET {:.1}s", magnet.timer.remaining_secs())
+    });
+    for mut text in text.iter_mut() {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+fn start_magnet_system(mut commands: Commands, mut collected: EventReader<PowerUpCollectedEvent>) {
+    // Picking up another magnet restarts the timer.
+    if count_collected(&mut collected, PowerUp::Magnet) > 0 {
+        commands.insert_resource(Magnet {
+            timer: Timer::from_seconds(MAGNET_SECS, TimerMode::Once),
+        });
+    }
+}
+
+pub struct MagnetPlugin;
+
+impl Plugin for MagnetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(spawn_magnet_hud_system)
+            .add_systems(
+                (
+                    spawn_magnet_pickups_system.run_if(endless_run),
+                    collect_magnet_pickups_system.in_set(GameplaySet::Collision),
+                    start_magnet_system.in_set(GameplaySet::Scoring),
+                    attract_coins_system,
+                    tick_magnet_system.run_if(resource_exists::<Magnet>()),
+                )
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(stop_magnet_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(stop_magnet_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(update_magnet_hud_system);
+    }
+}
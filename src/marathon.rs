@@ -0,0 +1,43 @@
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+
+use crate::{
+    daily::{civil_from_days, today},
+    mode::{endless_run, in_mode, GameMode},
+    score::Score,
+    seed_run_system, GameRng, GameState, RunSeed,
+};
+
+/// Seed of the marathon layout for the month `day` falls in, written out as
+/// the year and month, so 202610 for October 2026. Anyone can work it out,
+/// and it never comes near a daily seed, which counts days.
+pub fn marathon_seed(day: i64) -> u64 {
+    let (year, month, _) = civil_from_days(day);
+    (year * 100 + i64::from(month)) as u64
+}
+
+/// Seeds the pipes from the month. Like daily runs, marathon runs can't be
+/// continued, so every one starts here.
+fn start_marathon_system(score: Res<Score>, mut seed: ResMut<RunSeed>, mut rng: ResMut<GameRng>) {
+    if score.0 > 0 {
+        return;
+    }
+
+    seed.current = marathon_seed(today());
+    rng.0 = ChaCha12Rng::seed_from_u64(seed.current);
+}
+
+pub struct MarathonPlugin;
+
+impl Plugin for MarathonPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            start_marathon_system
+                .after(seed_run_system)
+                .run_if(in_mode(GameMode::Marathon))
+                .run_if(endless_run)
+                .in_schedule(OnEnter(GameState::InGame)),
+        );
+    }
+}
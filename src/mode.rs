@@ -0,0 +1,169 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    focus::MenuInput, level::LevelPlayback, practice::Practice, DespawnOnExit, GameState, FONT,
+};
+
+/// Rule set for runs started from the menu with Space.
+#[derive(Resource, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    #[default]
+    Classic,
+    /// Three lives instead of one, with heart pickups.
+    Lives,
+    /// F shoots a limited supply of projectiles that break pipes.
+    Arcade,
+    /// Classic rules on the pipe layout everyone gets today.
+    Daily,
+    /// The world scrolls down and the bird steers sideways through gaps in
+    /// pipes lying across its way.
+    Climb,
+    /// Classic rules on a long layout that stays the same all month.
+    Marathon,
+    /// One life and a solid ceiling, with no checkpoints or saved sessions to
+    /// fall back on.
+    Hardcore,
+}
+
+impl GameMode {
+    pub const ALL: [GameMode; 7] = [
+        GameMode::Classic,
+        GameMode::Lives,
+        GameMode::Arcade,
+        GameMode::Daily,
+        GameMode::Climb,
+        GameMode::Marathon,
+        GameMode::Hardcore,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            GameMode::Classic => "Classic",
+            GameMode::Lives => "Lives",
+            GameMode::Arcade => "Arcade",
+            GameMode::Daily => "Daily",
+            GameMode::Climb => "Climb",
+            GameMode::Marathon => "Marathon",
+            GameMode::Hardcore => "Hardcore",
+        }
+    }
+
+    fn step(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|mode| *mode == self).unwrap_or(0) as isize;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
+/// What stops the bird flying over the top of the pipes.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Ceiling {
+    /// Flying off the top of the screen is a crash.
+    Solid,
+    /// The bird is held at the top of the screen, and has to come back down
+    /// through the gaps since top pipes reach past it.
+    Clamped,
+}
+
+impl GameMode {
+    pub fn ceiling(self) -> Ceiling {
+        match self {
+            GameMode::Lives | GameMode::Arcade | GameMode::Hardcore => Ceiling::Solid,
+            GameMode::Classic | GameMode::Daily | GameMode::Climb | GameMode::Marathon => {
+                Ceiling::Clamped
+            }
+        }
+    }
+
+    /// Whether runs can be picked back up from a checkpoint or a saved session.
+    /// Not in modes where everyone's scores are compared on the same layout, or
+    /// where the point is having no second chances.
+    pub fn allows_continues(self) -> bool {
+        !matches!(
+            self,
+            GameMode::Daily | GameMode::Marathon | GameMode::Hardcore
+        )
+    }
+}
+
+pub fn in_mode(mode: GameMode) -> impl FnMut(Res<GameMode>) -> bool {
+    move |current: Res<GameMode>| *current == mode
+}
+
+pub fn continues_allowed(mode: Res<GameMode>) -> bool {
+    mode.allows_continues()
+}
+
+/// Whether the current run spawns random pipes forever, as opposed to playing a
+/// level or a practice drill.
+pub fn endless_run(level: Option<Res<LevelPlayback>>, practice: Option<Res<Practice>>) -> bool {
+    level.is_none() && practice.is_none()
+}
+
+#[derive(Component)]
+struct ModeLabel;
+
+fn spawn_mode_label_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Menu),
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::top(Val::Px(120.)),
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                ModeLabel,
+                TextBundle::from_section(
+                    "",
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 24.,
+                        color: Color::WHITE,
+                    },
+                ),
+            ));
+        });
+}
+
+fn cycle_mode_system(menu_input: MenuInput, mut mode: ResMut<GameMode>) {
+    let step = menu_input.adjustment();
+    if step != 0 {
+        *mode = mode.step(step);
+    }
+}
+
+fn update_mode_label_system(
+    mode: Res<GameMode>,
+    mut query: Query<&mut Text, With<ModeLabel>>,
+    added: Query<(), Added<ModeLabel>>,
+) {
+    if !mode.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = format!("Mode: < {} >", mode.name());
+    }
+}
+
+pub struct ModePlugin;
+
+impl Plugin for ModePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>()
+            .add_system(spawn_mode_label_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_systems(
+                (cycle_mode_system, update_mode_label_system)
+                    .chain()
+                    .in_set(OnUpdate(GameState::Menu)),
+            );
+    }
+}
@@ -0,0 +1,231 @@
+use bevy::prelude::*;
+
+use crate::{
+    resolution::WorldScale, screenshot::spawn_toast, DespawnOnExit, GameState, GameplaySet, Pipe,
+    Player, WorldDirection, FONT,
+};
+
+use KeyCode::{Down, Left, Right, Up};
+
+/// How much bigger the bird is drawn with [`Modifier::BigHead`]. Its hitbox
+/// stays the same.
+const BIG_HEAD_SCALE: f32 = 1.6;
+/// Fraction of normal gravity with [`Modifier::LowGravity`].
+pub const LOW_GRAVITY: f32 = 0.5;
+/// Hue change per second and per pixel across the screen of rainbow pipes.
+const RAINBOW_DEGREES_PER_SEC: f32 = 120.;
+const RAINBOW_DEGREES_PER_PIXEL: f32 = 0.5;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Modifier {
+    BigHead,
+    LowGravity,
+    RainbowPipes,
+    /// The world flipped horizontally, with pipes coming in from the left.
+    Mirrored,
+    /// Only a circle around the bird can be seen.
+    FogOfWar,
+}
+
+impl Modifier {
+    const ALL: [Modifier; 5] = [
+        Modifier::BigHead,
+        Modifier::LowGravity,
+        Modifier::RainbowPipes,
+        Modifier::Mirrored,
+        Modifier::FogOfWar,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Modifier::BigHead => "big head",
+            Modifier::LowGravity => "low gravity",
+            Modifier::RainbowPipes => "rainbow pipes",
+            Modifier::Mirrored => "mirrored",
+            Modifier::FogOfWar => "fog of war",
+        }
+    }
+
+    /// Arrow keys typed on the menu to toggle the modifier.
+    fn code(self) -> &'static [KeyCode] {
+        match self {
+            Modifier::BigHead => &[Up, Up, Down, Down, Left, Right],
+            Modifier::LowGravity => &[Down, Down, Up, Up, Right, Left],
+            Modifier::RainbowPipes => &[Left, Right, Left, Right, Up, Down],
+            Modifier::Mirrored => &[Right, Left, Right, Left, Down, Up],
+            Modifier::FogOfWar => &[Up, Down, Up, Down, Left, Left],
+        }
+    }
+}
+
+/// Modifiers toggled with cheat codes. Runs played with any of them on are
+/// marked as such wherever their score is shown.
+#[derive(Resource, Default)]
+pub struct ActiveModifiers(Vec<Modifier>);
+
+impl ActiveModifiers {
+    pub fn is_active(&self, modifier: Modifier) -> bool {
+        self.0.contains(&modifier)
+    }
+
+    pub fn any(&self) -> bool {
+        !self.0.is_empty()
+    }
+
+    /// Names of the active modifiers, for flagging scores.
+    pub fn describe(&self) -> String {
+        self.0
+            .iter()
+            .map(|modifier| modifier.name())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+
+    fn toggle(&mut self, modifier: Modifier) -> bool {
+        if let Some(index) = self.0.iter().position(|active| *active == modifier) {
+            self.0.remove(index);
+            false
+        } else {
+            self.0.push(modifier);
+            true
+        }
+    }
+}
+
+/// Listens for cheat codes among the arrow keys pressed on the menu.
+fn cheat_code_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut typed: Local<Vec<KeyCode>>,
+    mut modifiers: ResMut<ActiveModifiers>,
+) {
+    for key in keyboard_input.get_just_pressed() {
+        if !matches!(key, Up | Down | Left | Right) {
+            typed.clear();
+            continue;
+        }
+        typed.push(*key);
+
+        let entered = Modifier::ALL
+            .into_iter()
+            .find(|modifier| typed.ends_with(modifier.code()));
+        if let Some(modifier) = entered {
+            typed.clear();
+            let state = if modifiers.toggle(modifier) {
+                "on"
+            } else {
+                "off"
+            };
+            spawn_toast(
+                &mut commands,
+                &asset_server,
+                format!("Cheat: {} {state}", modifier.name()),
+            );
+        }
+    }
+}
+
+/// How big the bird is drawn with the cheats in `modifiers`.
+pub fn bird_scale(modifiers: &ActiveModifiers, world_scale: WorldScale) -> Vec3 {
+    if modifiers.is_active(Modifier::BigHead) {
+        world_scale.sprite() * BIG_HEAD_SCALE
+    } else {
+        world_scale.sprite()
+    }
+}
+
+fn big_head_system(
+    modifiers: Res<ActiveModifiers>,
+    world_scale: Res<WorldScale>,
+    mut player: Query<&mut Transform, With<Player>>,
+) {
+    if !modifiers.is_changed() && !world_scale.is_changed() {
+        return;
+    }
+
+    for mut transform in player.iter_mut() {
+        transform.scale = bird_scale(&modifiers, *world_scale);
+    }
+}
+
+fn mirror_world_system(
+    modifiers: Res<ActiveModifiers>,
+    mut direction: ResMut<WorldDirection>,
+    mut player: Query<(&mut Transform, &mut TextureAtlasSprite), With<Player>>,
+) {
+    if !modifiers.is_changed() {
+        return;
+    }
+
+    *direction = if modifiers.is_active(Modifier::Mirrored) {
+        WorldDirection::Left
+    } else {
+        WorldDirection::Right
+    };
+    for (mut transform, mut sprite) in player.iter_mut() {
+        transform.translation.x = direction.player_x();
+        sprite.flip_x = *direction == WorldDirection::Left;
+    }
+}
+
+fn rainbow_pipes_system(
+    time: Res<Time>,
+    modifiers: Res<ActiveModifiers>,
+    mut pipes: Query<(&Transform, &mut Sprite), With<Pipe>>,
+) {
+    if !modifiers.is_active(Modifier::RainbowPipes) {
+        return;
+    }
+
+    for (transform, mut sprite) in pipes.iter_mut() {
+        let hue = time.elapsed_seconds() * RAINBOW_DEGREES_PER_SEC
+            + transform.translation.x * RAINBOW_DEGREES_PER_PIXEL;
+        sprite.color = Color::hsl(hue.rem_euclid(360.), 0.8, 0.6);
+    }
+}
+
+/// Flags a run as modified while it's being played.
+fn spawn_modifiers_label_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    modifiers: Res<ActiveModifiers>,
+) {
+    if !modifiers.any() {
+        return;
+    }
+
+    commands.spawn((
+        DespawnOnExit(GameState::InGame),
+        TextBundle::from_section(
+            format!("Modified run: {}", modifiers.describe()),
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 14.,
+                color: Color::YELLOW,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(90.),
+                left: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+pub struct ModifiersPlugin;
+
+impl Plugin for ModifiersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveModifiers>()
+            .add_system(cheat_code_system.run_if(in_state(GameState::Menu)))
+            .add_system(big_head_system)
+            .add_system(mirror_world_system)
+            .add_system(rainbow_pipes_system.in_set(GameplaySet::Presentation))
+            .add_system(spawn_modifiers_label_system.in_schedule(OnEnter(GameState::InGame)));
+    }
+}
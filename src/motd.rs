@@ -0,0 +1,229 @@
+use std::{
+    io::{Read, Write},
+    net::{TcpStream, ToSocketAddrs},
+    sync::{
+        mpsc::{self, Receiver},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{share::seed_code, storage, touch::SafeArea, DespawnOnExit, GameState, FONT};
+
+/// Short enough that a dead server is given up on before anyone looks for news.
+const TIMEOUT: Duration = Duration::from_secs(5);
+/// Anything longer than this isn't a message of the day.
+const MAX_RESPONSE_BYTES: u64 = 16 * 1024;
+const PANEL_WIDTH: f32 = 220.;
+
+/// Where to fetch news from, read as RON from the platform's config directory.
+/// Without one the game never touches the network.
+///
+/// Only plain `http://` URLs are supported, like for cloud sync.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+struct MotdConfig {
+    /// Like `http://example.com/flappybird/motd.json`.
+    url: String,
+}
+
+impl storage::SaveFile for MotdConfig {
+    const VERSION: u32 = 1;
+}
+
+impl MotdConfig {
+    const FILE: &'static str = "motd.ron";
+
+    fn load() -> Option<Self> {
+        storage::config_path(Self::FILE).and_then(|path| storage::load(&path))
+    }
+
+    /// Fetches and parses the message with a bare HTTP/1.0 `GET`.
+    fn fetch(&self) -> Result<Motd, String> {
+        let rest = self
+            .url
+            .strip_prefix("http://")
+            .ok_or_else(|| format!("unsupported URL {}, only http:// is", self.url))?;
+        let (authority, path) = rest.split_once('/').unwrap_or((rest, ""));
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (
+                host,
+                port.parse()
+                    .map_err(|_| format!("invalid port in {}", self.url))?,
+            ),
+            None => (authority, 80),
+        };
+
+        let address = (host, port)
+            .to_socket_addrs()
+            .map_err(|err| err.to_string())?
+            .next()
+            .ok_or_else(|| format!("no address for {host}"))?;
+        let mut stream =
+            TcpStream::connect_timeout(&address, TIMEOUT).map_err(|err| err.to_string())?;
+        stream
+            .set_read_timeout(Some(TIMEOUT))
+            .and_then(|_| stream.set_write_timeout(Some(TIMEOUT)))
+            .map_err(|err| err.to_string())?;
+        write!(stream, "GET /{path} HTTP/1.0\r\nHost: {host}\r\n\r\n")
+            .map_err(|err| err.to_string())?;
+
+        let mut response = String::new();
+        stream
+            .take(MAX_RESPONSE_BYTES)
+            .read_to_string(&mut response)
+            .map_err(|err| err.to_string())?;
+        let (head, body) = response.split_once("\r\n\r\n").unwrap_or((&response, ""));
+        match head.split_whitespace().nth(1) {
+            Some("200") => {
+                serde_json::from_str(body).map_err(|err| format!("invalid message: {err}"))
+            }
+            Some(status) => Err(format!("GET failed with status {status}")),
+            None => Err("malformed response".into()),
+        }
+    }
+}
+
+/// News shown on the menu, fetched as JSON like
+/// `{"title": "Weekend event", "message": "Double coins!", "seed": 42}`.
+#[derive(Deserialize, Clone, Debug)]
+struct Motd {
+    #[serde(default)]
+    title: Option<String>,
+    message: String,
+    /// A seed to race on, shown as a code to enter from the menu.
+    #[serde(default)]
+    seed: Option<u64>,
+}
+
+impl Motd {
+    fn text(&self) -> String {
+        let mut text = self.message.clone();
+        if let Some(seed) = self.seed {
+            text += &format!("\nSeed {} (V to enter)", seed_code(seed));
+        }
+        text
+    }
+}
+
+/// Hands the message over from the thread fetching it, once it's arrived.
+#[derive(Resource)]
+struct MotdReceiver(Mutex<Receiver<Motd>>);
+
+/// The message, once fetched. Stays `None` for the session if fetching failed.
+#[derive(Resource, Default)]
+struct LatestMotd(Option<Motd>);
+
+#[derive(Component)]
+struct MotdPanel;
+
+fn receive_motd_system(
+    mut commands: Commands,
+    receiver: Res<MotdReceiver>,
+    mut latest: ResMut<LatestMotd>,
+) {
+    match receiver.0.lock().unwrap().try_recv() {
+        Ok(motd) => latest.0 = Some(motd),
+        Err(mpsc::TryRecvError::Empty) => return,
+        Err(mpsc::TryRecvError::Disconnected) => {}
+    }
+    commands.remove_resource::<MotdReceiver>();
+}
+
+/// Puts the message in the corner of the menu, also when it only arrives after
+/// the menu is already up.
+fn spawn_motd_panel_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    safe_area: Res<SafeArea>,
+    latest: Res<LatestMotd>,
+    panels: Query<(), With<MotdPanel>>,
+) {
+    let Some(motd) = &latest.0 else {
+        return;
+    };
+    if !panels.is_empty() {
+        return;
+    }
+
+    let font = asset_server.load(FONT);
+    commands
+        .spawn((
+            MotdPanel,
+            DespawnOnExit(GameState::Menu),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(safe_area.top + 10.),
+                        right: Val::Px(safe_area.right + 10.),
+                        ..Default::default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    padding: UiRect::all(Val::Px(8.)),
+                    max_size: Size::width(Val::Px(PANEL_WIDTH)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            if let Some(title) = &motd.title {
+                parent.spawn(TextBundle::from_section(
+                    title,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size: 18.,
+                        color: Color::YELLOW,
+                    },
+                ));
+            }
+            parent.spawn(
+                TextBundle::from_section(
+                    motd.text(),
+                    TextStyle {
+                        font,
+                        font_size: 14.,
+                        color: Color::WHITE,
+                    },
+                )
+                .with_style(Style {
+                    max_size: Size::width(Val::Px(PANEL_WIDTH - 16.)),
+                    ..Default::default()
+                }),
+            );
+        });
+}
+
+/// A news panel on the menu, fetched once at startup from the URL set in
+/// `motd.ron`. Nothing is shown when offline or without one.
+pub struct MotdPlugin;
+
+impl Plugin for MotdPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LatestMotd>()
+            .add_system(spawn_motd_panel_system.in_set(OnUpdate(GameState::Menu)));
+
+        let Some(config) = MotdConfig::load() else {
+            return;
+        };
+
+        let (sender, receiver) = mpsc::channel();
+        thread::Builder::new()
+            .name("motd".into())
+            .spawn(move || match config.fetch() {
+                Ok(motd) => {
+                    let _ = sender.send(motd);
+                }
+                Err(err) => debug!("No message of the day from {}: {err}", config.url),
+            })
+            .expect("failed to spawn message of the day thread");
+
+        app.insert_resource(MotdReceiver(Mutex::new(receiver)))
+            .add_system(receive_motd_system.run_if(resource_exists::<MotdReceiver>()));
+    }
+}
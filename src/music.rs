@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::{
+    audio::{AudioManager, Sound},
+    score::Score,
+};
+
+/// Layers on top of the base loop and the score they come in above.
+const LAYERS: [(Sound, u32); 2] = [(Sound::MusicDrums, 10), (Sound::MusicLead, 25)];
+
+/// A layer takes this long to fade all the way in or out.
+const FADE_SECS: f32 = 2.;
+
+/// Starts every layer at once so they stay in step, with all but the base
+/// silent.
+fn start_music_system(mut audio: AudioManager) {
+    audio.play_looped(Sound::MusicBase, 1.);
+    for (layer, _) in LAYERS {
+        audio.play_looped(layer, 0.);
+    }
+}
+
+/// Fades layers in as the score climbs past their threshold, and back out once
+/// it drops again at the end of a run.
+fn music_director_system(time: Res<Time>, score: Res<Score>, mut audio: AudioManager) {
+    let step = time.delta_seconds() / FADE_SECS;
+
+    for (layer, threshold) in LAYERS {
+        let target = if score.0 > threshold { 1. } else { 0. };
+        let gain = audio.loop_gain(layer);
+        if gain != target {
+            let gain = if target > gain {
+                (gain + step).min(target)
+            } else {
+                (gain - step).max(target)
+            };
+            audio.set_loop_gain(layer, gain);
+        }
+    }
+}
+
+pub struct MusicPlugin;
+
+impl Plugin for MusicPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(start_music_system)
+            .add_system(music_director_system);
+    }
+}
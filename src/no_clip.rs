@@ -0,0 +1,49 @@
+use bevy::prelude::*;
+
+use crate::{
+    cosmetics::{Customization, ItemKind},
+    screenshot::spawn_toast,
+    Player,
+};
+
+/// Tint of the bird while it can't die.
+const NO_CLIP_COLOR: Color = Color::rgba(0.6, 0.8, 1., 0.6);
+
+/// Present while the bird flies through pipes and hazards, so pipe spawning and
+/// scrolling can be watched for as long as needed. Only in debug builds.
+#[derive(Resource)]
+pub struct NoClip;
+
+fn toggle_no_clip_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<Input<KeyCode>>,
+    no_clip: Option<Res<NoClip>>,
+    customization: Res<Customization>,
+    mut player: Query<&mut TextureAtlasSprite, With<Player>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F8) {
+        return;
+    }
+
+    let color = if no_clip.is_some() {
+        commands.remove_resource::<NoClip>();
+        spawn_toast(&mut commands, &asset_server, "No-clip off".to_string());
+        customization.color(ItemKind::Skin)
+    } else {
+        commands.insert_resource(NoClip);
+        spawn_toast(&mut commands, &asset_server, "No-clip on".to_string());
+        NO_CLIP_COLOR
+    };
+    for mut sprite in player.iter_mut() {
+        sprite.color = color;
+    }
+}
+
+pub struct NoClipPlugin;
+
+impl Plugin for NoClipPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(toggle_no_clip_system);
+    }
+}
@@ -0,0 +1,176 @@
+use bevy::{input::touch::Touches, prelude::*, window::WindowFocused};
+
+use crate::{photo::photo_mode_off, touch::SafeArea, GameState, FONT, WINDOW_WIDTH};
+
+/// Size of the pause button's square, big enough to hit with a thumb.
+const BUTTON_SIZE: f32 = 48.;
+const BUTTON_MARGIN: f32 = 10.;
+
+/// Present while a run is paused. Everything in [`OnUpdate`] of
+/// [`GameState::InGame`] stops and [`Time`] is frozen, so timers don't run out
+/// in the meantime.
+#[derive(Resource)]
+pub struct Paused;
+
+pub fn not_paused(paused: Option<Res<Paused>>) -> bool {
+    paused.is_none()
+}
+
+/// Whether a point in window coordinates lands on the pause button, so tapping
+/// it doesn't also flap. The button grows with `ui_scale`.
+pub fn on_pause_button(position: Vec2, safe_area: &SafeArea, ui_scale: f32) -> bool {
+    let right = WINDOW_WIDTH - (safe_area.right + BUTTON_MARGIN) * ui_scale;
+    let top = (safe_area.top + BUTTON_MARGIN) * ui_scale;
+    let size = BUTTON_SIZE * ui_scale;
+    (right - size..=right).contains(&position.x) && (top..=top + size).contains(&position.y)
+}
+
+#[derive(Component)]
+struct PauseButton;
+
+#[derive(Component)]
+struct PauseUi;
+
+fn spawn_pause_button_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    safe_area: Res<SafeArea>,
+) {
+    commands
+        .spawn((
+            PauseButton,
+            ButtonBundle {
+                style: Style {
+                    size: Size::all(Val::Px(BUTTON_SIZE)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(safe_area.top + BUTTON_MARGIN),
+                        right: Val::Px(safe_area.right + BUTTON_MARGIN),
+                        ..Default::default()
+                    },
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.3).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "II",
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 24.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+fn despawn_pause_ui_system(
+    mut commands: Commands,
+    mut time: ResMut<Time>,
+    button: Query<Entity, With<PauseButton>>,
+    pause_ui: Query<Entity, With<PauseUi>>,
+) {
+    resume(&mut commands, &mut time, &pause_ui);
+    for entity in button.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn pause(commands: &mut Commands, asset_server: &AssetServer, time: &mut Time) {
+    commands.insert_resource(Paused);
+    time.pause();
+
+    commands
+        .spawn((
+            PauseUi,
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.4).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            let font = asset_server.load(FONT);
+            parent.spawn(TextBundle::from_section(
+                "Paused",
+                TextStyle {
+                    font: font.clone(),
+                    font_size: 40.,
+                    color: Color::WHITE,
+                },
+            ));
+            parent.spawn(TextBundle::from_section(
+                "Tap or Esc to resume\nP: photo mode",
+                TextStyle {
+                    font,
+                    font_size: 18.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+fn resume(commands: &mut Commands, time: &mut Time, pause_ui: &Query<Entity, With<PauseUi>>) {
+    commands.remove_resource::<Paused>();
+    time.unpause();
+    for entity in pause_ui.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Pauses with Esc, the pause button or when the window loses focus, like when
+/// switching apps on a phone, and resumes with Esc or a tap anywhere. Changes
+/// only apply once the frame's systems have run, so the resuming tap doesn't
+/// also flap.
+#[allow(clippy::too_many_arguments)]
+fn toggle_pause_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut time: ResMut<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    touches: Res<Touches>,
+    mut focused: EventReader<WindowFocused>,
+    paused: Option<Res<Paused>>,
+    button: Query<&Interaction, (Changed<Interaction>, With<PauseButton>)>,
+    pause_ui: Query<Entity, With<PauseUi>>,
+) {
+    let escape = keyboard_input.just_pressed(KeyCode::Escape);
+    let button_pressed = button
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked);
+    let lost_focus = focused.iter().any(|event| !event.focused);
+
+    if paused.is_some() {
+        if escape || button_pressed || touches.any_just_pressed() {
+            resume(&mut commands, &mut time, &pause_ui);
+        }
+    } else if escape || button_pressed || lost_focus {
+        pause(&mut commands, &asset_server, &mut time);
+    }
+}
+
+pub struct PausePlugin;
+
+impl Plugin for PausePlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_set(OnUpdate(GameState::InGame).run_if(not_paused))
+            .add_system(spawn_pause_button_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(despawn_pause_ui_system.in_schedule(OnExit(GameState::InGame)))
+            .add_system(
+                toggle_pause_system
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(photo_mode_off),
+            );
+    }
+}
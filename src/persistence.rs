@@ -0,0 +1,36 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+
+use crate::{profiles::Profiles, settings::Settings, storage};
+
+/// Reads and writes the player's files. The active profile and its settings
+/// are loaded before the app is built, pulling them from the cloud first when
+/// that's set up, so the window can be opened as they say. Without this the
+/// game runs on defaults and saves nothing.
+pub struct PersistencePlugin {
+    pub profiles: Profiles,
+    pub settings: Settings,
+}
+
+impl PersistencePlugin {
+    /// Loads from `config_dir` instead of the platform's config directory if
+    /// given.
+    pub fn load(config_dir: Option<PathBuf>) -> Self {
+        storage::enable(config_dir);
+        let profiles = Profiles::load();
+        profiles.activate();
+        #[cfg(feature = "cloud-sync")]
+        crate::cloud_sync::pull_on_startup();
+        let settings = Settings::load();
+
+        Self { profiles, settings }
+    }
+}
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(self.profiles.clone())
+            .insert_resource(self.settings.clone());
+    }
+}
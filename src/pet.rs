@@ -0,0 +1,185 @@
+use std::f32::consts::{PI, TAU};
+
+use bevy::prelude::*;
+
+use crate::{
+    coins::{collect_coin, touches_coin, Coin},
+    cosmetics::{Customization, ItemKind},
+    resolution::WorldScale,
+    score::ScoredEvent,
+    AnimationIndices, AnimationTimer, GameState, GameplaySet, Player, PowerUpCollectedEvent,
+    WorldDirection,
+};
+
+/// How big the pet is drawn next to the bird.
+const PET_SCALE: f32 = 0.5;
+/// Where the pet keeps to, relative to the bird while flying right.
+const FOLLOW_OFFSET: Vec2 = Vec2::new(-50., 25.);
+/// How quickly the pet closes in on where it wants to be, per second.
+const FOLLOW_RATE: f32 = 6.;
+/// In front of the pipes, like the bird.
+const PET_Z: f32 = 0.5;
+const CELEBRATE_SECS: f32 = 0.5;
+const HOP_HEIGHT: f32 = 20.;
+/// The pet that picks up coins.
+const COLLECTOR: &str = "pet.collector";
+/// Coins closer than this to the collector are picked up.
+const COLLECT_RADIUS: f32 = 50.;
+
+/// Companion trailing after the bird, hopping and flipping whenever a point is
+/// scored.
+#[derive(Component)]
+struct Pet {
+    /// Where it has followed the bird to, before any hop.
+    position: Vec2,
+    celebration: Timer,
+}
+
+/// Swaps the pet for the equipped one, or none.
+#[allow(clippy::too_many_arguments)]
+fn spawn_pet_system(
+    mut commands: Commands,
+    mut atlas: Local<Option<Handle<TextureAtlas>>>,
+    mut texture_atlases: ResMut<Assets<TextureAtlas>>,
+    asset_server: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+    customization: Res<Customization>,
+    player: Query<&Transform, With<Player>>,
+    new_player: Query<(), Added<Player>>,
+    pets: Query<Entity, With<Pet>>,
+) {
+    if !customization.is_changed() && new_player.is_empty() && !world_scale.is_changed() {
+        return;
+    }
+    if world_scale.is_changed() {
+        *atlas = None;
+    }
+
+    for entity in pets.iter() {
+        commands.entity(entity).despawn();
+    }
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    if customization.equipped(ItemKind::Pet) == "pet.none" {
+        return;
+    }
+
+    let texture_atlas = atlas
+        .get_or_insert_with(|| {
+            texture_atlases.add(world_scale.atlas(
+                &asset_server,
+                "bird.png",
+                Vec2::new(20., 20.),
+                4,
+                1,
+            ))
+        })
+        .clone();
+    let mut celebration = Timer::from_seconds(CELEBRATE_SECS, TimerMode::Once);
+    celebration.tick(celebration.duration());
+    let position = player.translation.truncate() + FOLLOW_OFFSET;
+
+    commands.spawn((
+        Pet {
+            position,
+            celebration,
+        },
+        SpriteSheetBundle {
+            texture_atlas,
+            sprite: TextureAtlasSprite {
+                color: customization.color(ItemKind::Pet),
+                ..TextureAtlasSprite::new(0)
+            },
+            transform: Transform::from_translation(position.extend(PET_Z))
+                .with_scale(world_scale.sprite() * PET_SCALE),
+            ..Default::default()
+        },
+        AnimationIndices { first: 0, last: 3 },
+        AnimationTimer(Timer::from_seconds(0.1, TimerMode::Repeating)),
+    ));
+}
+
+fn celebrate_system(mut passed: EventReader<ScoredEvent>, mut pets: Query<&mut Pet>) {
+    if passed.iter().count() == 0 {
+        return;
+    }
+    for mut pet in pets.iter_mut() {
+        pet.celebration.reset();
+    }
+}
+
+/// Eases the pet towards its spot behind the bird, so it lags a little behind
+/// flaps and swoops.
+fn follow_bird_system(
+    time: Res<Time>,
+    direction: Res<WorldDirection>,
+    player: Query<&Transform, (With<Player>, Without<Pet>)>,
+    mut pets: Query<(&mut Pet, &mut Transform, &mut TextureAtlasSprite)>,
+) {
+    let Ok(player) = player.get_single() else {
+        return;
+    };
+    let target = player.translation.truncate() + FOLLOW_OFFSET * Vec2::new(direction.sign(), 1.);
+    let catch_up = 1. - (-FOLLOW_RATE * time.delta_seconds()).exp();
+
+    for (mut pet, mut transform, mut sprite) in pets.iter_mut() {
+        pet.position = pet.position.lerp(target, catch_up);
+        pet.celebration.tick(time.delta());
+
+        let (hop, spin) = if pet.celebration.finished() {
+            (0., 0.)
+        } else {
+            let t = pet.celebration.percent();
+            ((t * PI).sin() * HOP_HEIGHT, t * TAU * direction.sign())
+        };
+        transform.translation = (pet.position + Vec2::Y * hop).extend(PET_Z);
+        transform.rotation = Quat::from_rotation_z(spin);
+        sprite.flip_x = *direction == WorldDirection::Left;
+    }
+}
+
+/// The collector picks up coins that float past near it, leaving those the
+/// bird is touching to the bird.
+fn collect_coins_system(
+    mut commands: Commands,
+    mut collected: EventWriter<PowerUpCollectedEvent>,
+    customization: Res<Customization>,
+    player: Query<&Transform, With<Player>>,
+    pets: Query<&Transform, With<Pet>>,
+    coins: Query<(Entity, &Transform), With<Coin>>,
+) {
+    if customization.equipped(ItemKind::Pet) != COLLECTOR {
+        return;
+    }
+    let (Ok(player), Ok(pet)) = (player.get_single(), pets.get_single()) else {
+        return;
+    };
+
+    for (entity, coin) in coins.iter() {
+        let near_pet = coin
+            .translation
+            .truncate()
+            .distance(pet.translation.truncate())
+            < COLLECT_RADIUS;
+        if near_pet && !touches_coin(player.translation, coin.translation) {
+            collect_coin(&mut commands, &mut collected, entity);
+        }
+    }
+}
+
+pub struct PetPlugin;
+
+impl Plugin for PetPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(spawn_pet_system)
+            .add_system(follow_bird_system.in_set(GameplaySet::Presentation))
+            .add_systems(
+                (
+                    celebrate_system.in_set(GameplaySet::Scoring),
+                    collect_coins_system.in_set(GameplaySet::Collision),
+                )
+                    .in_set(OnUpdate(GameState::InGame)),
+            );
+    }
+}
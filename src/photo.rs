@@ -0,0 +1,159 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    console::console_closed,
+    pause::Paused,
+    screenshot::{screenshots_dir, spawn_capture_camera, timestamp, CapturePurpose},
+    GameState, MainCamera,
+};
+
+/// Pixels per second the camera pans at its normal zoom.
+const PAN_SPEED: f32 = 300.;
+/// How much the view grows or shrinks per second while zooming.
+const ZOOM_SPEED: f32 = 1.5;
+const MIN_ZOOM: f32 = 0.25;
+const MAX_ZOOM: f32 = 2.;
+
+/// Present while the paused world is being looked around in with the HUD
+/// hidden. Holds where the camera was so it can be put back.
+#[derive(Resource)]
+pub struct PhotoMode {
+    transform: Transform,
+    zoom: f32,
+}
+
+pub fn photo_mode_off(photo_mode: Option<Res<PhotoMode>>) -> bool {
+    photo_mode.is_none()
+}
+
+fn enter_photo_mode_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    camera: Query<(Entity, &Transform, &OrthographicProjection), With<MainCamera>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::P) {
+        return;
+    }
+
+    let (entity, transform, projection) = camera.single();
+    commands
+        .entity(entity)
+        .insert(UiCameraConfig { show_ui: false });
+    commands.insert_resource(PhotoMode {
+        transform: *transform,
+        zoom: projection.scale,
+    });
+}
+
+fn leave_photo_mode(
+    commands: &mut Commands,
+    photo_mode: &PhotoMode,
+    camera: &mut Query<(Entity, &mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    let (entity, mut transform, mut projection) = camera.single_mut();
+    *transform = photo_mode.transform;
+    projection.scale = photo_mode.zoom;
+    commands.entity(entity).remove::<UiCameraConfig>();
+    commands.remove_resource::<PhotoMode>();
+}
+
+/// Arrow keys pan, +/- zoom, Enter saves what's in view without the HUD and
+/// Esc or P goes back to the pause screen. Time is frozen, so this goes by
+/// real time.
+fn photo_mode_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    photo_mode: Res<PhotoMode>,
+    mut images: ResMut<Assets<Image>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut camera: Query<(Entity, &mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    if keyboard_input.any_just_pressed([KeyCode::Escape, KeyCode::P]) {
+        leave_photo_mode(&mut commands, &photo_mode, &mut camera);
+        return;
+    }
+
+    let delta = time.raw_delta_seconds();
+    let (_, mut transform, mut projection) = camera.single_mut();
+
+    let mut pan = Vec2::ZERO;
+    for (key, direction) in [
+        (KeyCode::Left, Vec2::NEG_X),
+        (KeyCode::Right, Vec2::X),
+        (KeyCode::Up, Vec2::Y),
+        (KeyCode::Down, Vec2::NEG_Y),
+    ] {
+        if keyboard_input.pressed(key) {
+            pan += direction;
+        }
+    }
+    transform.translation += (pan * PAN_SPEED * projection.scale * delta).extend(0.);
+
+    let zoom_in = keyboard_input.any_pressed([KeyCode::Equals, KeyCode::NumpadAdd]);
+    let zoom_out = keyboard_input.any_pressed([KeyCode::Minus, KeyCode::NumpadSubtract]);
+    if zoom_in != zoom_out {
+        let factor = ZOOM_SPEED.powf(delta);
+        let scale = if zoom_in {
+            projection.scale / factor
+        } else {
+            projection.scale * factor
+        };
+        projection.scale = scale.clamp(MIN_ZOOM, MAX_ZOOM);
+    }
+
+    if !keyboard_input.just_pressed(KeyCode::Return) {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+    let path = screenshots_dir().join(format!("photo-{}.png", timestamp()));
+    let size = UVec2::new(
+        window.resolution.width() as u32,
+        window.resolution.height() as u32,
+    );
+    let capture = spawn_capture_camera(
+        &mut commands,
+        &mut images,
+        size,
+        CapturePurpose::Screenshot(path),
+    );
+    commands.entity(capture).insert((
+        *transform,
+        projection.clone(),
+        UiCameraConfig { show_ui: false },
+    ));
+}
+
+/// Leaving the run some other way, like from the console, still puts the camera
+/// back.
+fn close_photo_mode_system(
+    mut commands: Commands,
+    photo_mode: Option<Res<PhotoMode>>,
+    mut camera: Query<(Entity, &mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+) {
+    if let Some(photo_mode) = photo_mode {
+        leave_photo_mode(&mut commands, &photo_mode, &mut camera);
+    }
+}
+
+pub struct PhotoPlugin;
+
+impl Plugin for PhotoPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            enter_photo_mode_system
+                .run_if(in_state(GameState::InGame))
+                .run_if(resource_exists::<Paused>())
+                .run_if(photo_mode_off)
+                .run_if(console_closed),
+        )
+        .add_system(
+            photo_mode_system
+                .run_if(resource_exists::<PhotoMode>())
+                .run_if(console_closed),
+        )
+        .add_system(close_photo_mode_system.in_schedule(OnExit(GameState::InGame)));
+    }
+}
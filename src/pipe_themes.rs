@@ -0,0 +1,164 @@
+use bevy::{
+    asset::{AssetLoader, LoadContext, LoadedAsset},
+    prelude::*,
+    reflect::TypeUuid,
+    utils::BoxedFuture,
+};
+use serde::Deserialize;
+
+use crate::{
+    cosmetics::{Customization, ItemKind},
+    resolution::{ManifestTextures, WorldScale},
+    Pipe, PIPE_HEIGHT, PIPE_WIDTH, SCALE,
+};
+
+/// Lists the pipe themes that come with their own texture.
+const MANIFEST: &str = "pipe.themes.ron";
+
+fn default_width() -> f32 {
+    PIPE_WIDTH / SCALE.x
+}
+
+/// A pipe theme drawn from its own texture, for the shop item `item`.
+#[derive(Deserialize, Debug)]
+struct ThemeEntry {
+    item: String,
+    texture: String,
+    /// In the `@1x` texture's own pixels.
+    #[serde(default = "default_width")]
+    width: f32,
+}
+
+/// The pipe theme manifest, stored as a `.themes.ron` asset. Themes missing
+/// from it tint the classic `pipe.png`.
+#[derive(Deserialize, TypeUuid, Debug)]
+#[uuid = "5d0b7a3e-2f6c-4b9e-9a41-c8e3f1d27b60"]
+struct PipeThemes {
+    themes: Vec<ThemeEntry>,
+}
+
+#[derive(Default)]
+struct PipeThemesLoader;
+
+impl AssetLoader for PipeThemesLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let themes: PipeThemes = ron::de::from_bytes(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(themes));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["themes.ron"]
+    }
+}
+
+#[derive(Resource)]
+struct Manifest(Handle<PipeThemes>);
+
+/// Texture and size of the pipes of the equipped theme. Anything measuring a
+/// pipe goes by this rather than [`PIPE_WIDTH`], since themes can be wider or
+/// narrower than the classic pipe.
+#[derive(Resource, Clone, Debug)]
+pub struct PipeTheme {
+    pub texture: Handle<Image>,
+    /// In world pixels, upright.
+    pub size: Vec2,
+}
+
+impl PipeTheme {
+    pub fn width(&self) -> f32 {
+        self.size.x
+    }
+}
+
+impl FromWorld for PipeTheme {
+    fn from_world(world: &mut World) -> Self {
+        let world_scale = *world.resource::<WorldScale>();
+        Self {
+            texture: world_scale.load(world.resource::<AssetServer>(), "pipe.png"),
+            size: Vec2::new(PIPE_WIDTH, PIPE_HEIGHT),
+        }
+    }
+}
+
+fn load_manifest_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(Manifest(asset_server.load(MANIFEST)));
+}
+
+/// Picks the texture and size for the equipped theme whenever it changes, the
+/// manifest (re)loads or the [`WorldScale`] changes.
+#[allow(clippy::too_many_arguments)]
+fn pick_theme_system(
+    asset_server: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+    customization: Res<Customization>,
+    manifest: Res<Manifest>,
+    manifests: Res<Assets<PipeThemes>>,
+    mut manifest_events: EventReader<AssetEvent<PipeThemes>>,
+    mut theme: ResMut<PipeTheme>,
+    mut manifest_textures: ResMut<ManifestTextures>,
+) {
+    let manifest_changed = manifest_events.iter().count() > 0;
+    if !customization.is_changed() && !manifest_changed && !world_scale.is_changed() {
+        return;
+    }
+    if manifest_changed {
+        if let Some(manifest) = manifests.get(&manifest.0) {
+            let textures = manifest
+                .themes
+                .iter()
+                .map(|theme| theme.texture.clone())
+                .collect();
+            manifest_textures.set(MANIFEST, textures);
+        }
+    }
+
+    let equipped = customization.equipped(ItemKind::PipeTheme);
+    let entry = manifests
+        .get(&manifest.0)
+        .and_then(|manifest| manifest.themes.iter().find(|theme| theme.item == equipped));
+    let (texture, width) = match entry {
+        Some(entry) => (entry.texture.as_str(), entry.width * SCALE.x),
+        None => ("pipe.png", PIPE_WIDTH),
+    };
+    let size = Vec2::new(width, PIPE_HEIGHT);
+    let texture = world_scale.load(&asset_server, texture);
+    if theme.texture != texture || theme.size != size {
+        *theme = PipeTheme { texture, size };
+    }
+}
+
+/// Redraws pipes already on screen, like the demo's behind the shop, in the new
+/// theme.
+fn retheme_pipes_system(
+    theme: Res<PipeTheme>,
+    mut pipes: Query<(&mut Handle<Image>, &mut Sprite), With<Pipe>>,
+) {
+    if !theme.is_changed() {
+        return;
+    }
+
+    for (mut texture, mut sprite) in pipes.iter_mut() {
+        *texture = theme.texture.clone();
+        sprite.custom_size = Some(theme.size / SCALE.truncate());
+    }
+}
+
+/// Pipe themes with their own textures and widths, picked in the shop.
+pub struct PipeThemesPlugin;
+
+impl Plugin for PipeThemesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_asset::<PipeThemes>()
+            .init_asset_loader::<PipeThemesLoader>()
+            .init_resource::<PipeTheme>()
+            .add_startup_system(load_manifest_system)
+            .add_systems((pick_theme_system, retheme_pipes_system).chain());
+    }
+}
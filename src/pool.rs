@@ -0,0 +1,157 @@
+use bevy::{prelude::*, window::PrimaryWindow};
+
+use crate::{
+    checkpoint::CheckpointPipe,
+    climb::ClimbGap,
+    level::MovingPipe,
+    pipe_themes::PipeTheme,
+    resolution::WorldScale,
+    score::{Crossed, ScorePipe},
+    tween::{Ease, Scale, Tween, TweenCompleted},
+    whoosh::Whoosh,
+    DespawnWhenOffscreen, Pipe, Velocity, PIPE_SPACING, PIPE_SPAWN_X, PIPE_WIDTH, SCALE,
+    WINDOW_WIDTH,
+};
+
+/// How long a released pipe takes to shrink away before it's hidden.
+const SHRINK_SECS: f32 = 0.2;
+/// How small a pipe gets, relative to its usual size, before it's hidden.
+const SHRINK_TO: f32 = 0.6;
+
+/// How many pipes are on screen at once in a window `window_width` wide: a pair
+/// spawns every [`PIPE_SPACING`] and is removed once it has scrolled from
+/// [`PIPE_SPAWN_X`] past the left edge of the window. One spare pair covers
+/// pipes that are removed late.
+fn pool_size(window_width: f32) -> usize {
+    let travel = PIPE_SPAWN_X + window_width / 2. + PIPE_WIDTH;
+    ((travel / PIPE_SPACING).ceil() as usize + 1) * 2
+}
+
+/// A released pipe shrinking away, to be hidden and handed back to the pool
+/// once it's done.
+#[derive(Component)]
+struct Released;
+
+/// Hidden pipe entities waiting to be reused, so pipes aren't spawned and
+/// despawned every second. It grows by spawning a pipe whenever none is free,
+/// like after the window is made wider.
+#[derive(Resource, Default)]
+pub struct PipePool {
+    free: Vec<Entity>,
+}
+
+impl PipePool {
+    /// Shows a pipe with `sprite` moving at `velocity`, reusing a free entity if
+    /// there is one.
+    pub fn spawn(
+        &mut self,
+        commands: &mut Commands,
+        sprite: SpriteBundle,
+        velocity: Velocity,
+    ) -> Entity {
+        let pipe = (
+            Pipe,
+            velocity,
+            DespawnWhenOffscreen { margin: PIPE_WIDTH },
+            sprite,
+        );
+        match self.free.pop() {
+            Some(entity) => {
+                commands.entity(entity).insert(pipe);
+                entity
+            }
+            None => commands.spawn(pipe).id(),
+        }
+    }
+
+    /// Strips everything that made a pipe part of a run, including its
+    /// [`ScoreZone`](crate::score::ScoreZone), and shrinks it away until
+    /// [`PipePool::spawn`] hands it out again. Pipes are drawn at the size of
+    /// `theme`, which it shrinks them about the centre of.
+    pub fn release(&mut self, commands: &mut Commands, entity: Entity, theme: &PipeTheme) {
+        if self.free.contains(&entity) {
+            return;
+        }
+
+        commands.entity(entity).despawn_descendants();
+        commands
+            .entity(entity)
+            .remove::<(
+                Pipe,
+                ScorePipe,
+                CheckpointPipe,
+                ClimbGap,
+                Crossed,
+                Whoosh,
+                MovingPipe,
+                DespawnWhenOffscreen,
+            )>()
+            .insert((
+                Released,
+                Tween::new(
+                    Scale {
+                        start: SCALE,
+                        end: SCALE * SHRINK_TO,
+                        // About the centre, since sprites are anchored at their
+                        // top left corner.
+                        pivot: (theme.size * Vec2::new(1., -1.)).extend(0.) / SCALE / 2.,
+                    },
+                    SHRINK_SECS,
+                    Ease::QuadIn,
+                ),
+            ));
+    }
+}
+
+fn return_released_system(
+    mut commands: Commands,
+    mut pool: ResMut<PipePool>,
+    mut completed: EventReader<TweenCompleted>,
+    mut released: Query<&mut Velocity, With<Released>>,
+) {
+    for TweenCompleted { entity } in completed.iter() {
+        let Ok(mut velocity) = released.get_mut(*entity) else {
+            continue;
+        };
+        *velocity = Velocity { x: 0., y: 0. };
+        commands
+            .entity(*entity)
+            .remove::<Released>()
+            .insert(Visibility::Hidden);
+        pool.free.push(*entity);
+    }
+}
+
+fn fill_pool_system(
+    mut commands: Commands,
+    asset: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut pool: ResMut<PipePool>,
+) {
+    let window_width = windows.get_single().map_or(WINDOW_WIDTH, Window::width);
+    let texture = world_scale.load(&asset, "pipe.png");
+    for _ in 0..pool_size(window_width) {
+        let entity = commands
+            .spawn((
+                Velocity { x: 0., y: 0. },
+                SpriteBundle {
+                    texture: texture.clone(),
+                    visibility: Visibility::Hidden,
+                    ..Default::default()
+                },
+            ))
+            .id();
+        pool.free.push(entity);
+    }
+}
+
+pub struct PoolPlugin;
+
+impl Plugin for PoolPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PipePool>()
+            .add_startup_system(fill_pool_system)
+            .add_system(return_released_system);
+    }
+}
@@ -0,0 +1,349 @@
+use bevy::prelude::*;
+use rand::Rng;
+
+use crate::{
+    difficulty::Difficulty,
+    focus::{remove_focus_system, Focus, Focusable, MenuInput},
+    pipe_pair_sprites,
+    pipe_themes::PipeTheme,
+    pool::PipePool,
+    DespawnOnExit, GameRng, GameState, PipeSpawnX, WorldDirection, FONT, MIN_PIPE_OFFSET, PIPE_GAP,
+    WINDOW_HEIGHT,
+};
+
+const GAP_RANGE: (f32, f32) = (100., 220.);
+const GAP_STEP: f32 = 10.;
+const SPACING_RANGE: (f32, f32) = (0.6, 2.);
+const SPACING_STEP: f32 = 0.1;
+/// Largest change in gap height between neighbouring corridor pipes.
+const CORRIDOR_STEP: f32 = 40.;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum PracticePattern {
+    Random,
+    /// Gaps alternate between the top and bottom of the screen.
+    HighLow,
+    /// Gaps drift only slightly from one pipe to the next.
+    Corridor,
+}
+
+impl PracticePattern {
+    const ALL: [PracticePattern; 3] = [
+        PracticePattern::Random,
+        PracticePattern::HighLow,
+        PracticePattern::Corridor,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            PracticePattern::Random => "Random",
+            PracticePattern::HighLow => "High-low",
+            PracticePattern::Corridor => "Corridor",
+        }
+    }
+
+    fn step(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|p| *p == self).unwrap_or(0) as isize;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+}
+
+/// Options picked on the practice screen, kept between drills.
+#[derive(Resource, Clone)]
+struct PracticeSettings {
+    gap: f32,
+    /// Seconds between pipe pairs.
+    spacing: f32,
+    pattern: PracticePattern,
+}
+
+impl Default for PracticeSettings {
+    fn default() -> Self {
+        Self {
+            gap: PIPE_GAP,
+            spacing: 1.,
+            pattern: PracticePattern::Random,
+        }
+    }
+}
+
+/// A practice drill in progress. Present from starting the drill until returning
+/// to the menu; pipes are spawned from its settings and nothing is scored.
+#[derive(Resource)]
+pub struct Practice {
+    settings: PracticeSettings,
+    timer: Timer,
+    pipes_spawned: u32,
+    last_gap_bottom: f32,
+}
+
+impl Practice {
+    fn new(settings: PracticeSettings) -> Self {
+        let mut timer = Timer::from_seconds(settings.spacing, TimerMode::Repeating);
+        timer.tick(timer.duration());
+
+        Self {
+            settings,
+            timer,
+            pipes_spawned: 0,
+            last_gap_bottom: -PIPE_GAP / 2.,
+        }
+    }
+
+    fn next_gap_bottom(&mut self, rng: &mut impl Rng) -> f32 {
+        let lowest = -WINDOW_HEIGHT / 2. + MIN_PIPE_OFFSET;
+        let highest = WINDOW_HEIGHT / 2. - MIN_PIPE_OFFSET - self.settings.gap;
+        let gap_bottom = match self.settings.pattern {
+            PracticePattern::Random => rng.gen_range(lowest..highest),
+            PracticePattern::HighLow if self.pipes_spawned.is_multiple_of(2) => highest,
+            PracticePattern::HighLow => lowest,
+            PracticePattern::Corridor => {
+                let step = rng.gen_range(-CORRIDOR_STEP..CORRIDOR_STEP);
+                (self.last_gap_bottom + step).clamp(lowest, highest)
+            }
+        };
+
+        self.pipes_spawned += 1;
+        self.last_gap_bottom = gap_bottom;
+        gap_bottom
+    }
+}
+
+/// Label showing the value of the option at this row.
+#[derive(Component)]
+struct PracticeLabel(usize);
+
+const OPTION_NAMES: [&str; 3] = ["Gap", "Spacing", "Pattern"];
+
+fn option_label(settings: &PracticeSettings, row: usize, selected: bool) -> String {
+    let value = match row {
+        0 => format!("{}", settings.gap),
+        1 => format!("{:.1}s", settings.spacing),
+        _ => settings.pattern.name().to_string(),
+    };
+
+    if selected {
+        format!("{}: < {value} >", OPTION_NAMES[row])
+    } else {
+        format!("{}: {value}", OPTION_NAMES[row])
+    }
+}
+
+fn open_practice_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::P) {
+        next_state.set(GameState::Practice);
+    }
+}
+
+fn spawn_practice_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<PracticeSettings>,
+) {
+    commands.insert_resource(Focus::new(OPTION_NAMES.len()));
+
+    let font = asset_server.load(FONT);
+    let style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Practice),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(12.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Practice", style(40.)));
+            for row in 0..OPTION_NAMES.len() {
+                parent.spawn((
+                    PracticeLabel(row),
+                    Focusable(row),
+                    TextBundle::from_section(option_label(&settings, row, row == 0), style(22.)),
+                ));
+            }
+            parent.spawn(TextBundle::from_section(
+                "Up/Down: select   Left/Right: change\nEnter: start   Esc: back",
+                style(16.),
+            ));
+        });
+}
+
+fn practice_input_system(
+    mut commands: Commands,
+    menu_input: MenuInput,
+    focus: Res<Focus>,
+    mut settings: ResMut<PracticeSettings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if menu_input.back() {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    // Any row starts the drill.
+    if focus.activated() {
+        commands.insert_resource(Practice::new(settings.clone()));
+        next_state.set(GameState::InGame);
+        return;
+    }
+
+    let step = menu_input.adjustment();
+    if step == 0 {
+        return;
+    }
+
+    match focus.index {
+        0 => settings.gap = (settings.gap + step as f32 * GAP_STEP).clamp(GAP_RANGE.0, GAP_RANGE.1),
+        1 => {
+            let spacing = settings.spacing + step as f32 * SPACING_STEP;
+            // Round away the float error that builds up from repeated steps.
+            settings.spacing = ((spacing / SPACING_STEP).round() * SPACING_STEP)
+                .clamp(SPACING_RANGE.0, SPACING_RANGE.1)
+        }
+        _ => settings.pattern = settings.pattern.step(step),
+    }
+}
+
+fn update_practice_ui_system(
+    settings: Res<PracticeSettings>,
+    focus: Res<Focus>,
+    mut query: Query<(&mut Text, &PracticeLabel)>,
+) {
+    if !settings.is_changed() && !focus.is_changed() {
+        return;
+    }
+
+    for (mut text, label) in query.iter_mut() {
+        text.sections[0].value = option_label(&settings, label.0, label.0 == focus.index);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_practice_pipes_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    difficulty: Res<Difficulty>,
+    theme: Res<PipeTheme>,
+    mut practice: ResMut<Practice>,
+    mut pool: ResMut<PipePool>,
+    mut rng: ResMut<GameRng>,
+    direction: Res<WorldDirection>,
+    spawn_x: Res<PipeSpawnX>,
+) {
+    // Sped up along with the game, so pipes stay as far apart.
+    if !practice
+        .timer
+        .tick(time.delta().mul_f32(difficulty.speed))
+        .just_finished()
+    {
+        return;
+    }
+
+    let gap_bottom = practice.next_gap_bottom(&mut rng.0);
+    let sprites = pipe_pair_sprites(
+        &theme,
+        direction.pipe_spawn_x(&spawn_x, theme.width()),
+        gap_bottom,
+        practice.settings.gap,
+    );
+    for sprite in sprites {
+        pool.spawn(&mut commands, sprite, direction.scroll_velocity());
+    }
+}
+
+fn restart_practice_timer_system(mut practice: ResMut<Practice>) {
+    let settings = practice.settings.clone();
+    *practice = Practice::new(settings);
+}
+
+fn spawn_retry_hint_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands
+        .spawn((
+            DespawnOnExit(GameState::GameOver),
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::bottom(Val::Px(150.)),
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "R: retry drill",
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 18.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+fn retry_practice_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::R) {
+        next_state.set(GameState::InGame);
+    }
+}
+
+fn stop_practice_system(mut commands: Commands) {
+    commands.remove_resource::<Practice>();
+}
+
+pub struct PracticePlugin;
+
+impl Plugin for PracticePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PracticeSettings>()
+            .add_system(open_practice_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_practice_ui_system.in_schedule(OnEnter(GameState::Practice)))
+            .add_system(remove_focus_system.in_schedule(OnExit(GameState::Practice)))
+            .add_systems(
+                (practice_input_system, update_practice_ui_system)
+                    .in_set(OnUpdate(GameState::Practice)),
+            )
+            .add_system(
+                spawn_practice_pipes_system
+                    .run_if(resource_exists::<Practice>())
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(
+                restart_practice_timer_system
+                    .run_if(resource_exists::<Practice>())
+                    .in_schedule(OnExit(GameState::GameOver)),
+            )
+            .add_system(
+                spawn_retry_hint_system
+                    .run_if(resource_exists::<Practice>())
+                    .in_schedule(OnEnter(GameState::GameOver)),
+            )
+            .add_system(
+                retry_practice_system
+                    .run_if(resource_exists::<Practice>())
+                    .in_set(OnUpdate(GameState::GameOver)),
+            )
+            .add_system(stop_practice_system.in_schedule(OnEnter(GameState::Menu)));
+    }
+}
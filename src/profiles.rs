@@ -0,0 +1,270 @@
+use bevy::{prelude::*, window::ReceivedCharacter};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievements::Achievements,
+    coins::Wallet,
+    cosmetics::Customization,
+    daily::DailyBests,
+    focus::{Focus, MenuInput},
+    leaderboard::Leaderboard,
+    savegame::SavegameAction,
+    session,
+    settings::Settings,
+    speedrun::PersonalBests,
+    stats::Stats,
+    storage, DespawnOnExit, GameState, FONT,
+};
+
+/// Keeps its files where they were before profiles existed.
+const DEFAULT_PROFILE: &str = "Default";
+const MAX_NAME_LEN: usize = 16;
+
+/// Every profile and the one in use, persisted as RON in the platform's data
+/// directory alongside, rather than inside, the profiles' own files.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Profiles {
+    pub names: Vec<String>,
+    pub active: String,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Self {
+            names: vec![DEFAULT_PROFILE.into()],
+            active: DEFAULT_PROFILE.into(),
+        }
+    }
+}
+
+impl storage::SaveFile for Profiles {
+    const VERSION: u32 = 1;
+}
+
+impl Profiles {
+    const FILE: &'static str = "profiles.ron";
+
+    pub fn load() -> Self {
+        storage::shared_data_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default()
+    }
+
+    fn save(&self) {
+        if let Some(path) = storage::shared_data_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+
+    /// Points storage at the active profile's files. Has to happen before
+    /// anything profile-specific is loaded.
+    pub fn activate(&self) {
+        storage::set_profile((self.active != DEFAULT_PROFILE).then(|| self.active.clone()));
+    }
+}
+
+/// Name being typed for a new profile.
+#[derive(Resource, Default)]
+struct NewProfileName(Option<String>);
+
+#[derive(Component)]
+struct ProfilesText;
+
+fn valid_name_char(c: char) -> bool {
+    c.is_alphanumeric() || c == ' ' || c == '-' || c == '_'
+}
+
+/// Reloads everything that's saved per profile after switching to another one.
+fn load_profile(commands: &mut Commands) {
+    commands.insert_resource(Settings::load());
+    commands.insert_resource(Stats::load());
+    commands.insert_resource(DailyBests::load());
+    commands.insert_resource(Leaderboard::load());
+    commands.insert_resource(Wallet::load());
+    commands.insert_resource(Customization::load());
+    commands.insert_resource(Achievements::load());
+    commands.insert_resource(PersonalBests::load());
+    session::reload_session(commands);
+}
+
+/// Only asks which profile to play as when there's more than one.
+fn choose_profile_on_startup_system(
+    profiles: Res<Profiles>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if profiles.names.len() > 1 {
+        next_state.set(GameState::Profiles);
+    }
+}
+
+fn open_profiles_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::U) {
+        next_state.set(GameState::Profiles);
+    }
+}
+
+fn spawn_profiles_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    profiles: Res<Profiles>,
+) {
+    let active = profiles
+        .names
+        .iter()
+        .position(|name| *name == profiles.active)
+        .unwrap_or(0);
+    commands.insert_resource(Focus::at(active, profiles.names.len()));
+    commands.insert_resource(NewProfileName::default());
+
+    let font = asset_server.load(FONT);
+    let style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Profiles),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(12.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Profiles", style(40.)));
+            parent.spawn((ProfilesText, TextBundle::from_section("", style(22.))));
+            parent.spawn(TextBundle::from_section(
+                "Up/Down: select   Enter: play as\nN: new profile   X: export   I: import\nEsc: back",
+                style(16.),
+            ));
+        });
+}
+
+fn remove_profile_selection_system(mut commands: Commands) {
+    commands.remove_resource::<Focus>();
+    commands.remove_resource::<NewProfileName>();
+}
+
+#[allow(clippy::too_many_arguments)]
+fn profiles_input_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    menu_input: MenuInput,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut profiles: ResMut<Profiles>,
+    mut focus: ResMut<Focus>,
+    mut new_name: ResMut<NewProfileName>,
+    mut savegame: EventWriter<SavegameAction>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    // Typing a new name takes over the keyboard until it's confirmed or cancelled.
+    if let Some(name) = new_name.0.as_mut() {
+        for character in characters.iter() {
+            if valid_name_char(character.char) && name.chars().count() < MAX_NAME_LEN {
+                name.push(character.char);
+            }
+        }
+        if keyboard_input.just_pressed(KeyCode::Back) {
+            name.pop();
+        }
+
+        if keyboard_input.just_pressed(KeyCode::Escape) {
+            new_name.0 = None;
+        } else if keyboard_input.just_pressed(KeyCode::Return) {
+            let name = name.trim().to_string();
+            let taken = profiles
+                .names
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&name));
+            if !name.is_empty() && !taken {
+                profiles.names.push(name);
+                profiles.save();
+                focus.set_len(profiles.names.len());
+                focus.index = profiles.names.len() - 1;
+                new_name.0 = None;
+            }
+        }
+        return;
+    }
+    characters.clear();
+
+    if menu_input.back() {
+        next_state.set(GameState::Menu);
+    } else if keyboard_input.just_pressed(KeyCode::N) {
+        new_name.0 = Some(String::new());
+    } else if keyboard_input.just_pressed(KeyCode::X) {
+        savegame.send(SavegameAction::Export);
+    } else if keyboard_input.just_pressed(KeyCode::I) {
+        savegame.send(SavegameAction::Import);
+    } else if focus.activated() {
+        let name = profiles.names[focus.index].clone();
+        if name != profiles.active {
+            profiles.active = name;
+            profiles.save();
+            profiles.activate();
+            load_profile(&mut commands);
+        }
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn update_profiles_ui_system(
+    profiles: Res<Profiles>,
+    focus: Res<Focus>,
+    new_name: Res<NewProfileName>,
+    mut query: Query<&mut Text, With<ProfilesText>>,
+) {
+    if !profiles.is_changed() && !focus.is_changed() && !new_name.is_changed() {
+        return;
+    }
+
+    let mut lines = profiles
+        .names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| {
+            let active = if *name == profiles.active { " *" } else { "" };
+            if index == focus.index && new_name.0.is_none() {
+                format!("> {name}{active} <")
+            } else {
+                format!("{name}{active}")
+            }
+        })
+        .collect::<Vec<_>>();
+    if let Some(name) = &new_name.0 {
+        lines.push(format!("New: {name}_"));
+    }
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+pub struct ProfilesPlugin;
+
+impl Plugin for ProfilesPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(choose_profile_on_startup_system)
+            .add_system(open_profiles_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_profiles_ui_system.in_schedule(OnEnter(GameState::Profiles)))
+            .add_system(remove_profile_selection_system.in_schedule(OnExit(GameState::Profiles)))
+            .add_systems(
+                (profiles_input_system, update_profiles_ui_system)
+                    .chain()
+                    .in_set(OnUpdate(GameState::Profiles)),
+            );
+    }
+}
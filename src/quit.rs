@@ -0,0 +1,108 @@
+use bevy::{app::AppExit, prelude::*};
+
+use crate::{stats::SessionStats, DespawnOnExit, GameState, FONT};
+
+/// How long the session summary stays up before the game closes by itself.
+const SUMMARY_SECS: f32 = 4.;
+
+#[derive(Resource)]
+struct QuitTimer(Timer);
+
+fn open_quit_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Q) {
+        next_state.set(GameState::Quitting);
+    }
+}
+
+fn spawn_summary_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    session: Res<SessionStats>,
+) {
+    commands.insert_resource(QuitTimer(Timer::from_seconds(
+        SUMMARY_SECS,
+        TimerMode::Once,
+    )));
+
+    let font = asset_server.load(FONT);
+    let style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    let rows = [
+        ("Runs played", session.runs),
+        ("Best score", session.best_score),
+        ("Coins earned", session.coins),
+    ];
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Quitting),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(12.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("This session", style(40.)));
+            for (label, value) in rows {
+                parent.spawn(TextBundle::from_section(
+                    format!("{label}: {value}"),
+                    style(22.),
+                ));
+            }
+            parent.spawn(TextBundle::from_section(
+                "Space: quit now   Esc: back",
+                style(16.),
+            ));
+        });
+}
+
+fn remove_quit_timer_system(mut commands: Commands) {
+    commands.remove_resource::<QuitTimer>();
+}
+
+/// Quits once the summary has been up for a while, or straight away on a skip
+/// key.
+fn quit_input_system(
+    time: Res<Time>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut timer: ResMut<QuitTimer>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut exit: EventWriter<AppExit>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    let skipped = keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::Return, KeyCode::Q]);
+    if timer.0.tick(time.delta()).finished() || skipped {
+        exit.send(AppExit);
+    }
+}
+
+/// Quitting from the menu, by way of a summary of the session.
+pub struct QuitPlugin;
+
+impl Plugin for QuitPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(open_quit_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_summary_system.in_schedule(OnEnter(GameState::Quitting)))
+            .add_system(remove_quit_timer_system.in_schedule(OnExit(GameState::Quitting)))
+            .add_system(quit_input_system.in_set(OnUpdate(GameState::Quitting)));
+    }
+}
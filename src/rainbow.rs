@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievements::Achievements,
+    checkpoint::CheckpointPipe,
+    cosmetics::{Customization, ItemKind},
+    modifiers::{ActiveModifiers, Modifier},
+    settings::Settings,
+    Background, Pipe,
+};
+
+/// Achievement that unlocks rainbow mode.
+pub const UNLOCK: &str = "score-100";
+/// Spreads the colours out along the pipes instead of shifting them in step.
+const DEGREES_PER_PIXEL: f32 = 0.2;
+const LIGHTNESS: f32 = 0.75;
+
+/// How strongly rainbow mode shifts colours. The hue only ever drifts round
+/// smoothly, without flashing, and even at its strongest takes six seconds to
+/// come full circle.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum RainbowMode {
+    #[default]
+    Off,
+    Gentle,
+    Vivid,
+}
+
+impl RainbowMode {
+    const ALL: [RainbowMode; 3] = [RainbowMode::Off, RainbowMode::Gentle, RainbowMode::Vivid];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RainbowMode::Off => "off",
+            RainbowMode::Gentle => "gentle",
+            RainbowMode::Vivid => "vivid",
+        }
+    }
+
+    pub fn step(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|m| *m == self).unwrap_or(0) as isize;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    /// Degrees the hue turns per second and how saturated the tint is, `None`
+    /// when off.
+    fn shift(self) -> Option<(f32, f32)> {
+        match self {
+            RainbowMode::Off => None,
+            RainbowMode::Gentle => Some((20., 0.3)),
+            RainbowMode::Vivid => Some((60., 0.7)),
+        }
+    }
+}
+
+fn tint(color: Color, hue: f32, saturation: f32) -> Color {
+    color * Color::hsl(hue.rem_euclid(360.), saturation, LIGHTNESS).as_rgba_f32()
+}
+
+/// Shifts the hue of the pipes and background over time while rainbow mode is
+/// unlocked and switched on, and puts their colours back once it's off. Pipes
+/// are left to the rainbow pipes modifier when that's active.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+fn rainbow_system(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    achievements: Res<Achievements>,
+    customization: Res<Customization>,
+    modifiers: Res<ActiveModifiers>,
+    mut was_on: Local<bool>,
+    mut pipes: Query<
+        (&Transform, &mut Sprite),
+        (With<Pipe>, Without<CheckpointPipe>, Without<Background>),
+    >,
+    mut backgrounds: Query<&mut Sprite, (With<Background>, Without<Pipe>)>,
+) {
+    let pipe_color = customization.color(ItemKind::PipeTheme);
+    let background_color = customization.color(ItemKind::Background);
+    let recolor_pipes = !modifiers.is_active(Modifier::RainbowPipes);
+
+    let shift = if achievements.is_unlocked(UNLOCK) {
+        settings.rainbow_mode.shift()
+    } else {
+        None
+    };
+    let Some((degrees_per_sec, saturation)) = shift else {
+        if std::mem::take(&mut *was_on) {
+            if recolor_pipes {
+                for (_, mut sprite) in pipes.iter_mut() {
+                    sprite.color = pipe_color;
+                }
+            }
+            for mut sprite in backgrounds.iter_mut() {
+                sprite.color = background_color;
+            }
+        }
+        return;
+    };
+    *was_on = true;
+
+    let hue = time.elapsed_seconds() * degrees_per_sec;
+    if recolor_pipes {
+        for (transform, mut sprite) in pipes.iter_mut() {
+            let hue = hue + transform.translation.x * DEGREES_PER_PIXEL;
+            sprite.color = tint(pipe_color, hue, saturation);
+        }
+    }
+    for mut sprite in backgrounds.iter_mut() {
+        sprite.color = tint(background_color, hue, saturation);
+    }
+}
+
+/// A celebratory colour-shifting mode, unlocked by scoring 100 and picked in
+/// the settings.
+pub struct RainbowPlugin;
+
+impl Plugin for RainbowPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(rainbow_system);
+    }
+}
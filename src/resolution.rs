@@ -0,0 +1,157 @@
+use std::path::Path;
+
+use bevy::{
+    prelude::*,
+    utils::HashMap,
+    window::{PrimaryWindow, WindowCreated, WindowScaleFactorChanged},
+};
+
+use crate::{settings::Settings, SCALE};
+
+/// Densest variant there's art for, `@3x`.
+const MAX_DENSITY: u32 = 3;
+/// Textures every set has to have for it to be picked, along with the
+/// [`ManifestTextures`].
+const TEXTURES: [&str; 5] = [
+    "bird.png",
+    "pipe.png",
+    "floor.png",
+    "bg.png",
+    "ground_hazards.png",
+];
+
+/// `path` of the variant of a texture `density` times as detailed, like
+/// `bird@2x.png` for `bird.png`. The `@1x` ones have no suffix.
+fn variant_path(path: &str, density: u32) -> String {
+    match path.rsplit_once('.') {
+        Some((stem, extension)) if density > 1 => format!("{stem}@{density}x.{extension}"),
+        _ => path.to_string(),
+    }
+}
+
+/// Which set of sprites is drawn, `@1x`, `@2x` or `@3x`, and how big sprites
+/// sized by their texture are drawn to cover as much of the world as the `@1x`
+/// art at [`SCALE`]. Sizes in the world stay the same whatever the set, so the
+/// game plays the same on every screen.
+///
+/// Starts at `@1x` and is picked again from the window's scale factor once it
+/// reports one, and whenever it or the texture pack changes, falling back to a
+/// less detailed set when the assets don't have the one that fits. Anything
+/// drawn that lasts has to be redrawn when it changes.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct WorldScale {
+    density: u32,
+}
+
+impl Default for WorldScale {
+    fn default() -> Self {
+        Self { density: 1 }
+    }
+}
+
+impl WorldScale {
+    /// Texture pixels to a pixel of the `@1x` art.
+    pub fn density(self) -> f32 {
+        self.density as f32
+    }
+
+    /// Scale for a sprite drawn at its texture's size.
+    pub fn sprite(self) -> Vec3 {
+        SCALE / self.density()
+    }
+
+    /// Path of the variant of the texture at `path` in the chosen set.
+    pub fn path(self, path: &str) -> String {
+        variant_path(path, self.density)
+    }
+
+    /// Loads the variant of the texture at `path` in the chosen set.
+    pub fn load(self, asset_server: &AssetServer, path: &str) -> Handle<Image> {
+        asset_server.load(self.path(path))
+    }
+
+    /// A sprite sheet of `columns` by `rows` frames `tile_size` big in the
+    /// `@1x` texture at `path`, cut from its variant in the chosen set.
+    pub fn atlas(
+        self,
+        asset_server: &AssetServer,
+        path: &str,
+        tile_size: Vec2,
+        columns: usize,
+        rows: usize,
+    ) -> TextureAtlas {
+        TextureAtlas::from_grid(
+            self.load(asset_server, path),
+            tile_size * self.density(),
+            columns,
+            rows,
+            None,
+            None,
+        )
+    }
+}
+
+/// Textures named in asset manifests, which have to come in the picked set too.
+/// Each manifest's plugin keeps its own list up to date as it loads.
+#[derive(Resource, Default)]
+pub struct ManifestTextures(HashMap<&'static str, Vec<String>>);
+
+impl ManifestTextures {
+    pub fn set(&mut self, manifest: &'static str, textures: Vec<String>) {
+        self.0.insert(manifest, textures);
+    }
+}
+
+/// Picks the densest set that fits the window's scale factor and that every
+/// texture comes in, looking in the active texture pack as well. Winit only
+/// reports the real scale factor once the window is up, so this waits for it
+/// rather than picking while the app is built.
+fn pick_world_scale_system(
+    mut created: EventReader<WindowCreated>,
+    mut scale_factor_changed: EventReader<WindowScaleFactorChanged>,
+    settings: Res<Settings>,
+    manifest_textures: Res<ManifestTextures>,
+    asset_server: Res<AssetServer>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    mut world_scale: ResMut<WorldScale>,
+) {
+    let window_changed = created.iter().count() + scale_factor_changed.iter().count() > 0;
+    if !window_changed && !settings.is_changed() && !manifest_textures.is_changed() {
+        return;
+    }
+    let Ok(window) = windows.get_single() else {
+        return;
+    };
+
+    let scale_factor = window.scale_factor();
+    let wanted = (scale_factor.round() as u32).clamp(1, MAX_DENSITY);
+    let asset_io = asset_server.asset_io();
+    let textures = TEXTURES
+        .iter()
+        .copied()
+        .chain(manifest_textures.0.values().flatten().map(String::as_str));
+    let density = (2..=wanted)
+        .rev()
+        .find(|&density| {
+            textures
+                .clone()
+                .all(|texture| asset_io.is_file(Path::new(&variant_path(texture, density))))
+        })
+        .unwrap_or(1);
+    if world_scale.density != density {
+        info!("Drawing @{density}x sprites for a scale factor of {scale_factor}");
+        world_scale.density = density;
+    }
+}
+
+/// Sharper sprites on high-DPI screens, from `@2x` and `@3x` variants of the
+/// textures put next to the originals.
+pub struct ResolutionPlugin;
+
+impl Plugin for ResolutionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldScale>()
+            .init_resource::<ManifestTextures>()
+            .add_system(pick_world_scale_system.in_base_set(CoreSet::PreUpdate));
+    }
+}
@@ -0,0 +1,269 @@
+use bevy::{prelude::*, sprite::Anchor};
+use rand::Rng;
+
+use crate::{
+    mode::endless_run,
+    modifiers::{bird_scale, ActiveModifiers},
+    resolution::WorldScale,
+    score::Score,
+    GameRng, GameState, Player, WorldDirection, FONT, WINDOW_HEIGHT, WINDOW_WIDTH,
+};
+
+/// A roulette spins every this many points.
+const POINTS_PER_SPIN: u32 = 15;
+const SPIN_SECS: f32 = 1.5;
+/// How long each name shows while spinning.
+const SPIN_TICK_SECS: f32 = 0.1;
+/// How long the picked modifier shows before the spin ends.
+const SETTLE_SECS: f32 = 0.4;
+const EFFECT_SECS: f32 = 10.;
+const TINY_BIRD_SCALE: f32 = 0.6;
+/// How much faster the world scrolls with [`RouletteModifier::FastPipes`].
+const FAST_PIPES_SPEED: f32 = 1.5;
+const FOG_COLOR: Color = Color::rgb(0.8, 0.82, 0.85);
+const FOG_ALPHA: f32 = 0.95;
+/// How long the fog takes to roll in and clear.
+const FOG_FADE_SECS: f32 = 0.5;
+const ROULETTE_COLOR: Color = Color::rgb(0.6, 0.9, 1.);
+
+/// Temporary modifiers the roulette picks from during a run, unlike the cheat
+/// [`Modifier`](crate::modifiers::Modifier)s that last for the whole run.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum RouletteModifier {
+    /// Shrinks the bird and its hitbox.
+    TinyBird,
+    FastPipes,
+    /// Hides the half of the screen the pipes come from.
+    Fog,
+}
+
+impl RouletteModifier {
+    const ALL: [RouletteModifier; 3] = [
+        RouletteModifier::TinyBird,
+        RouletteModifier::FastPipes,
+        RouletteModifier::Fog,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            RouletteModifier::TinyBird => "tiny bird",
+            RouletteModifier::FastPipes => "fast pipes",
+            RouletteModifier::Fog => "fog",
+        }
+    }
+}
+
+#[derive(Default)]
+enum Spin {
+    #[default]
+    Idle,
+    Spinning {
+        timer: Timer,
+        result: RouletteModifier,
+    },
+    Active {
+        timer: Timer,
+        modifier: RouletteModifier,
+    },
+}
+
+/// The roulette's part in the current endless run.
+#[derive(Resource, Default)]
+pub struct Roulette {
+    /// Spins so far, counting those skipped by continuing a run.
+    spins: u32,
+    spin: Spin,
+}
+
+impl Roulette {
+    pub fn is_active(&self, modifier: RouletteModifier) -> bool {
+        matches!(self.spin, Spin::Active { modifier: active, .. } if active == modifier)
+    }
+
+    /// Multiplier on how fast the world scrolls past.
+    pub fn scroll_scale(&self) -> f32 {
+        if self.is_active(RouletteModifier::FastPipes) {
+            FAST_PIPES_SPEED
+        } else {
+            1.
+        }
+    }
+}
+
+#[derive(Component)]
+struct Fog;
+
+#[derive(Component)]
+struct RouletteText;
+
+fn reset_roulette_system(score: Res<Score>, mut roulette: ResMut<Roulette>) {
+    *roulette = Roulette {
+        spins: score.0 / POINTS_PER_SPIN,
+        spin: Spin::Idle,
+    };
+}
+
+fn stop_roulette_system(mut roulette: ResMut<Roulette>) {
+    roulette.spin = Spin::Idle;
+}
+
+/// Spins once the score reaches the next multiple of [`POINTS_PER_SPIN`], or as
+/// soon as the modifier before has worn off.
+fn start_spin_system(score: Res<Score>, mut rng: ResMut<GameRng>, mut roulette: ResMut<Roulette>) {
+    if !matches!(roulette.spin, Spin::Idle) || score.0 / POINTS_PER_SPIN <= roulette.spins {
+        return;
+    }
+
+    roulette.spins += 1;
+    let result = RouletteModifier::ALL[rng.gen_range(0..RouletteModifier::ALL.len())];
+    roulette.spin = Spin::Spinning {
+        timer: Timer::from_seconds(SPIN_SECS, TimerMode::Once),
+        result,
+    };
+}
+
+fn tick_roulette_system(time: Res<Time>, mut roulette: ResMut<Roulette>) {
+    let next = match &mut roulette.spin {
+        Spin::Idle => return,
+        Spin::Spinning { timer, result } => {
+            if !timer.tick(time.delta()).finished() {
+                return;
+            }
+            Spin::Active {
+                timer: Timer::from_seconds(EFFECT_SECS, TimerMode::Once),
+                modifier: *result,
+            }
+        }
+        Spin::Active { timer, .. } => {
+            if !timer.tick(time.delta()).finished() {
+                return;
+            }
+            Spin::Idle
+        }
+    };
+    roulette.spin = next;
+}
+
+fn tiny_bird_system(
+    roulette: Res<Roulette>,
+    modifiers: Res<ActiveModifiers>,
+    world_scale: Res<WorldScale>,
+    mut player: Query<&mut Transform, With<Player>>,
+) {
+    if !roulette.is_changed() {
+        return;
+    }
+
+    let mut scale = bird_scale(&modifiers, *world_scale);
+    if roulette.is_active(RouletteModifier::TinyBird) {
+        scale *= TINY_BIRD_SCALE;
+    }
+    for mut transform in player.iter_mut() {
+        if transform.scale != scale {
+            transform.scale = scale;
+        }
+    }
+}
+
+fn spawn_roulette_hud_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        Fog,
+        SpriteBundle {
+            sprite: Sprite {
+                color: FOG_COLOR.with_a(0.),
+                // Wide enough for windows stretched well past the default.
+                custom_size: Some(Vec2::new(WINDOW_WIDTH * 2., WINDOW_HEIGHT)),
+                ..Default::default()
+            },
+            // Above the pipes and floor, below the UI.
+            transform: Transform::from_xyz(0., 0., 15.),
+            ..Default::default()
+        },
+    ));
+    commands.spawn((
+        RouletteText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 22.,
+                color: ROULETTE_COLOR,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(150.),
+                right: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+/// Rolls the fog over the side the pipes come from, and shows the roulette
+/// spinning and then the modifier it landed on with the time left.
+fn update_roulette_hud_system(
+    roulette: Res<Roulette>,
+    direction: Res<WorldDirection>,
+    mut fog: Query<&mut Sprite, With<Fog>>,
+    mut text: Query<&mut Text, With<RouletteText>>,
+) {
+    let alpha = match &roulette.spin {
+        Spin::Active {
+            timer,
+            modifier: RouletteModifier::Fog,
+        } => {
+            let fade = timer.elapsed_secs().min(timer.remaining_secs()) / FOG_FADE_SECS;
+            FOG_ALPHA * fade.min(1.)
+        }
+        _ => 0.,
+    };
+    for mut sprite in fog.iter_mut() {
+        sprite.color.set_a(alpha);
+        sprite.anchor = match *direction {
+            WorldDirection::Right => Anchor::CenterLeft,
+            WorldDirection::Left => Anchor::CenterRight,
+        };
+    }
+
+    let value = match &roulette.spin {
+        Spin::Idle => String::new(),
+        Spin::Spinning { timer, result } => {
+            let shown = if timer.remaining_secs() < SETTLE_SECS {
+                *result
+            } else {
+                let tick = (timer.elapsed_secs() / SPIN_TICK_SECS) as usize;
+                RouletteModifier::ALL[tick % RouletteModifier::ALL.len()]
+            };
+            format!("ROULETTE: {}", shown.name())
+        }
+        Spin::Active { timer, modifier } => {
+            format!("{} {:.1}s", modifier.name(), timer.remaining_secs())
+        }
+    };
+    for mut text in text.iter_mut() {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+pub struct RoulettePlugin;
+
+impl Plugin for RoulettePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Roulette>()
+            .add_startup_system(spawn_roulette_hud_system)
+            .add_system(reset_roulette_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(stop_roulette_system.in_schedule(OnExit(GameState::InGame)))
+            .add_systems(
+                (start_spin_system, tick_roulette_system)
+                    .chain()
+                    .distributive_run_if(endless_run)
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_systems((tiny_bird_system, update_roulette_hud_system));
+    }
+}
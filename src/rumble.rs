@@ -0,0 +1,133 @@
+use bevy::prelude::*;
+use gilrs::{
+    ff::{BaseEffect, BaseEffectType, Effect, EffectBuilder, Repeat, Replay, Ticks},
+    Gilrs,
+};
+
+use crate::{score::Score, settings::Settings, stats::Stats, Crashed, FlapEvent};
+
+/// A rumble made of `pulses` pulses of the same strength, one every
+/// `play_ms + pause_ms`.
+#[derive(Clone, Copy)]
+struct Pattern {
+    kind: BaseEffectType,
+    play_ms: u32,
+    pause_ms: u32,
+    pulses: u32,
+}
+
+impl Pattern {
+    fn duration_ms(&self) -> u32 {
+        (self.play_ms + self.pause_ms) * self.pulses - self.pause_ms
+    }
+}
+
+const FLAP: Pattern = Pattern {
+    kind: BaseEffectType::Weak { magnitude: 20_000 },
+    play_ms: 60,
+    pause_ms: 0,
+    pulses: 1,
+};
+
+const CRASH: Pattern = Pattern {
+    kind: BaseEffectType::Strong {
+        magnitude: u16::MAX,
+    },
+    play_ms: 400,
+    pause_ms: 0,
+    pulses: 1,
+};
+
+const NEW_HIGH_SCORE: Pattern = Pattern {
+    kind: BaseEffectType::Strong { magnitude: 40_000 },
+    play_ms: 100,
+    pause_ms: 100,
+    pulses: 3,
+};
+
+/// Effects still playing and when they end, in unpaused seconds since startup.
+/// Dropping an effect stops it, so they're kept around until then.
+#[derive(Default)]
+struct Rumbling(Vec<(Effect, f64)>);
+
+fn play(gilrs: &mut Gilrs, rumbling: &mut Rumbling, now: f64, pattern: Pattern) {
+    let gamepads = gilrs
+        .gamepads()
+        .filter(|(_, gamepad)| gamepad.is_ff_supported())
+        .map(|(id, _)| id)
+        .collect::<Vec<_>>();
+    if gamepads.is_empty() {
+        return;
+    }
+
+    let effect = EffectBuilder::new()
+        .add_effect(BaseEffect {
+            kind: pattern.kind,
+            scheduling: Replay {
+                play_for: Ticks::from_ms(pattern.play_ms),
+                with_delay: Ticks::from_ms(pattern.pause_ms),
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .repeat(Repeat::For(Ticks::from_ms(pattern.duration_ms())))
+        .gamepads(&gamepads)
+        .finish(gilrs)
+        .and_then(|effect| effect.play().map(|_| effect));
+
+    match effect {
+        Ok(effect) => rumbling
+            .0
+            .push((effect, now + pattern.duration_ms() as f64 / 1000.)),
+        Err(err) => warn!("Failed to rumble: {err}"),
+    }
+}
+
+/// Pulses on every flap, rumbles hard on every crash and celebrates the moment
+/// a run beats the high score.
+#[allow(clippy::too_many_arguments)]
+fn rumble_system(
+    gilrs: Option<NonSendMut<Gilrs>>,
+    mut rumbling: Local<Rumbling>,
+    mut celebrated: Local<bool>,
+    time: Res<Time>,
+    settings: Res<Settings>,
+    score: Res<Score>,
+    stats: Res<Stats>,
+    mut flapped: EventReader<FlapEvent>,
+    mut crashed: EventReader<Crashed>,
+) {
+    let now = time.raw_elapsed_seconds_f64();
+    rumbling.0.retain(|(_, ends_at)| *ends_at > now);
+
+    let flapped = flapped.iter().count() > 0;
+    let crashed = crashed.iter().count() > 0;
+    if score.0 == 0 {
+        *celebrated = false;
+    }
+    let new_high_score = !*celebrated && stats.best_score > 0 && score.0 > stats.best_score;
+    *celebrated |= new_high_score;
+
+    let Some(mut gilrs) = gilrs else {
+        return;
+    };
+    if !settings.rumble {
+        return;
+    }
+
+    if crashed {
+        play(&mut gilrs, &mut rumbling, now, CRASH);
+    } else if new_high_score {
+        play(&mut gilrs, &mut rumbling, now, NEW_HIGH_SCORE);
+    } else if flapped {
+        play(&mut gilrs, &mut rumbling, now, FLAP);
+    }
+}
+
+pub struct RumblePlugin;
+
+impl Plugin for RumblePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(rumble_system);
+    }
+}
@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+
+use bevy::{asset::FileAssetIo, ecs::system::SystemParam, prelude::*};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievements::{achievement, Achievements},
+    coins::Wallet,
+    cosmetics::{item, Customization},
+    daily::DailyBests,
+    leaderboard::Leaderboard,
+    profiles::Profiles,
+    screenshot::{spawn_toast, timestamp},
+    settings::Settings,
+    speedrun::PersonalBests,
+    stats::Stats,
+    storage,
+};
+
+/// Picked up from the saves folder by [`SavegameAction::Import`].
+const IMPORT_FILE: &str = "import.ron";
+
+/// Sent to export the active profile to a file, or to merge one back in.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SavegameAction {
+    Export,
+    Import,
+}
+
+/// Everything saved for a profile, in one file that can be carried to another
+/// machine.
+#[derive(Serialize, Deserialize, Default)]
+#[serde(default)]
+struct Savegame {
+    /// Profile the file was exported from, just for showing.
+    profile: String,
+    settings: Settings,
+    stats: Stats,
+    daily: DailyBests,
+    leaderboard: Leaderboard,
+    wallet: Wallet,
+    customization: Customization,
+    achievements: Achievements,
+    splits: PersonalBests,
+}
+
+impl storage::SaveFile for Savegame {
+    const VERSION: u32 = 2;
+
+    /// Version 1 exports hold a version 1 leaderboard.
+    fn migrate(&mut self, version: u32) {
+        if version < 2 {
+            storage::SaveFile::migrate(&mut self.leaderboard, 1);
+        }
+    }
+}
+
+/// The active profile's saved resources.
+#[derive(SystemParam)]
+struct ProfileData<'w> {
+    settings: ResMut<'w, Settings>,
+    stats: ResMut<'w, Stats>,
+    daily: ResMut<'w, DailyBests>,
+    leaderboard: ResMut<'w, Leaderboard>,
+    wallet: ResMut<'w, Wallet>,
+    customization: ResMut<'w, Customization>,
+    achievements: ResMut<'w, Achievements>,
+    splits: ResMut<'w, PersonalBests>,
+}
+
+impl ProfileData<'_> {
+    fn export(&self, profile: &str) -> Savegame {
+        Savegame {
+            profile: profile.to_string(),
+            settings: self.settings.clone(),
+            stats: self.stats.clone(),
+            daily: self.daily.clone(),
+            leaderboard: self.leaderboard.clone(),
+            wallet: self.wallet.clone(),
+            customization: self.customization.clone(),
+            achievements: self.achievements.clone(),
+            splits: self.splits.clone(),
+        }
+    }
+
+    /// Merges an imported profile in and saves the result. Returns how many
+    /// leaderboard entries were left out for having been edited.
+    ///
+    /// Settings are taken as they are, the stats with the higher best score
+    /// win like with cloud sync, and the coin count is the higher one. The rest
+    /// is combined: cosmetics, achievements and scores from either side are
+    /// kept, along with the best score of each day and the fastest splits.
+    /// Items and achievements this version doesn't know are dropped.
+    fn import(&mut self, savegame: Savegame) -> usize {
+        *self.settings = savegame.settings;
+        if savegame.stats.best_score > self.stats.best_score {
+            *self.stats = savegame.stats;
+        }
+        self.daily.merge(&savegame.daily);
+        let tampered = self.leaderboard.merge(&savegame.leaderboard);
+        self.wallet.coins = self.wallet.coins.max(savegame.wallet.coins);
+        self.customization.owned.extend(
+            savegame
+                .customization
+                .owned
+                .into_iter()
+                .filter(|id| item(id).is_some()),
+        );
+        self.achievements.unlocked.extend(
+            savegame
+                .achievements
+                .unlocked
+                .into_iter()
+                .filter(|id| achievement(id).is_some()),
+        );
+        self.splits.merge(&savegame.splits);
+
+        self.settings.save();
+        self.stats.save();
+        self.daily.save();
+        self.leaderboard.save();
+        self.wallet.save();
+        self.customization.save();
+        self.achievements.save();
+        self.splits.save();
+        tampered
+    }
+}
+
+/// Next to the game, like screenshots, so the files are easy to find and move.
+fn saves_dir() -> PathBuf {
+    FileAssetIo::get_base_path().join("saves")
+}
+
+fn savegame_system(
+    mut commands: Commands,
+    mut actions: EventReader<SavegameAction>,
+    asset_server: Res<AssetServer>,
+    profiles: Res<Profiles>,
+    mut data: ProfileData,
+) {
+    for action in actions.iter() {
+        let message = match action {
+            SavegameAction::Export => {
+                let path = saves_dir().join(format!("{}-{}.ron", profiles.active, timestamp()));
+                match storage::save(&path, &data.export(&profiles.active)) {
+                    Ok(()) => format!("Exported {} to {}", profiles.active, path.display()),
+                    Err(err) => format!("Couldn't export {}: {err}", profiles.active),
+                }
+            }
+            SavegameAction::Import => {
+                let path = saves_dir().join(IMPORT_FILE);
+                if !path.exists() {
+                    format!("Put a save to import at {}", path.display())
+                } else if let Some(savegame) = storage::load::<Savegame>(&path) {
+                    let from = savegame.profile.clone();
+                    match data.import(savegame) {
+                        0 => format!("Imported {from} into {}", profiles.active),
+                        tampered => format!(
+                            "Imported {from} into {}, leaving out {tampered} edited score(s)",
+                            profiles.active
+                        ),
+                    }
+                } else {
+                    format!("Couldn't import {}, see the log", path.display())
+                }
+            }
+        };
+        spawn_toast(&mut commands, &asset_server, message);
+    }
+}
+
+pub struct SavegamePlugin;
+
+impl Plugin for SavegamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<SavegameAction>()
+            .add_system(savegame_system);
+    }
+}
@@ -0,0 +1,236 @@
+use bevy::prelude::*;
+
+use crate::{
+    pipe_themes::PipeTheme, touch::SafeArea, GameState, GameplaySet, Pipe, Player, WorldDirection,
+    FONT, PIPE_WIDTH, SCALE,
+};
+
+/// How far a [`ScoreZone`] reaches past the far side of its pipes, far more than
+/// the bird moves in a frame.
+const ZONE_DEPTH: f32 = PIPE_WIDTH;
+
+/// Pipes passed in the current run.
+#[derive(Resource, Default, Deref, DerefMut)]
+pub struct Score(pub u32);
+
+/// Marks the bottom pipe of every pair whose point is still to be scored. Its
+/// [`ScoreZone`] is a child.
+#[derive(Component)]
+pub struct ScorePipe;
+
+/// Invisible sensor lined up with the gap of a pipe pair, just past its far side.
+/// The player's centre entering it scores the pair's point and uses it up.
+#[derive(Component)]
+pub struct ScoreZone {
+    size: Vec2,
+}
+
+/// Sent whenever the player scores a point by passing a pipe. The point is
+/// added in [`GameplaySet::Scoring`].
+pub struct ScoredEvent;
+
+/// Sent when the player flies through the middle of a gap, before the point for
+/// it is scored.
+pub struct GapCrossed {
+    /// How far above (positive) or below the centre of the gap the player was
+    /// at the moment it crossed the middle.
+    pub offset: f32,
+    pub gap: f32,
+}
+
+/// Marks a [`ScorePipe`] whose gap the player has already crossed.
+#[derive(Component)]
+pub struct Crossed;
+
+#[derive(Component)]
+struct ScoreText;
+
+/// Gives `pipe`, the bottom of a pair `width` wide with a gap of `gap` above
+/// it, a [`ScoreZone`] that moves along with it.
+pub fn spawn_score_zone(
+    commands: &mut Commands,
+    pipe: Entity,
+    direction: WorldDirection,
+    width: f32,
+    gap: f32,
+) {
+    let offset = Vec3::new(
+        direction.past_pipe(0., width, width + ZONE_DEPTH / 2.),
+        gap / 2.,
+        0.,
+    );
+    commands.entity(pipe).with_children(|parent| {
+        parent.spawn((
+            ScoreZone {
+                size: Vec2::new(ZONE_DEPTH, gap),
+            },
+            // The pipe's scale applies to its children.
+            TransformBundle::from_transform(Transform::from_translation(offset / SCALE)),
+        ));
+    });
+}
+
+/// Works from the pipe's transform, since the zone's global one lags a frame
+/// behind and is still at the origin on the frame it spawns.
+fn score_zone_system(
+    mut commands: Commands,
+    mut passed: EventWriter<ScoredEvent>,
+    player: Query<&Transform, With<Player>>,
+    zones: Query<(Entity, &Parent, &Transform, &ScoreZone)>,
+    pipes: Query<&Transform, With<ScorePipe>>,
+) {
+    let player = player.single().translation.truncate();
+
+    for (entity, pipe, transform, zone) in zones.iter() {
+        let Ok(pipe_transform) = pipes.get(pipe.get()) else {
+            continue;
+        };
+        let center = pipe_transform
+            .mul_transform(*transform)
+            .translation
+            .truncate();
+        if Rect::from_center_size(center, zone.size).contains(player) {
+            passed.send(ScoredEvent);
+            commands.entity(pipe.get()).remove::<ScorePipe>();
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+/// Measures the player against the gap above each scoring pipe as it passes its
+/// midpoint. Pipes whose partner was broken off have no gap to measure.
+///
+/// The bird is usually a little past the middle by the time it's noticed, so
+/// its offset is interpolated back to the moment it crossed, from where the
+/// bird and pipe were last frame. Global transforms still hold those, as they
+/// lag a frame behind.
+#[allow(clippy::type_complexity)]
+fn gap_crossing_system(
+    mut commands: Commands,
+    mut crossed: EventWriter<GapCrossed>,
+    direction: Res<WorldDirection>,
+    theme: Res<PipeTheme>,
+    player: Query<(&Transform, &GlobalTransform), With<Player>>,
+    score_pipes: Query<(Entity, &Transform, &GlobalTransform, Option<&Crossed>), With<ScorePipe>>,
+    pipes: Query<&Transform, (With<Pipe>, Without<ScorePipe>)>,
+) {
+    let (player, last_player) = player.single();
+    let (player, last_player) = (player.translation, last_player.translation());
+
+    for (entity, bottom, last_bottom, already_crossed) in score_pipes.iter() {
+        let middle = bottom.translation.x + theme.width() / 2.;
+        if already_crossed.is_some() || !direction.passed(middle, player.x) {
+            continue;
+        }
+
+        commands.entity(entity).insert(Crossed);
+        let top = pipes.iter().find(|top| {
+            (top.translation.x - bottom.translation.x).abs() < 1.
+                && top.translation.y > bottom.translation.y
+        });
+        let Some(top) = top else {
+            continue;
+        };
+
+        let gap_bottom = bottom.translation.y;
+        let gap = top.translation.y - theme.size.y - gap_bottom;
+        let offset = player.y - (gap_bottom + gap / 2.);
+
+        let last_bottom = last_bottom.translation();
+        let last_middle = last_bottom.x + theme.width() / 2.;
+        // Already past it last frame, as with pipes restored mid-run, leaves
+        // nothing to go back to.
+        let offset = if direction.passed(last_middle, last_player.x) {
+            offset
+        } else {
+            let last_offset = last_player.y - (last_bottom.y + gap / 2.);
+            let before = (last_middle - last_player.x).abs();
+            let after = (middle - player.x).abs();
+            let t = before / (before + after).max(f32::EPSILON);
+            last_offset + (offset - last_offset) * t
+        };
+        crossed.send(GapCrossed { offset, gap });
+    }
+}
+
+fn add_points_system(mut passed: EventReader<ScoredEvent>, mut score: ResMut<Score>) {
+    score.0 += passed.iter().count() as u32;
+}
+
+fn reset_score_system(mut score: ResMut<Score>) {
+    score.0 = 0;
+}
+
+fn spawn_score_text_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    safe_area: Res<SafeArea>,
+) {
+    commands
+        .spawn(NodeBundle {
+            style: Style {
+                size: Size::width(Val::Percent(100.)),
+                position_type: PositionType::Absolute,
+                position: UiRect::top(Val::Px(safe_area.top + 30.)),
+                justify_content: JustifyContent::Center,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+        .with_children(|parent| {
+            parent.spawn((
+                ScoreText,
+                TextBundle::from_section(
+                    "0",
+                    TextStyle {
+                        font: asset_server.load(FONT),
+                        font_size: 48.,
+                        color: Color::WHITE,
+                    },
+                ),
+            ));
+        });
+}
+
+fn update_score_text_system(
+    score: Res<Score>,
+    state: Res<State<GameState>>,
+    mut query: Query<(&mut Text, &mut Visibility), With<ScoreText>>,
+) {
+    if !score.is_changed() && !state.is_changed() {
+        return;
+    }
+
+    let visible = matches!(state.0, GameState::InGame | GameState::GameOver);
+
+    for (mut text, mut visibility) in query.iter_mut() {
+        text.sections[0].value = score.to_string();
+        *visibility = if visible {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+    }
+}
+
+pub struct ScorePlugin;
+
+impl Plugin for ScorePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Score>()
+            .add_event::<ScoredEvent>()
+            .add_event::<GapCrossed>()
+            .add_startup_system(spawn_score_text_system)
+            .add_systems(
+                (
+                    gap_crossing_system.in_set(GameplaySet::Collision),
+                    score_zone_system.in_set(GameplaySet::Collision),
+                    add_points_system.in_set(GameplaySet::Scoring),
+                )
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(update_score_text_system.in_set(GameplaySet::Presentation))
+            .add_system(reset_score_system.in_schedule(OnEnter(GameState::Menu)))
+            .add_system(reset_score_system.in_schedule(OnEnter(GameState::LevelComplete)));
+    }
+}
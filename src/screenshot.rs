@@ -0,0 +1,379 @@
+use std::{
+    num::NonZeroU32,
+    path::PathBuf,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bevy::{
+    asset::FileAssetIo,
+    prelude::*,
+    render::{
+        camera::RenderTarget,
+        render_asset::RenderAssets,
+        render_resource::{
+            BufferDescriptor, BufferUsages, CommandEncoderDescriptor, Extent3d, ImageCopyBuffer,
+            ImageDataLayout, MapMode, TextureDescriptor, TextureDimension, TextureFormat,
+            TextureUsages,
+        },
+        renderer::{RenderDevice, RenderQueue},
+        Extract, ExtractSchedule, RenderApp, RenderSet,
+    },
+    window::PrimaryWindow,
+};
+
+use crate::FONT;
+
+const TOAST_SECS: f32 = 2.;
+
+/// Folder next to `assets/` that screenshots are written to.
+pub fn screenshots_dir() -> PathBuf {
+    FileAssetIo::get_base_path().join("screenshots")
+}
+
+/// What a captured frame is for.
+#[derive(Clone, Debug)]
+pub enum CapturePurpose {
+    /// Save it as a PNG at the given path.
+    Screenshot(PathBuf),
+    /// Save it as a PNG at the given path and show where it went.
+    ScoreCard(PathBuf),
+    #[cfg(feature = "highlight-gif")]
+    Highlight,
+}
+
+/// Sent with the RGBA pixels of a frame requested with [`capture_frame`].
+pub struct FrameCaptured {
+    pub purpose: CapturePurpose,
+    pub width: u32,
+    pub height: u32,
+    pub data: Vec<u8>,
+}
+
+/// Camera rendering a single frame into an image that's then read back into a
+/// [`FrameCaptured`]. Bevy has no screenshot API yet, so this stands in for one.
+#[derive(Component)]
+struct CaptureCamera {
+    image: Handle<Image>,
+    purpose: CapturePurpose,
+}
+
+#[derive(Resource)]
+struct CaptureSender(Sender<FrameCaptured>);
+
+#[derive(Resource)]
+struct CaptureReceiver(Mutex<Receiver<FrameCaptured>>);
+
+#[derive(Resource, Default)]
+struct ExtractedCaptures(Vec<(Handle<Image>, CapturePurpose)>);
+
+#[derive(Component)]
+struct Toast(Timer);
+
+/// Renders the next frame offscreen at the window's size and sends it as a
+/// [`FrameCaptured`] event once it's back from the GPU.
+pub fn capture_frame(
+    commands: &mut Commands,
+    images: &mut Assets<Image>,
+    window: &Window,
+    purpose: CapturePurpose,
+) {
+    let size = UVec2::new(
+        window.resolution.width() as u32,
+        window.resolution.height() as u32,
+    );
+    spawn_capture_camera(commands, images, size, purpose);
+}
+
+/// Spawns a camera that renders the next frame into an image of `size` and
+/// sends it as a [`FrameCaptured`] event. The caller can insert components such
+/// as `RenderLayers` to choose what it sees.
+pub fn spawn_capture_camera(
+    commands: &mut Commands,
+    images: &mut Assets<Image>,
+    size: UVec2,
+    purpose: CapturePurpose,
+) -> Entity {
+    let size = Extent3d {
+        width: size.x,
+        height: size.y,
+        depth_or_array_layers: 1,
+    };
+    let mut image = Image {
+        texture_descriptor: TextureDescriptor {
+            label: Some("capture"),
+            size,
+            dimension: TextureDimension::D2,
+            format: TextureFormat::Rgba8UnormSrgb,
+            mip_level_count: 1,
+            sample_count: 1,
+            usage: TextureUsages::TEXTURE_BINDING
+                | TextureUsages::COPY_SRC
+                | TextureUsages::RENDER_ATTACHMENT,
+            view_formats: &[],
+        },
+        ..Default::default()
+    };
+    image.resize(size);
+    let image = images.add(image);
+
+    commands
+        .spawn((
+            CaptureCamera {
+                image: image.clone(),
+                purpose,
+            },
+            Camera2dBundle {
+                camera: Camera {
+                    target: RenderTarget::Image(image),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .id()
+}
+
+/// Timestamp for naming exported files.
+pub fn timestamp() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_millis())
+}
+
+fn take_screenshot_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut images: ResMut<Assets<Image>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::F12) {
+        return;
+    }
+
+    if let Ok(window) = windows.get_single() {
+        let path = screenshots_dir().join(format!("screenshot-{}.png", timestamp()));
+        capture_frame(
+            &mut commands,
+            &mut images,
+            window,
+            CapturePurpose::Screenshot(path),
+        );
+    }
+}
+
+/// Capture cameras only need to be extracted once.
+fn despawn_capture_cameras_system(
+    mut commands: Commands,
+    query: Query<Entity, With<CaptureCamera>>,
+) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn extract_captures_system(mut commands: Commands, cameras: Extract<Query<&CaptureCamera>>) {
+    commands.insert_resource(ExtractedCaptures(
+        cameras
+            .iter()
+            .map(|camera| (camera.image.clone_weak(), camera.purpose.clone()))
+            .collect(),
+    ));
+}
+
+/// Copies each capture image into a buffer once it has been rendered and
+/// hands the pixels back to the main world.
+fn read_back_captures_system(
+    captures: Res<ExtractedCaptures>,
+    images: Res<RenderAssets<Image>>,
+    device: Res<RenderDevice>,
+    queue: Res<RenderQueue>,
+    sender: Res<CaptureSender>,
+) {
+    for (handle, purpose) in &captures.0 {
+        let Some(gpu_image) = images.get(handle) else {
+            continue;
+        };
+
+        let size = Extent3d {
+            width: gpu_image.size.x as u32,
+            height: gpu_image.size.y as u32,
+            depth_or_array_layers: 1,
+        };
+        let row_bytes = size.width as usize * 4;
+        let padded_row_bytes = RenderDevice::align_copy_bytes_per_row(row_bytes);
+
+        let buffer = device.create_buffer(&BufferDescriptor {
+            label: Some("capture"),
+            size: (padded_row_bytes * size.height as usize) as u64,
+            usage: BufferUsages::MAP_READ | BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let mut encoder = device.create_command_encoder(&CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            gpu_image.texture.as_image_copy(),
+            ImageCopyBuffer {
+                buffer: &buffer,
+                layout: ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: NonZeroU32::new(padded_row_bytes as u32),
+                    rows_per_image: None,
+                },
+            },
+            size,
+        );
+        queue.submit([encoder.finish()]);
+
+        let slice = buffer.slice(..);
+        let (mapped_sender, mapped) = mpsc::channel();
+        device.map_buffer(&slice, MapMode::Read, move |result| {
+            let _ = mapped_sender.send(result);
+        });
+        device.poll(wgpu::Maintain::Wait);
+        if !matches!(mapped.recv(), Ok(Ok(()))) {
+            error!("Failed to read back captured frame for {purpose:?}");
+            continue;
+        }
+
+        // Rows are padded to the copy alignment, which consumers don't want.
+        let data = slice
+            .get_mapped_range()
+            .chunks(padded_row_bytes)
+            .flat_map(|row| &row[..row_bytes])
+            .copied()
+            .collect();
+        buffer.unmap();
+
+        let _ = sender.0.send(FrameCaptured {
+            purpose: purpose.clone(),
+            width: size.width,
+            height: size.height,
+            data,
+        });
+    }
+}
+
+fn receive_captures_system(
+    receiver: Res<CaptureReceiver>,
+    mut captured: EventWriter<FrameCaptured>,
+) {
+    if let Ok(receiver) = receiver.0.lock() {
+        captured.send_batch(receiver.try_iter());
+    }
+}
+
+fn save_screenshots_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut captured: EventReader<FrameCaptured>,
+) {
+    for frame in captured.iter() {
+        // Only refutable when other features add purposes.
+        #[allow(irrefutable_let_patterns)]
+        let (CapturePurpose::Screenshot(path) | CapturePurpose::ScoreCard(path)) = &frame.purpose
+        else {
+            continue;
+        };
+
+        let image = Image::new(
+            Extent3d {
+                width: frame.width,
+                height: frame.height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            frame.data.clone(),
+            TextureFormat::Rgba8UnormSrgb,
+        );
+        let result = std::fs::create_dir_all(screenshots_dir())
+            .map_err(|err| err.to_string())
+            .and_then(|_| image.try_into_dynamic().map_err(|err| err.to_string()))
+            .and_then(|image| image.save(path).map_err(|err| err.to_string()));
+
+        let message = match result {
+            // Only text goes on the clipboard, so show the whole path instead.
+            Ok(()) if matches!(frame.purpose, CapturePurpose::ScoreCard(_)) => {
+                format!("Saved score card to {}", path.display())
+            }
+            Ok(()) => {
+                let name = path.file_name().unwrap_or_default();
+                format!("Saved {}", name.to_string_lossy())
+            }
+            Err(err) => {
+                error!("Failed to save screenshot {}: {err}", path.display());
+                "Screenshot failed".to_string()
+            }
+        };
+        spawn_toast(&mut commands, &asset_server, message);
+    }
+}
+
+/// Shows `message` at the bottom of the screen for a moment.
+pub fn spawn_toast(commands: &mut Commands, asset_server: &AssetServer, message: String) {
+    commands
+        .spawn((
+            Toast(Timer::from_seconds(TOAST_SECS, TimerMode::Once)),
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::bottom(Val::Px(10.)),
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                z_index: ZIndex::Global(10),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                message,
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 16.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+fn expire_toasts_system(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut query: Query<(Entity, &mut Toast)>,
+) {
+    for (entity, mut toast) in query.iter_mut() {
+        if toast.0.tick(time.delta()).finished() {
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
+
+pub struct ScreenshotPlugin;
+
+impl Plugin for ScreenshotPlugin {
+    fn build(&self, app: &mut App) {
+        let (sender, receiver) = mpsc::channel();
+
+        app.insert_resource(CaptureReceiver(Mutex::new(receiver)))
+            .add_event::<FrameCaptured>()
+            .add_system(despawn_capture_cameras_system.in_base_set(CoreSet::First))
+            .add_system(receive_captures_system.in_base_set(CoreSet::PreUpdate))
+            .add_system(take_screenshot_system)
+            .add_system(save_screenshots_system)
+            .add_system(expire_toasts_system);
+
+        // Without a GPU there's no render app and nothing to capture.
+        if let Ok(render_app) = app.get_sub_app_mut(RenderApp) {
+            render_app
+                .insert_resource(CaptureSender(sender))
+                .init_resource::<ExtractedCaptures>()
+                .add_system(extract_captures_system.in_schedule(ExtractSchedule))
+                .add_system(read_back_captures_system.in_set(RenderSet::Cleanup));
+        }
+    }
+}
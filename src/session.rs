@@ -0,0 +1,300 @@
+use bevy::{
+    app::AppExit,
+    prelude::*,
+    window::{WindowCloseRequested, WindowFocused},
+};
+use rand::SeedableRng;
+use rand_chacha::ChaCha12Rng;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    arcade::Ammo,
+    checkpoint::{CheckpointPipe, Checkpoints},
+    lives::Lives,
+    mode::{continues_allowed, endless_run, GameMode},
+    pipe_sprite,
+    pipe_themes::PipeTheme,
+    pool::PipePool,
+    score::{spawn_score_zone, Score, ScorePipe},
+    scrolls_horizontally, storage,
+    tournament::Tournament,
+    DespawnOnExit, GameRng, GameState, Pipe, Player, RunSeed, Velocity, WorldDirection, CEILING_Y,
+    FONT,
+};
+
+const FILE: &str = "session.ron";
+
+#[derive(Serialize, Deserialize)]
+struct SavedPipe {
+    x: f32,
+    y: f32,
+    top: bool,
+    /// Whether passing this pipe still awards a point.
+    score_pending: bool,
+    checkpoint: bool,
+}
+
+/// Where a run's [`GameRng`] was up to, so the pipes after those on screen come
+/// out as they would have had the run gone on.
+#[derive(Clone, Copy, Serialize, Deserialize)]
+struct SavedRng {
+    seed: u64,
+    /// Words of the stream used up since seeding. A `u128` to the generator,
+    /// but no run gets through anywhere near `u64::MAX`.
+    word_pos: u64,
+}
+
+/// An endless run interrupted by quitting or losing focus. Present on the menu
+/// while there is one to continue.
+///
+/// Pipes are saved as the upright ones scrolling sideways, so Climb runs, whose
+/// pipes lie across the screen and come down, aren't saved at all. They can
+/// still be continued from a checkpoint.
+///
+/// The run's seed and how far its RNG got are saved along with it, so a
+/// continued run plays out the same as if it had never stopped.
+#[derive(Resource, Serialize, Deserialize)]
+struct SavedSession {
+    mode: GameMode,
+    score: u32,
+    player_y: f32,
+    player_velocity: f32,
+    pipes: Vec<SavedPipe>,
+    checkpoints: Checkpoints,
+    /// Remaining lives in [`GameMode::Lives`] runs.
+    lives: Option<u32>,
+    /// Remaining shots in [`GameMode::Arcade`] runs.
+    #[serde(default)]
+    ammo: Option<u32>,
+    /// Missing from sessions saved before it was, which carry on unseeded.
+    #[serde(default)]
+    rng: Option<SavedRng>,
+}
+
+impl storage::SaveFile for SavedSession {
+    const VERSION: u32 = 3;
+
+    fn migrate(&mut self, version: u32) {
+        if version < 3 {
+            self.checkpoints.migrate_passed();
+        }
+    }
+}
+
+/// Replaces the session to continue with the one saved for the active profile.
+pub fn reload_session(commands: &mut Commands) {
+    commands.remove_resource::<SavedSession>();
+    if let Some(session) =
+        storage::data_path(FILE).and_then(|path| storage::load::<SavedSession>(&path))
+    {
+        commands.insert_resource(session);
+    }
+}
+
+fn load_session_system(mut commands: Commands) {
+    reload_session(&mut commands);
+}
+
+#[allow(clippy::too_many_arguments)]
+fn save_session_system(
+    mut exit: EventReader<AppExit>,
+    mut close_requested: EventReader<WindowCloseRequested>,
+    mut focused: EventReader<WindowFocused>,
+    mode: Res<GameMode>,
+    score: Res<Score>,
+    checkpoints: Res<Checkpoints>,
+    lives: Option<Res<Lives>>,
+    ammo: Option<Res<Ammo>>,
+    seed: Res<RunSeed>,
+    rng: Res<GameRng>,
+    player: Query<(&Transform, &Velocity), With<Player>>,
+    pipes: Query<(Entity, &Transform, &Sprite), With<Pipe>>,
+    score_pipes: Query<(), With<ScorePipe>>,
+    checkpoint_pipes: Query<(), With<CheckpointPipe>>,
+) {
+    let quitting = exit.iter().count() > 0 || close_requested.iter().count() > 0;
+    let lost_focus = focused.iter().any(|event| !event.focused);
+    if !quitting && !lost_focus {
+        return;
+    }
+
+    let (transform, velocity) = player.single();
+    let session = SavedSession {
+        mode: *mode,
+        score: score.0,
+        player_y: transform.translation.y,
+        player_velocity: velocity.y,
+        pipes: pipes
+            .iter()
+            .map(|(entity, transform, sprite)| SavedPipe {
+                x: transform.translation.x,
+                y: transform.translation.y,
+                top: sprite.flip_y,
+                score_pending: score_pipes.contains(entity),
+                checkpoint: checkpoint_pipes.contains(entity),
+            })
+            .collect(),
+        checkpoints: checkpoints.clone(),
+        lives: lives.map(|lives| lives.remaining()),
+        ammo: ammo.map(|ammo| ammo.0),
+        rng: Some(SavedRng {
+            seed: seed.current,
+            word_pos: rng.get_word_pos() as u64,
+        }),
+    };
+
+    if let Some(path) = storage::data_path(FILE) {
+        let _ = storage::save(&path, &session);
+    }
+}
+
+fn remove_saved_session() {
+    if let Some(path) = storage::data_path(FILE) {
+        storage::remove(&path);
+    }
+}
+
+/// A run ending, or a new one starting, makes any saved one stale.
+fn discard_session_system(mut commands: Commands) {
+    commands.remove_resource::<SavedSession>();
+    remove_saved_session();
+}
+
+fn spawn_continue_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    session: Res<SavedSession>,
+) {
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Menu),
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::top(Val::Px(160.)),
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("C: continue (score {})", session.score),
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 20.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+#[allow(clippy::too_many_arguments)]
+fn continue_session_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    theme: Res<PipeTheme>,
+    session: Res<SavedSession>,
+    mut mode: ResMut<GameMode>,
+    mut score: ResMut<Score>,
+    mut checkpoints: ResMut<Checkpoints>,
+    mut pool: ResMut<PipePool>,
+    mut seed: ResMut<RunSeed>,
+    mut rng: ResMut<GameRng>,
+    direction: Res<WorldDirection>,
+    mut player: Query<(&mut Transform, &mut Velocity), With<Player>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::C) {
+        return;
+    }
+
+    *mode = session.mode;
+    score.0 = session.score;
+    *checkpoints = session.checkpoints.clone();
+    // Lives and ammo are only started when absent, so inserting them first
+    // carries the saved counts into the run.
+    if let Some(remaining) = session.lives {
+        commands.insert_resource(Lives::with_remaining(remaining));
+    }
+    if let Some(ammo) = session.ammo {
+        commands.insert_resource(Ammo(ammo));
+    }
+    // The run isn't reseeded on entering the game with points on the board,
+    // so this carries on from where the saved one left off.
+    if let Some(saved) = session.rng {
+        seed.current = saved.seed;
+        rng.0 = ChaCha12Rng::seed_from_u64(saved.seed);
+        rng.set_word_pos(u128::from(saved.word_pos));
+    }
+
+    let (mut transform, mut velocity) = player.single_mut();
+    transform.translation.y = session.player_y;
+    velocity.y = session.player_velocity;
+
+    for pipe in &session.pipes {
+        let entity = pool.spawn(
+            &mut commands,
+            pipe_sprite(&theme, pipe.x, pipe.y, pipe.top),
+            direction.scroll_velocity(),
+        );
+        if pipe.score_pending {
+            // Up to the partner pipe, or the ceiling if it was broken off.
+            let gap_top = session
+                .pipes
+                .iter()
+                .find(|top| top.top && (top.x - pipe.x).abs() < 1.)
+                .map_or(CEILING_Y, |top| top.y - theme.size.y);
+            commands.entity(entity).insert(ScorePipe);
+            spawn_score_zone(
+                &mut commands,
+                entity,
+                *direction,
+                theme.width(),
+                gap_top - pipe.y,
+            );
+        }
+        if pipe.checkpoint {
+            commands.entity(entity).insert(CheckpointPipe);
+        }
+    }
+
+    commands.remove_resource::<SavedSession>();
+    remove_saved_session();
+    next_state.set(GameState::InGame);
+}
+
+pub struct SessionPlugin;
+
+impl Plugin for SessionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(load_session_system)
+            .add_system(
+                save_session_system
+                    .in_base_set(CoreSet::Last)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(endless_run)
+                    .run_if(continues_allowed)
+                    .run_if(scrolls_horizontally)
+                    .run_if(not(resource_exists::<Tournament>())),
+            )
+            .add_system(discard_session_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(
+                discard_session_system
+                    .run_if(resource_exists::<SavedSession>())
+                    .in_schedule(OnEnter(GameState::InGame)),
+            )
+            .add_system(
+                spawn_continue_ui_system
+                    .run_if(resource_exists::<SavedSession>())
+                    .in_schedule(OnEnter(GameState::Menu)),
+            )
+            .add_system(
+                continue_session_system
+                    .run_if(resource_exists::<SavedSession>())
+                    .in_set(OnUpdate(GameState::Menu)),
+            );
+    }
+}
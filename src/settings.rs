@@ -0,0 +1,449 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    achievements::{achievement, Achievements},
+    audio::{Bus, BusVolumes},
+    characters::Characters,
+    charge_flap::FlapControl,
+    difficulty::{GapNarrowing, SpeedPreset},
+    focus::{remove_focus_system, Focus, Focusable, MenuInput},
+    rainbow::{self, RainbowMode},
+    storage,
+    texture_packs::TexturePacks,
+    DespawnOnExit, GameState, FONT,
+};
+
+/// Most frames a second to draw, picked in the settings to save battery.
+#[derive(Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Debug)]
+pub enum FpsCap {
+    Fps30,
+    Fps60,
+    Fps120,
+    #[default]
+    Unlimited,
+}
+
+impl FpsCap {
+    const ALL: [FpsCap; 4] = [
+        FpsCap::Fps30,
+        FpsCap::Fps60,
+        FpsCap::Fps120,
+        FpsCap::Unlimited,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            FpsCap::Fps30 => "30",
+            FpsCap::Fps60 => "60",
+            FpsCap::Fps120 => "120",
+            FpsCap::Unlimited => "unlimited",
+        }
+    }
+
+    pub fn step(self, step: isize) -> Self {
+        let index = Self::ALL.iter().position(|c| *c == self).unwrap_or(0) as isize;
+        Self::ALL[(index + step).rem_euclid(Self::ALL.len() as isize) as usize]
+    }
+
+    /// Time each frame gets at most, if capped.
+    pub fn frame_time(self) -> Option<Duration> {
+        let fps = match self {
+            FpsCap::Fps30 => 30.,
+            FpsCap::Fps60 => 60.,
+            FpsCap::Fps120 => 120.,
+            FpsCap::Unlimited => return None,
+        };
+        Some(Duration::from_secs_f64(1. / fps))
+    }
+}
+
+/// Player preferences, persisted as RON in the platform's config directory.
+#[derive(Resource, Serialize, Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct Settings {
+    /// Folder in `mods/` to load replacement sprites from, `None` for the default sprites.
+    pub texture_pack: Option<String>,
+    /// Show a run timer with splits every 10 points in endless runs.
+    pub speedrun_timer: bool,
+    /// Whether pipe gaps shrink as the score goes up in endless runs.
+    pub gap_narrowing: GapNarrowing,
+    /// Rumble connected gamepads on flaps, crashes and new high scores.
+    pub rumble: bool,
+    pub volumes: BusVolumes,
+    /// Silences every bus without touching their volumes.
+    pub muted: bool,
+    /// Multiplies the size of all HUD and menu text and layout.
+    pub ui_scale: f32,
+    pub speed: SpeedPreset,
+    /// Pixels around the bird that can be seen with the fog of war modifier.
+    pub fog_of_war_radius: f32,
+    /// Widen the gap for a while after a few early deaths in a row.
+    pub dynamic_assist: bool,
+    pub flap_control: FlapControl,
+    /// Id of the character from the character manifest.
+    pub character: String,
+    /// Only takes effect once rainbow mode is unlocked.
+    pub rainbow_mode: RainbowMode,
+    /// Show the score and high score in the window title.
+    pub score_in_title: bool,
+    pub vsync: bool,
+    pub fps_cap: FpsCap,
+    /// Drop the trail and the skyline and animate sprites more slowly, for
+    /// slower machines.
+    pub low_spec: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            texture_pack: None,
+            speedrun_timer: false,
+            gap_narrowing: GapNarrowing::default(),
+            rumble: true,
+            volumes: BusVolumes::default(),
+            muted: false,
+            ui_scale: 1.,
+            speed: SpeedPreset::default(),
+            fog_of_war_radius: 140.,
+            dynamic_assist: false,
+            flap_control: FlapControl::default(),
+            character: "bird".into(),
+            rainbow_mode: RainbowMode::default(),
+            score_in_title: false,
+            vsync: true,
+            fps_cap: FpsCap::default(),
+            low_spec: false,
+        }
+    }
+}
+
+impl storage::SaveFile for Settings {
+    const VERSION: u32 = 1;
+}
+
+impl Settings {
+    const FILE: &'static str = "settings.ron";
+
+    pub fn load() -> Self {
+        storage::config_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = storage::config_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+}
+
+/// Label showing the value of the setting at this row.
+#[derive(Component)]
+struct SettingLabel(usize);
+
+const SETTING_NAMES: [&str; 19] = [
+    "Texture pack",
+    "Speedrun timer",
+    "Narrowing gaps",
+    "Rumble",
+    "Music volume",
+    "Effects volume",
+    "Menus volume",
+    "Mute",
+    "UI scale",
+    "Game speed",
+    "Fog of war radius",
+    "Dynamic assist",
+    "Flap control",
+    "Character",
+    "Rainbow mode",
+    "Score in title",
+    "VSync",
+    "FPS cap",
+    "Low-spec mode",
+];
+
+/// The rows from here set the volume of [`Bus::ALL`] in order.
+const FIRST_VOLUME_ROW: usize = 4;
+const MUTE_ROW: usize = FIRST_VOLUME_ROW + Bus::ALL.len();
+const UI_SCALE_ROW: usize = MUTE_ROW + 1;
+const SPEED_ROW: usize = UI_SCALE_ROW + 1;
+const FOG_OF_WAR_ROW: usize = SPEED_ROW + 1;
+const ASSIST_ROW: usize = FOG_OF_WAR_ROW + 1;
+const FLAP_CONTROL_ROW: usize = ASSIST_ROW + 1;
+const CHARACTER_ROW: usize = FLAP_CONTROL_ROW + 1;
+const RAINBOW_ROW: usize = CHARACTER_ROW + 1;
+const TITLE_ROW: usize = RAINBOW_ROW + 1;
+const VSYNC_ROW: usize = TITLE_ROW + 1;
+const FPS_CAP_ROW: usize = VSYNC_ROW + 1;
+const LOW_SPEC_ROW: usize = FPS_CAP_ROW + 1;
+const FOG_OF_WAR_RANGE: (f32, f32) = (60., 300.);
+const FOG_OF_WAR_STEP: f32 = 20.;
+const UI_SCALE_RANGE: (f32, f32) = (0.75, 2.);
+const UI_SCALE_STEP: f32 = 0.25;
+/// Rows shown at once, few enough to fit the window even at the largest UI
+/// scale. The page follows the focused row.
+const ROWS_PER_PAGE: usize = 7;
+
+fn page_count() -> usize {
+    SETTING_NAMES.len().div_ceil(ROWS_PER_PAGE)
+}
+
+fn page_hint(page: usize) -> String {
+    format!(
+        "Page {}/{}   Up/Down: select   Left/Right: change\nEsc: back",
+        page + 1,
+        page_count()
+    )
+}
+
+#[derive(Component)]
+struct PageHint;
+
+fn setting_label(
+    settings: &Settings,
+    characters: &Characters,
+    achievements: &Achievements,
+    row: usize,
+    selected: bool,
+) -> String {
+    let formatted;
+    let value = match row {
+        0 => settings.texture_pack.as_deref().unwrap_or("default"),
+        1 if settings.speedrun_timer => "on",
+        1 => "off",
+        2 => settings.gap_narrowing.name(),
+        3 if settings.rumble => "on",
+        3 => "off",
+        MUTE_ROW if settings.muted => "on",
+        MUTE_ROW => "off",
+        UI_SCALE_ROW => {
+            formatted = format!("{:.0}%", settings.ui_scale * 100.);
+            &formatted
+        }
+        SPEED_ROW => settings.speed.name(),
+        FOG_OF_WAR_ROW => {
+            formatted = format!("{:.0}px", settings.fog_of_war_radius);
+            &formatted
+        }
+        ASSIST_ROW if settings.dynamic_assist => "on",
+        ASSIST_ROW => "off",
+        FLAP_CONTROL_ROW => settings.flap_control.name(),
+        CHARACTER_ROW => characters.name(&settings.character),
+        RAINBOW_ROW if !achievements.is_unlocked(rainbow::UNLOCK) => {
+            let requirement = achievement(rainbow::UNLOCK).map_or("", |a| a.description);
+            formatted = format!("locked ({requirement})");
+            &formatted
+        }
+        RAINBOW_ROW => settings.rainbow_mode.name(),
+        TITLE_ROW if settings.score_in_title => "on",
+        TITLE_ROW => "off",
+        VSYNC_ROW if settings.vsync => "on",
+        VSYNC_ROW => "off",
+        FPS_CAP_ROW => settings.fps_cap.name(),
+        LOW_SPEC_ROW if settings.low_spec => "on",
+        LOW_SPEC_ROW => "off",
+        _ => {
+            let bus = Bus::ALL[row - FIRST_VOLUME_ROW];
+            formatted = format!("{:.0}%", settings.volumes.get(bus) * 100.);
+            &formatted
+        }
+    };
+
+    if selected {
+        format!("{}: < {value} >", SETTING_NAMES[row])
+    } else {
+        format!("{}: {value}", SETTING_NAMES[row])
+    }
+}
+
+fn spawn_settings_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<Settings>,
+    characters: Res<Characters>,
+    achievements: Res<Achievements>,
+) {
+    commands.insert_resource(Focus::new(SETTING_NAMES.len()));
+
+    let font = asset_server.load(FONT);
+    let style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Settings),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(12.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Settings", style(40.)));
+            for row in 0..SETTING_NAMES.len() {
+                parent.spawn((
+                    SettingLabel(row),
+                    Focusable(row),
+                    TextBundle::from_section(
+                        setting_label(&settings, &characters, &achievements, row, row == 0),
+                        style(22.),
+                    )
+                    .with_style(Style {
+                        display: if row < ROWS_PER_PAGE {
+                            Display::Flex
+                        } else {
+                            Display::None
+                        },
+                        ..Default::default()
+                    }),
+                ));
+            }
+            parent.spawn((PageHint, TextBundle::from_section(page_hint(0), style(16.))));
+        });
+}
+
+fn open_settings_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::S) {
+        next_state.set(GameState::Settings);
+    }
+}
+
+fn cycle_texture_pack(settings: &mut Settings, packs: &TexturePacks, step: isize) {
+    // Index 0 is the default sprites, followed by every pack in `mods/`.
+    let choices = packs.available.len() as isize + 1;
+    let current = settings
+        .texture_pack
+        .as_ref()
+        .and_then(|pack| packs.available.iter().position(|p| p == pack))
+        .map_or(0, |index| index as isize + 1);
+    let next = (current + step).rem_euclid(choices) as usize;
+
+    settings.texture_pack = next
+        .checked_sub(1)
+        .map(|index| packs.available[index].clone());
+}
+
+fn settings_input_system(
+    menu_input: MenuInput,
+    packs: Res<TexturePacks>,
+    characters: Res<Characters>,
+    achievements: Res<Achievements>,
+    focus: Res<Focus>,
+    mut settings: ResMut<Settings>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if menu_input.back() {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    let step = menu_input.adjustment();
+    if step == 0 {
+        return;
+    }
+
+    match focus.index {
+        0 => cycle_texture_pack(&mut settings, &packs, step),
+        1 => settings.speedrun_timer = !settings.speedrun_timer,
+        2 => settings.gap_narrowing = settings.gap_narrowing.step(step),
+        3 => settings.rumble = !settings.rumble,
+        MUTE_ROW => settings.muted = !settings.muted,
+        UI_SCALE_ROW => {
+            settings.ui_scale = (settings.ui_scale + step as f32 * UI_SCALE_STEP)
+                .clamp(UI_SCALE_RANGE.0, UI_SCALE_RANGE.1)
+        }
+        SPEED_ROW => settings.speed = settings.speed.step(step),
+        FOG_OF_WAR_ROW => {
+            settings.fog_of_war_radius = (settings.fog_of_war_radius
+                + step as f32 * FOG_OF_WAR_STEP)
+                .clamp(FOG_OF_WAR_RANGE.0, FOG_OF_WAR_RANGE.1)
+        }
+        ASSIST_ROW => settings.dynamic_assist = !settings.dynamic_assist,
+        FLAP_CONTROL_ROW => settings.flap_control = settings.flap_control.step(step),
+        CHARACTER_ROW => settings.character = characters.step(&settings.character, step),
+        RAINBOW_ROW if !achievements.is_unlocked(rainbow::UNLOCK) => return,
+        RAINBOW_ROW => settings.rainbow_mode = settings.rainbow_mode.step(step),
+        TITLE_ROW => settings.score_in_title = !settings.score_in_title,
+        VSYNC_ROW => settings.vsync = !settings.vsync,
+        FPS_CAP_ROW => settings.fps_cap = settings.fps_cap.step(step),
+        LOW_SPEC_ROW => settings.low_spec = !settings.low_spec,
+        row => {
+            let volume = settings.volumes.get_mut(Bus::ALL[row - FIRST_VOLUME_ROW]);
+            *volume = (*volume + step as f32 * 0.1).clamp(0., 1.);
+        }
+    }
+    settings.save();
+}
+
+fn update_settings_ui_system(
+    settings: Res<Settings>,
+    characters: Res<Characters>,
+    achievements: Res<Achievements>,
+    focus: Res<Focus>,
+    mut query: Query<(&mut Text, &mut Style, &SettingLabel)>,
+    mut page_hint_text: Query<&mut Text, (With<PageHint>, Without<SettingLabel>)>,
+) {
+    if !settings.is_changed() && !focus.is_changed() {
+        return;
+    }
+
+    let page = focus.index / ROWS_PER_PAGE;
+    for (mut text, mut style, label) in query.iter_mut() {
+        text.sections[0].value = setting_label(
+            &settings,
+            &characters,
+            &achievements,
+            label.0,
+            label.0 == focus.index,
+        );
+        let display = if label.0 / ROWS_PER_PAGE == page {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        if style.display != display {
+            style.display = display;
+        }
+    }
+    for mut text in page_hint_text.iter_mut() {
+        text.sections[0].value = page_hint(page);
+    }
+}
+
+fn apply_ui_scale_system(settings: Res<Settings>, mut ui_scale: ResMut<UiScale>) {
+    if settings.is_changed() {
+        ui_scale.scale = settings.ui_scale.into();
+    }
+}
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(open_settings_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_settings_ui_system.in_schedule(OnEnter(GameState::Settings)))
+            .add_system(remove_focus_system.in_schedule(OnExit(GameState::Settings)))
+            .add_systems(
+                (settings_input_system, update_settings_ui_system)
+                    .in_set(OnUpdate(GameState::Settings)),
+            )
+            .add_system(apply_ui_scale_system);
+    }
+}
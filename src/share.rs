@@ -0,0 +1,362 @@
+use bevy::{prelude::*, render::view::RenderLayers, sprite::Anchor, window::ReceivedCharacter};
+
+use crate::{
+    clipboard,
+    console::console_closed,
+    daily::{civil_from_days, today},
+    focus::FocusSet,
+    score::Score,
+    screenshot::{screenshots_dir, spawn_capture_camera, spawn_toast, timestamp, CapturePurpose},
+    DespawnOnExit, GameState, RunSeed, FONT,
+};
+
+const CARD_SIZE: Vec2 = Vec2::new(480., 270.);
+/// Layer the card is drawn on, so only the capture camera sees it.
+const CARD_LAYER: u8 = 1;
+const CARD_COLOR: Color = Color::rgb(0.3, 0.65, 0.85);
+/// Lowest score for each medal, best first.
+const MEDALS: [(u32, &str, Color); 4] = [
+    (40, "Platinum", Color::rgb(0.85, 0.9, 0.95)),
+    (30, "Gold", Color::rgb(1., 0.8, 0.2)),
+    (20, "Silver", Color::rgb(0.75, 0.75, 0.8)),
+    (10, "Bronze", Color::rgb(0.8, 0.5, 0.25)),
+];
+
+/// Longest code a `u64` seed can take.
+const MAX_CODE_LEN: usize = 13;
+
+/// Part of a score card being rendered. Only lives for the frame it's captured in.
+#[derive(Component)]
+struct ScoreCard;
+
+/// A seed code being typed in on the menu.
+#[derive(Resource, Default)]
+struct SeedEntry(String);
+
+#[derive(Component)]
+struct SeedEntryText;
+
+/// Short form of a seed for players to pass around.
+pub fn seed_code(mut seed: u64) -> String {
+    let mut digits = Vec::new();
+    loop {
+        digits.push(char::from_digit((seed % 36) as u32, 36).unwrap_or('0'));
+        seed /= 36;
+        if seed == 0 {
+            break;
+        }
+    }
+    digits.iter().rev().collect::<String>().to_uppercase()
+}
+
+fn parse_seed_code(code: &str) -> Option<u64> {
+    u64::from_str_radix(code, 36).ok()
+}
+
+fn medal(score: u32) -> Option<(&'static str, Color)> {
+    MEDALS
+        .iter()
+        .find(|(min, _, _)| score >= *min)
+        .map(|(_, name, color)| (*name, *color))
+}
+
+fn spawn_share_hint_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    seed: Res<RunSeed>,
+) {
+    commands
+        .spawn((
+            DespawnOnExit(GameState::GameOver),
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::bottom(Val::Px(90.)),
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!(
+                    "S: share score card   Seed code: {} (Ctrl+C to copy)",
+                    seed_code(seed.current)
+                ),
+                TextStyle {
+                    font: asset_server.load(FONT),
+                    font_size: 18.,
+                    color: Color::WHITE,
+                },
+            ));
+        });
+}
+
+fn copy_seed_code_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    keyboard_input: Res<Input<KeyCode>>,
+    seed: Res<RunSeed>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::C) || !clipboard::shortcut_held(&keyboard_input) {
+        return;
+    }
+
+    let code = seed_code(seed.current);
+    let message = match clipboard::copy(&code) {
+        Ok(()) => format!("Copied seed code {code}"),
+        Err(err) => {
+            warn!("Failed to copy the seed code: {err}");
+            format!("Couldn't copy seed code: {err}")
+        }
+    };
+    spawn_toast(&mut commands, &asset_server, message);
+}
+
+/// Lays the card out on its own layer and points a capture camera at it.
+fn share_score_card_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    asset_server: Res<AssetServer>,
+    score: Res<Score>,
+    seed: Res<RunSeed>,
+    mut images: ResMut<Assets<Image>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::S) {
+        return;
+    }
+
+    let font = asset_server.load(FONT);
+    let layer = RenderLayers::layer(CARD_LAYER);
+    let mut text = |value: String, font_size, color, position: Vec2, anchor| {
+        commands.spawn((
+            ScoreCard,
+            layer,
+            Text2dBundle {
+                text: Text::from_section(
+                    value,
+                    TextStyle {
+                        font: font.clone(),
+                        font_size,
+                        color,
+                    },
+                ),
+                text_anchor: anchor,
+                transform: Transform::from_translation(position.extend(1.)),
+                ..Default::default()
+            },
+        ));
+    };
+
+    let (year, month, day) = civil_from_days(today());
+    text(
+        "Flappy Bird".to_string(),
+        32.,
+        Color::WHITE,
+        Vec2::new(0., 95.),
+        Anchor::Center,
+    );
+    text(
+        score.0.to_string(),
+        72.,
+        Color::WHITE,
+        Vec2::new(0., 15.),
+        Anchor::Center,
+    );
+    text(
+        format!("{year}-{month:02}-{day:02}"),
+        18.,
+        Color::WHITE,
+        Vec2::new(-CARD_SIZE.x / 2. + 20., -CARD_SIZE.y / 2. + 20.),
+        Anchor::BottomLeft,
+    );
+    text(
+        format!("Seed {}", seed_code(seed.current)),
+        18.,
+        Color::WHITE,
+        Vec2::new(CARD_SIZE.x / 2. - 20., -CARD_SIZE.y / 2. + 20.),
+        Anchor::BottomRight,
+    );
+    if let Some((name, color)) = medal(score.0) {
+        text(
+            format!("{name} medal"),
+            22.,
+            color,
+            Vec2::new(0., -55.),
+            Anchor::Center,
+        );
+    }
+
+    commands.spawn((
+        ScoreCard,
+        layer,
+        SpriteBundle {
+            sprite: Sprite {
+                color: CARD_COLOR,
+                custom_size: Some(CARD_SIZE),
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+    ));
+
+    let path = screenshots_dir().join(format!("score-card-{}.png", timestamp()));
+    let camera = spawn_capture_camera(
+        &mut commands,
+        &mut images,
+        CARD_SIZE.as_uvec2(),
+        CapturePurpose::ScoreCard(path),
+    );
+    commands
+        .entity(camera)
+        .insert((layer, UiCameraConfig { show_ui: false }));
+}
+
+/// The card has been extracted along with its camera by now.
+fn despawn_score_cards_system(mut commands: Commands, query: Query<Entity, With<ScoreCard>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+fn spawn_seed_entry_text(commands: &mut Commands, asset_server: &AssetServer) {
+    commands.spawn((
+        SeedEntryText,
+        DespawnOnExit(GameState::Menu),
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 24.,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(200.),
+                left: Val::Px(40.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+/// Types a seed code in once opened, or pastes one with Ctrl+V, keeping the
+/// keys from reaching the menu underneath. Entering a valid code starts a run
+/// with that seed.
+#[allow(clippy::too_many_arguments)]
+fn seed_entry_input_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    entry: Option<ResMut<SeedEntry>>,
+    mut seed: ResMut<RunSeed>,
+    mut keyboard_input: ResMut<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut next_state: ResMut<NextState<GameState>>,
+    text: Query<Entity, With<SeedEntryText>>,
+) {
+    let Some(mut entry) = entry else {
+        characters.clear();
+        if keyboard_input.just_pressed(KeyCode::V) {
+            commands.init_resource::<SeedEntry>();
+            spawn_seed_entry_text(&mut commands, &asset_server);
+            keyboard_input.reset_all();
+        }
+        return;
+    };
+
+    let pasting =
+        keyboard_input.just_pressed(KeyCode::V) && clipboard::shortcut_held(&keyboard_input);
+    let pasted = if pasting {
+        clipboard::paste().unwrap_or_else(|err| {
+            spawn_toast(
+                &mut commands,
+                &asset_server,
+                format!("Couldn't paste: {err}"),
+            );
+            String::new()
+        })
+    } else {
+        String::new()
+    };
+    // The V of a paste comes through as a character too, or as a control one.
+    let typed = characters
+        .iter()
+        .map(|character| character.char)
+        .filter(|_| !pasting);
+    for char in typed.chain(pasted.trim().chars()) {
+        if char.is_ascii_alphanumeric() && entry.0.len() < MAX_CODE_LEN {
+            entry.0.push(char.to_ascii_uppercase());
+        }
+    }
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        entry.0.pop();
+    }
+
+    let close = if keyboard_input.just_pressed(KeyCode::Escape) {
+        true
+    } else if keyboard_input.just_pressed(KeyCode::Return) {
+        match parse_seed_code(&entry.0) {
+            Some(code) => {
+                seed.next = Some(code);
+                next_state.set(GameState::InGame);
+            }
+            None => spawn_toast(
+                &mut commands,
+                &asset_server,
+                format!("Invalid seed code: {}", entry.0),
+            ),
+        }
+        true
+    } else {
+        false
+    };
+    keyboard_input.reset_all();
+
+    if close {
+        commands.remove_resource::<SeedEntry>();
+        for entity in text.iter() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn update_seed_entry_text_system(
+    entry: Res<SeedEntry>,
+    mut text: Query<&mut Text, With<SeedEntryText>>,
+) {
+    for mut text in text.iter_mut() {
+        text.sections[0].value = format!(
+            "Seed code: {}_\nEnter: play   Ctrl+V: paste   Esc: cancel",
+            entry.0
+        );
+    }
+}
+
+fn close_seed_entry_system(mut commands: Commands) {
+    commands.remove_resource::<SeedEntry>();
+}
+
+pub struct SharePlugin;
+
+impl Plugin for SharePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(spawn_share_hint_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(share_score_card_system.in_set(OnUpdate(GameState::GameOver)))
+            .add_system(copy_seed_code_system.in_set(OnUpdate(GameState::GameOver)))
+            .add_system(despawn_score_cards_system.in_base_set(CoreSet::First))
+            .add_system(
+                seed_entry_input_system
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(FocusSet)
+                    .run_if(console_closed)
+                    .run_if(in_state(GameState::Menu)),
+            )
+            .add_system(update_seed_entry_text_system.run_if(resource_exists::<SeedEntry>()))
+            .add_system(close_seed_entry_system.in_schedule(OnExit(GameState::Menu)));
+    }
+}
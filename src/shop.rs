@@ -0,0 +1,213 @@
+use bevy::prelude::*;
+
+use crate::{
+    achievements::{achievement, Achievements},
+    coins::Wallet,
+    cosmetics::{Customization, Item, ITEMS},
+    focus::{remove_focus_system, Focus, MenuInput},
+    DespawnOnExit, GameState, FONT,
+};
+
+/// Items listed at once. The list scrolls to keep the selected one in view.
+const VISIBLE_ROWS: usize = 18;
+
+/// Label for the item at this many rows down the visible part of [`ITEMS`].
+#[derive(Component)]
+struct ShopRow(usize);
+
+#[derive(Component)]
+enum ShopLabel {
+    Balance,
+    /// Says what unlocks the selected item while it's locked.
+    UnlockHint,
+}
+
+fn item_label(
+    customization: &Customization,
+    achievements: &Achievements,
+    item: &Item,
+    selected: bool,
+) -> String {
+    let status = if Customization::is_locked(item, achievements) {
+        "locked".to_string()
+    } else if customization.equipped(item.kind) == item.id {
+        "equipped".to_string()
+    } else if customization.owns(item) {
+        "owned".to_string()
+    } else {
+        format!("{} ●", item.price)
+    };
+    let label = format!("{:<6}{:<10}{status:>9}", item.kind.name(), item.name);
+
+    if selected {
+        format!("> {label} <")
+    } else {
+        format!("  {label}  ")
+    }
+}
+
+/// Index in [`ITEMS`] of the top row, keeping `selected` near the middle.
+fn first_visible(selected: usize) -> usize {
+    selected
+        .saturating_sub(VISIBLE_ROWS / 2)
+        .min(ITEMS.len().saturating_sub(VISIBLE_ROWS))
+}
+
+fn unlock_hint(achievements: &Achievements, item: &Item) -> String {
+    item.unlock
+        .filter(|_| Customization::is_locked(item, achievements))
+        .and_then(achievement)
+        .map_or(String::new(), |achievement| {
+            format!("Unlock: {}", achievement.description)
+        })
+}
+
+fn open_shop_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::B) {
+        next_state.set(GameState::Shop);
+    }
+}
+
+fn spawn_shop_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    customization: Res<Customization>,
+    achievements: Res<Achievements>,
+    wallet: Res<Wallet>,
+) {
+    commands.insert_resource(Focus::new(ITEMS.len()));
+
+    let font = asset_server.load(FONT);
+    let style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Shop),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(6.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Shop", style(40.)));
+            parent.spawn((
+                ShopLabel::Balance,
+                TextBundle::from_section(format!("{} ●", wallet.coins), style(22.)),
+            ));
+            for (index, item) in ITEMS.iter().take(VISIBLE_ROWS).enumerate() {
+                parent.spawn((
+                    ShopRow(index),
+                    TextBundle::from_section(
+                        item_label(&customization, &achievements, item, index == 0),
+                        style(17.),
+                    ),
+                ));
+            }
+            parent.spawn((
+                ShopLabel::UnlockHint,
+                TextBundle::from_section(unlock_hint(&achievements, &ITEMS[0]), style(16.)),
+            ));
+            parent.spawn(TextBundle::from_section(
+                "Up/Down: select   Enter: buy/equip\nEsc: back",
+                style(16.),
+            ));
+        });
+}
+
+/// Enter buys the selected item if it's affordable and equips it once owned.
+/// Locked items can't be bought until their achievement is unlocked.
+fn shop_input_system(
+    menu_input: MenuInput,
+    achievements: Res<Achievements>,
+    focus: Res<Focus>,
+    mut customization: ResMut<Customization>,
+    mut wallet: ResMut<Wallet>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if menu_input.back() {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    if !focus.activated() {
+        return;
+    }
+
+    let item = &ITEMS[focus.index];
+    if Customization::is_locked(item, &achievements) {
+        return;
+    }
+    if !customization.owns(item) {
+        if wallet.coins < item.price {
+            return;
+        }
+        wallet.coins -= item.price;
+        customization.owned.insert(item.id.into());
+        wallet.save();
+    }
+    customization.equip(item);
+    customization.save();
+}
+
+/// Redraws on purchases, selection and newly unlocked achievements.
+fn update_shop_ui_system(
+    customization: Res<Customization>,
+    achievements: Res<Achievements>,
+    wallet: Res<Wallet>,
+    focus: Res<Focus>,
+    mut rows: Query<(&mut Text, &ShopRow)>,
+    mut labels: Query<(&mut Text, &ShopLabel), Without<ShopRow>>,
+) {
+    if !customization.is_changed()
+        && !achievements.is_changed()
+        && !wallet.is_changed()
+        && !focus.is_changed()
+    {
+        return;
+    }
+
+    let first = first_visible(focus.index);
+    for (mut text, row) in rows.iter_mut() {
+        let index = first + row.0;
+        text.sections[0].value = item_label(
+            &customization,
+            &achievements,
+            &ITEMS[index],
+            index == focus.index,
+        );
+    }
+    for (mut text, label) in labels.iter_mut() {
+        text.sections[0].value = match label {
+            ShopLabel::Balance => format!("{} ●", wallet.coins),
+            ShopLabel::UnlockHint => unlock_hint(&achievements, &ITEMS[focus.index]),
+        };
+    }
+}
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(open_shop_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_shop_ui_system.in_schedule(OnEnter(GameState::Shop)))
+            .add_system(remove_focus_system.in_schedule(OnExit(GameState::Shop)))
+            .add_systems(
+                (shop_input_system, update_shop_ui_system).in_set(OnUpdate(GameState::Shop)),
+            );
+    }
+}
@@ -0,0 +1,237 @@
+use std::ops::Range;
+
+use bevy::{prelude::*, sprite::Anchor};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+use crate::{
+    resolution::WorldScale, seed_run_system, settings::Settings, Background, GameState, RunSeed,
+    BACKGROUND_SEGMENT_WIDTH, SCALE,
+};
+
+/// Width of a background segment in the `@1x` `bg.png`'s own pixels, which the
+/// buildings are laid out in.
+const SEGMENT_WIDTH: u32 = (BACKGROUND_SEGMENT_WIDTH / SCALE.x) as u32;
+/// Where the buildings stand, in pixels down from the top of `bg.png`, just
+/// behind the tops of the bushes.
+const GROUND: f32 = 187.;
+const BUILDING_WIDTH: Range<u32> = 7..19;
+const WINDOW_COLOR: Color = Color::rgb(0.83, 0.93, 0.78);
+/// Windows are drawn as a lit row every this many pixels up a building.
+const WINDOW_SPACING: u32 = 3;
+
+/// The skyline is two rows of buildings, a taller, paler one behind the other.
+#[derive(Clone, Copy)]
+enum Layer {
+    Far,
+    Near,
+}
+
+impl Layer {
+    fn color(self) -> Color {
+        match self {
+            Layer::Far => Color::rgb(0.73, 0.9, 0.77),
+            Layer::Near => Color::rgb(0.63, 0.86, 0.83),
+        }
+    }
+
+    fn heights(self) -> Range<u32> {
+        match self {
+            Layer::Far => 14..32,
+            Layer::Near => 6..20,
+        }
+    }
+
+    /// Most pixels between one building and the next.
+    fn max_gap(self) -> u32 {
+        match self {
+            Layer::Far => 1,
+            Layer::Near => 6,
+        }
+    }
+
+    /// Relative to the background segment, so both stay behind the pipes.
+    fn depth(self) -> f32 {
+        match self {
+            Layer::Far => 0.001,
+            Layer::Near => 0.002,
+        }
+    }
+}
+
+/// Lays out the buildings, kept apart from [`GameRng`](crate::GameRng) so the
+/// scenery can't change how a seeded run plays out.
+#[derive(Resource)]
+struct SkylineRng(StdRng);
+
+impl Default for SkylineRng {
+    fn default() -> Self {
+        Self(StdRng::from_entropy())
+    }
+}
+
+/// A background segment with buildings on it, and where it was last seen so
+/// it can be rebuilt once it wraps round to the back of the line.
+#[derive(Component)]
+struct SkylineSegment {
+    x: f32,
+}
+
+/// Part of a building, tinted along with the background segment it stands on.
+#[derive(Component)]
+struct Building {
+    color: Color,
+}
+
+/// Blown up from the `@1x` pixels the skyline is laid out in to those of the
+/// background texture it stands on, which is `density` times as detailed.
+fn building_sprite(
+    color: Color,
+    tint: Color,
+    density: f32,
+    x: u32,
+    y: f32,
+    size: Vec2,
+    depth: f32,
+) -> impl Bundle {
+    (
+        Building { color },
+        SpriteBundle {
+            sprite: Sprite {
+                color: color * tint.as_rgba_f32(),
+                custom_size: Some(size * density),
+                anchor: Anchor::BottomLeft,
+                ..Default::default()
+            },
+            transform: Transform::from_xyz(x as f32 * density, y * density, depth),
+            ..Default::default()
+        },
+    )
+}
+
+fn spawn_layer(
+    parent: &mut ChildBuilder,
+    rng: &mut impl Rng,
+    tint: Color,
+    density: f32,
+    layer: Layer,
+) {
+    let mut x = rng.gen_range(0..=layer.max_gap());
+    while x < SEGMENT_WIDTH {
+        let width = rng.gen_range(BUILDING_WIDTH).min(SEGMENT_WIDTH - x);
+        let height = rng.gen_range(layer.heights());
+        parent.spawn(building_sprite(
+            layer.color(),
+            tint,
+            density,
+            x,
+            -GROUND,
+            Vec2::new(width as f32, height as f32),
+            layer.depth(),
+        ));
+
+        if matches!(layer, Layer::Near) && width > 2 {
+            for row in (WINDOW_SPACING..height - 1).step_by(WINDOW_SPACING as usize) {
+                parent.spawn(building_sprite(
+                    WINDOW_COLOR,
+                    tint,
+                    density,
+                    x + 1,
+                    -GROUND + row as f32,
+                    Vec2::new(width as f32 - 2., 1.),
+                    layer.depth() + 0.0005,
+                ));
+            }
+        }
+
+        x += width + rng.gen_range(0..=layer.max_gap());
+    }
+}
+
+/// Gives every run its own skyline, which plays again along with the run's
+/// seed.
+fn seed_skyline_system(
+    mut commands: Commands,
+    seed: Res<RunSeed>,
+    mut rng: ResMut<SkylineRng>,
+    segments: Query<Entity, With<SkylineSegment>>,
+) {
+    rng.0 = StdRng::seed_from_u64(seed.current);
+    for entity in segments.iter() {
+        commands.entity(entity).remove::<SkylineSegment>();
+    }
+}
+
+/// Puts up new buildings on background segments as they're tiled, and again
+/// each time one wraps round out of sight, so the skyline never repeats.
+/// Low-spec mode goes without.
+#[allow(clippy::type_complexity)]
+fn build_skyline_system(
+    mut commands: Commands,
+    settings: Res<Settings>,
+    world_scale: Res<WorldScale>,
+    mut low_spec: Local<bool>,
+    mut rng: ResMut<SkylineRng>,
+    mut segments: Query<
+        (Entity, &Transform, &Sprite, Option<&mut SkylineSegment>),
+        With<Background>,
+    >,
+) {
+    let toggled = *low_spec != settings.low_spec;
+    *low_spec = settings.low_spec;
+
+    for (entity, transform, sprite, segment) in segments.iter_mut() {
+        let tint = sprite.color;
+        let x = transform.translation.x;
+        match segment {
+            Some(mut segment) if !toggled && (x - segment.x).abs() < BACKGROUND_SEGMENT_WIDTH => {
+                segment.x = x;
+                continue;
+            }
+            Some(mut segment) => segment.x = x,
+            None => {
+                commands.entity(entity).insert(SkylineSegment { x });
+            }
+        }
+
+        let mut segment = commands.entity(entity);
+        segment.despawn_descendants();
+        if settings.low_spec {
+            continue;
+        }
+        segment.with_children(|parent| {
+            let density = world_scale.density();
+            spawn_layer(parent, &mut rng.0, tint, density, Layer::Far);
+            spawn_layer(parent, &mut rng.0, tint, density, Layer::Near);
+        });
+    }
+}
+
+/// Keeps the buildings in the background's tint as it changes, whether from
+/// the shop or rainbow mode.
+#[allow(clippy::type_complexity)]
+fn tint_skyline_system(
+    segments: Query<&Sprite, (With<Background>, Changed<Sprite>, Without<Building>)>,
+    mut buildings: Query<(&Building, &Parent, &mut Sprite)>,
+) {
+    for (building, parent, mut sprite) in buildings.iter_mut() {
+        if let Ok(segment) = segments.get(parent.get()) {
+            sprite.color = building.color * segment.color.as_rgba_f32();
+        }
+    }
+}
+
+/// A city skyline drawn from random buildings instead of a texture, different
+/// every run and scrolling along with the background.
+pub struct SkylinePlugin;
+
+impl Plugin for SkylinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SkylineRng>()
+            .add_system(
+                seed_skyline_system
+                    .after(seed_run_system)
+                    .in_schedule(OnEnter(GameState::InGame)),
+            )
+            .add_systems((build_skyline_system, tint_skyline_system));
+    }
+}
@@ -0,0 +1,191 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    mode::endless_run, score::Score, settings::Settings, storage, touch::FlapInput, GameState,
+    GameplaySet, FONT,
+};
+
+/// A split is recorded every this many points.
+pub const SPLIT_INTERVAL: u32 = 10;
+
+/// Best time to reach each split, persisted as RON in the platform's data
+/// directory. Index 0 is the time to 10 points, 1 to 20 and so on.
+#[derive(Resource, Serialize, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct PersonalBests {
+    splits: Vec<f32>,
+}
+
+impl storage::SaveFile for PersonalBests {
+    const VERSION: u32 = 1;
+}
+
+impl PersonalBests {
+    const FILE: &'static str = "splits.ron";
+
+    pub fn load() -> Self {
+        storage::data_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = storage::data_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+
+    /// Keeps the faster time for every split.
+    pub fn merge(&mut self, other: &PersonalBests) {
+        for (index, time) in other.splits.iter().enumerate() {
+            match self.splits.get_mut(index) {
+                Some(best) => *best = best.min(*time),
+                None => self.splits.push(*time),
+            }
+        }
+    }
+}
+
+pub struct Split {
+    /// The multiple of [`SPLIT_INTERVAL`] the split was recorded at.
+    pub points: u32,
+    pub time: f32,
+    /// Difference to the personal best at the time of the split, if there was one.
+    pub delta: Option<f32>,
+}
+
+/// Timer for the current endless run. Only present when enabled in the settings
+/// and the run started from zero.
+#[derive(Resource, Default)]
+pub struct Speedrun {
+    /// Seconds since the first flap, `None` until then.
+    elapsed: Option<f32>,
+    /// The score when splits were last checked.
+    last_score: u32,
+    pub splits: Vec<Split>,
+}
+
+#[derive(Component)]
+struct SpeedrunText;
+
+fn start_speedrun_system(mut commands: Commands, settings: Res<Settings>, score: Res<Score>) {
+    // Continuing from a checkpoint or a saved session wouldn't be a fair time.
+    if settings.speedrun_timer && score.0 == 0 {
+        commands.insert_resource(Speedrun::default());
+    }
+}
+
+fn stop_speedrun_system(mut commands: Commands) {
+    commands.remove_resource::<Speedrun>();
+}
+
+fn tick_speedrun_system(time: Res<Time>, flap_input: FlapInput, mut speedrun: ResMut<Speedrun>) {
+    if let Some(elapsed) = &mut speedrun.elapsed {
+        *elapsed += time.delta_seconds();
+    } else if flap_input.just_pressed() {
+        speedrun.elapsed = Some(0.);
+    }
+}
+
+/// Records a split for every multiple of [`SPLIT_INTERVAL`] the score went
+/// past, so points worth more than one can't jump over one.
+fn record_splits_system(
+    score: Res<Score>,
+    mut speedrun: ResMut<Speedrun>,
+    mut bests: ResMut<PersonalBests>,
+) {
+    let crossed = speedrun.last_score / SPLIT_INTERVAL..score.0 / SPLIT_INTERVAL;
+    speedrun.last_score = score.0;
+
+    let time = speedrun.elapsed.unwrap_or_default();
+    for index in crossed {
+        let index = index as usize;
+        let best = bests.splits.get(index).copied();
+        speedrun.splits.push(Split {
+            points: (index as u32 + 1) * SPLIT_INTERVAL,
+            time,
+            delta: best.map(|best| time - best),
+        });
+
+        match best {
+            Some(best) if best <= time => {}
+            Some(_) => bests.splits[index] = time,
+            None => bests.splits.push(time),
+        }
+    }
+}
+
+fn save_personal_bests_system(bests: Res<PersonalBests>) {
+    if bests.is_changed() {
+        bests.save();
+    }
+}
+
+pub fn format_time(secs: f32) -> String {
+    format!("{}:{:05.2}", (secs / 60.) as u32, secs % 60.)
+}
+
+fn spawn_speedrun_text_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.spawn((
+        SpeedrunText,
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font: asset_server.load(FONT),
+                font_size: 20.,
+                color: Color::WHITE,
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            position: UiRect {
+                top: Val::Px(10.),
+                right: Val::Px(10.),
+                ..Default::default()
+            },
+            ..Default::default()
+        }),
+    ));
+}
+
+fn update_speedrun_text_system(
+    speedrun: Option<Res<Speedrun>>,
+    mut query: Query<&mut Text, With<SpeedrunText>>,
+) {
+    let value = speedrun.map_or(String::new(), |speedrun| {
+        format_time(speedrun.elapsed.unwrap_or_default())
+    });
+
+    for mut text in query.iter_mut() {
+        if text.sections[0].value != value {
+            text.sections[0].value = value.clone();
+        }
+    }
+}
+
+pub struct SpeedrunPlugin;
+
+impl Plugin for SpeedrunPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(PersonalBests::load())
+            .add_startup_system(spawn_speedrun_text_system)
+            .add_system(
+                start_speedrun_system
+                    .run_if(endless_run)
+                    .run_if(not(resource_exists::<Speedrun>()))
+                    .in_schedule(OnEnter(GameState::InGame)),
+            )
+            .add_system(stop_speedrun_system.in_schedule(OnExit(GameState::GameOver)))
+            .add_systems(
+                (
+                    tick_speedrun_system,
+                    record_splits_system.after(GameplaySet::Scoring),
+                )
+                    .distributive_run_if(resource_exists::<Speedrun>())
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(save_personal_bests_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(update_speedrun_text_system);
+    }
+}
@@ -0,0 +1,198 @@
+use bevy::{app::AppExit, prelude::*, window::WindowCloseRequested};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    coins::RunCoins,
+    focus::MenuInput,
+    grading::{PassGrade, PassGraded},
+    score::{Score, ScoredEvent},
+    storage,
+    touch::FlapInput,
+    DespawnOnExit, GameState, FONT,
+};
+
+/// Totals across every run ever played, persisted as RON in the platform's
+/// data directory.
+#[derive(Resource, Serialize, Deserialize, Clone, Default, Debug)]
+#[serde(default)]
+pub struct Stats {
+    pub games_played: u32,
+    pub flaps: u32,
+    pub pipes_passed: u32,
+    pub playtime_secs: f64,
+    /// Sum of final scores, for the average.
+    pub total_score: u64,
+    pub best_score: u32,
+    /// Gaps flown through dead centre.
+    pub perfect_passes: u32,
+}
+
+impl storage::SaveFile for Stats {
+    const VERSION: u32 = 1;
+}
+
+impl Stats {
+    pub const FILE: &'static str = "stats.ron";
+
+    pub fn load() -> Self {
+        storage::data_path(Self::FILE)
+            .and_then(|path| storage::load(&path))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self) {
+        if let Some(path) = storage::data_path(Self::FILE) {
+            let _ = storage::save(&path, self);
+        }
+    }
+
+    pub fn average_score(&self) -> f64 {
+        if self.games_played == 0 {
+            0.
+        } else {
+            self.total_score as f64 / self.games_played as f64
+        }
+    }
+}
+
+/// Totals for this session only, kept apart from [`Stats`] and never saved.
+#[derive(Resource, Default, Debug)]
+pub struct SessionStats {
+    pub runs: u32,
+    pub best_score: u32,
+    pub coins: u32,
+}
+
+fn format_playtime(secs: f64) -> String {
+    let secs = secs as u64;
+    format!("{}h {:02}m {:02}s", secs / 3600, secs / 60 % 60, secs % 60)
+}
+
+fn track_run_system(
+    time: Res<Time>,
+    flap_input: FlapInput,
+    mut passed: EventReader<ScoredEvent>,
+    mut graded: EventReader<PassGraded>,
+    mut stats: ResMut<Stats>,
+) {
+    stats.playtime_secs += time.delta_seconds_f64();
+    stats.pipes_passed += passed.iter().count() as u32;
+    stats.perfect_passes += graded
+        .iter()
+        .filter(|PassGraded(grade)| *grade == PassGrade::Perfect)
+        .count() as u32;
+    if flap_input.just_pressed() {
+        stats.flaps += 1;
+    }
+}
+
+fn finish_run_system(score: Res<Score>, mut stats: ResMut<Stats>) {
+    stats.games_played += 1;
+    stats.total_score += score.0 as u64;
+    stats.best_score = stats.best_score.max(score.0);
+    stats.save();
+}
+
+fn finish_session_run_system(
+    score: Res<Score>,
+    run_coins: Res<RunCoins>,
+    mut session: ResMut<SessionStats>,
+) {
+    session.runs += 1;
+    session.best_score = session.best_score.max(score.0);
+    session.coins += run_coins.0;
+}
+
+/// Keeps the playtime of a run that's quit midway.
+fn save_stats_on_quit_system(
+    mut exit: EventReader<AppExit>,
+    mut close_requested: EventReader<WindowCloseRequested>,
+    stats: Res<Stats>,
+) {
+    if exit.iter().count() > 0 || close_requested.iter().count() > 0 {
+        stats.save();
+    }
+}
+
+fn open_stats_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::T) {
+        next_state.set(GameState::Stats);
+    }
+}
+
+fn spawn_stats_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    stats: Res<Stats>,
+) {
+    let font = asset_server.load(FONT);
+    let style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    let rows = [
+        ("Games played", stats.games_played.to_string()),
+        ("Flaps", stats.flaps.to_string()),
+        ("Pipes passed", stats.pipes_passed.to_string()),
+        ("Perfect passes", stats.perfect_passes.to_string()),
+        ("Playtime", format_playtime(stats.playtime_secs)),
+        ("Average score", format!("{:.1}", stats.average_score())),
+        ("Best score", stats.best_score.to_string()),
+    ];
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Stats),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(12.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section("Stats", style(40.)));
+            for (label, value) in rows {
+                parent.spawn(TextBundle::from_section(
+                    format!("{label}: {value}"),
+                    style(22.),
+                ));
+            }
+            parent.spawn(TextBundle::from_section("Esc: back", style(16.)));
+        });
+}
+
+fn stats_input_system(menu_input: MenuInput, mut next_state: ResMut<NextState<GameState>>) {
+    if menu_input.back() {
+        next_state.set(GameState::Menu);
+    }
+}
+
+pub struct StatsPlugin;
+
+impl Plugin for StatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Stats::load())
+            .init_resource::<SessionStats>()
+            .add_system(track_run_system.in_set(OnUpdate(GameState::InGame)))
+            .add_system(finish_run_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(finish_run_system.in_schedule(OnEnter(GameState::LevelComplete)))
+            .add_system(finish_session_run_system.in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(finish_session_run_system.in_schedule(OnEnter(GameState::LevelComplete)))
+            .add_system(save_stats_on_quit_system.in_base_set(CoreSet::Last))
+            .add_system(open_stats_system.run_if(in_state(GameState::Menu)))
+            .add_system(spawn_stats_ui_system.in_schedule(OnEnter(GameState::Stats)))
+            .add_system(stats_input_system.in_set(OnUpdate(GameState::Stats)));
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    ffi::{c_char, c_void, CString},
+    ptr,
+};
+
+use bevy::prelude::*;
+use libloading::Library;
+
+use crate::{
+    achievements::{AchievementUnlocked, Achievements},
+    difficulty::SpeedPreset,
+    mode::endless_run,
+    score::Score,
+    settings::Settings,
+    GameState,
+};
+
+#[cfg(target_os = "windows")]
+const LIBRARY: &str = "steam_api64.dll";
+#[cfg(target_os = "macos")]
+const LIBRARY: &str = "libsteam_api.dylib";
+#[cfg(not(any(target_os = "windows", target_os = "macos")))]
+const LIBRARY: &str = "libsteam_api.so";
+
+/// Endless runs upload their score here, keeping each player's best.
+const LEADERBOARD: &str = "High Score";
+const UPLOAD_KEEP_BEST: i32 = 1;
+/// `LeaderboardFindResult_t::k_iCallback`.
+const LEADERBOARD_FIND_RESULT: i32 = 1104;
+
+/// Result of looking up a leaderboard. Steam packs its structs to 4 bytes
+/// everywhere but Windows.
+#[cfg_attr(windows, repr(C))]
+#[cfg_attr(not(windows), repr(C, packed(4)))]
+#[derive(Clone, Copy, Default)]
+struct LeaderboardFindResult {
+    leaderboard: u64,
+    found: u8,
+}
+
+/// The parts of the flat Steamworks API in use, loaded from the redistributable
+/// next to the executable so builds with the feature still start without Steam.
+/// Raw interface pointers keep it on the main thread.
+struct SteamApi {
+    _library: Library,
+    shutdown: unsafe extern "C" fn(),
+    run_callbacks: unsafe extern "C" fn(),
+    user_stats: *mut c_void,
+    utils: *mut c_void,
+    set_achievement: unsafe extern "C" fn(*mut c_void, *const c_char) -> bool,
+    store_stats: unsafe extern "C" fn(*mut c_void) -> bool,
+    find_leaderboard: unsafe extern "C" fn(*mut c_void, *const c_char) -> u64,
+    upload_leaderboard_score:
+        unsafe extern "C" fn(*mut c_void, u64, i32, i32, *const i32, i32) -> u64,
+    is_api_call_completed: unsafe extern "C" fn(*mut c_void, u64, *mut bool) -> bool,
+    get_api_call_result:
+        unsafe extern "C" fn(*mut c_void, u64, *mut c_void, i32, i32, *mut bool) -> bool,
+}
+
+/// First of `names` the library exports, as newer SDKs renamed some of them.
+///
+/// # Safety
+///
+/// `T` has to match the signature of the exported function.
+unsafe fn symbol<T: Copy>(library: &Library, names: &[&str]) -> Result<T, String> {
+    names
+        .iter()
+        .find_map(|name| library.get::<T>(name.as_bytes()).ok().map(|symbol| *symbol))
+        .ok_or_else(|| format!("{LIBRARY} has no {}", names[0]))
+}
+
+impl SteamApi {
+    /// Loads the library and connects to the running Steam client.
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialisation code.
+    unsafe fn load() -> Result<Self, String> {
+        let library = Library::new(LIBRARY).map_err(|err| err.to_string())?;
+
+        let init: unsafe extern "C" fn() -> bool = symbol(&library, &["SteamAPI_Init"])?;
+        if !init() {
+            return Err("Steam isn't running or doesn't know this app".into());
+        }
+
+        let user_stats: unsafe extern "C" fn() -> *mut c_void = symbol(
+            &library,
+            &[
+                "SteamAPI_SteamUserStats_v013",
+                "SteamAPI_SteamUserStats_v012",
+            ],
+        )?;
+        let utils: unsafe extern "C" fn() -> *mut c_void =
+            symbol(&library, &["SteamAPI_SteamUtils_v010"])?;
+
+        let api = Self {
+            shutdown: symbol(&library, &["SteamAPI_Shutdown"])?,
+            run_callbacks: symbol(&library, &["SteamAPI_RunCallbacks"])?,
+            user_stats: user_stats(),
+            utils: utils(),
+            set_achievement: symbol(&library, &["SteamAPI_ISteamUserStats_SetAchievement"])?,
+            store_stats: symbol(&library, &["SteamAPI_ISteamUserStats_StoreStats"])?,
+            find_leaderboard: symbol(&library, &["SteamAPI_ISteamUserStats_FindLeaderboard"])?,
+            upload_leaderboard_score: symbol(
+                &library,
+                &["SteamAPI_ISteamUserStats_UploadLeaderboardScore"],
+            )?,
+            is_api_call_completed: symbol(&library, &["SteamAPI_ISteamUtils_IsAPICallCompleted"])?,
+            get_api_call_result: symbol(&library, &["SteamAPI_ISteamUtils_GetAPICallResult"])?,
+            _library: library,
+        };
+
+        // Older SDKs only hand out the player's stats once asked for them.
+        if let Ok(request_current_stats) = symbol::<unsafe extern "C" fn(*mut c_void) -> bool>(
+            &api._library,
+            &["SteamAPI_ISteamUserStats_RequestCurrentStats"],
+        ) {
+            request_current_stats(api.user_stats);
+        }
+
+        Ok(api)
+    }
+
+    fn run_callbacks(&self) {
+        unsafe { (self.run_callbacks)() }
+    }
+
+    /// Marks achievements as unlocked on Steam, which shows its own popup.
+    fn unlock<'a>(&self, ids: impl IntoIterator<Item = &'a str>) {
+        for id in ids {
+            let name = CString::new(api_name(id)).unwrap();
+            if !unsafe { (self.set_achievement)(self.user_stats, name.as_ptr()) } {
+                warn!("Steam doesn't know achievement {}", api_name(id));
+            }
+        }
+        unsafe { (self.store_stats)(self.user_stats) };
+    }
+
+    fn find_leaderboard(&self, name: &str) -> u64 {
+        let name = CString::new(name).unwrap();
+        unsafe { (self.find_leaderboard)(self.user_stats, name.as_ptr()) }
+    }
+
+    /// The leaderboard handle once the lookup has finished, `Some(None)` if
+    /// there's no such leaderboard.
+    fn leaderboard_found(&self, call: u64) -> Option<Option<u64>> {
+        let mut failed = false;
+        if !unsafe { (self.is_api_call_completed)(self.utils, call, &mut failed) } {
+            return None;
+        }
+
+        let mut result = LeaderboardFindResult::default();
+        let ok = unsafe {
+            (self.get_api_call_result)(
+                self.utils,
+                call,
+                ptr::addr_of_mut!(result).cast(),
+                std::mem::size_of::<LeaderboardFindResult>() as i32,
+                LEADERBOARD_FIND_RESULT,
+                &mut failed,
+            )
+        };
+        Some((ok && !failed && result.found != 0).then_some(result.leaderboard))
+    }
+
+    /// Uploads `score` with the speed it was set at as its one detail, for
+    /// showing alongside it.
+    fn upload_score(&self, leaderboard: u64, score: u32, speed: SpeedPreset) {
+        let details = [speed_detail(speed)];
+        unsafe {
+            (self.upload_leaderboard_score)(
+                self.user_stats,
+                leaderboard,
+                UPLOAD_KEEP_BEST,
+                score.min(i32::MAX as u32) as i32,
+                details.as_ptr(),
+                details.len() as i32,
+            )
+        };
+    }
+}
+
+impl Drop for SteamApi {
+    fn drop(&mut self) {
+        unsafe { (self.shutdown)() }
+    }
+}
+
+/// Steamworks achievement API names are conventionally upper snake case, so
+/// `score-10` is set up as `SCORE_10`.
+fn api_name(id: &str) -> String {
+    id.to_uppercase().replace('-', "_")
+}
+
+/// Leaderboard score details are plain integers.
+fn speed_detail(speed: SpeedPreset) -> i32 {
+    match speed {
+        SpeedPreset::Chill => 0,
+        SpeedPreset::Classic => 1,
+        SpeedPreset::Turbo => 2,
+    }
+}
+
+/// The high score leaderboard, which takes a while to look up.
+#[derive(Resource)]
+enum Leaderboard {
+    Finding {
+        call: u64,
+        /// Best score finished while the lookup was still running, and the
+        /// speed it was set at.
+        pending: Option<(u32, SpeedPreset)>,
+    },
+    Found(u64),
+    Missing,
+}
+
+/// Catches Steam up on achievements unlocked while playing without it.
+fn sync_achievements_system(steam: NonSend<SteamApi>, achievements: Res<Achievements>) {
+    steam.unlock(achievements.unlocked.iter().map(String::as_str));
+}
+
+fn run_callbacks_system(steam: NonSend<SteamApi>, mut leaderboard: ResMut<Leaderboard>) {
+    steam.run_callbacks();
+
+    let Leaderboard::Finding { call, pending } = *leaderboard else {
+        return;
+    };
+    match steam.leaderboard_found(call) {
+        Some(Some(handle)) => {
+            if let Some((score, speed)) = pending {
+                steam.upload_score(handle, score, speed);
+            }
+            *leaderboard = Leaderboard::Found(handle);
+        }
+        Some(None) => {
+            warn!("Steam has no leaderboard called {LEADERBOARD}");
+            *leaderboard = Leaderboard::Missing;
+        }
+        None => {}
+    }
+}
+
+fn unlock_achievements_system(
+    steam: NonSend<SteamApi>,
+    mut unlocked: EventReader<AchievementUnlocked>,
+) {
+    let ids = unlocked
+        .iter()
+        .map(|AchievementUnlocked(achievement)| achievement.id)
+        .collect::<Vec<_>>();
+    if !ids.is_empty() {
+        steam.unlock(ids);
+    }
+}
+
+fn upload_score_system(
+    steam: NonSend<SteamApi>,
+    score: Res<Score>,
+    settings: Res<Settings>,
+    mut leaderboard: ResMut<Leaderboard>,
+) {
+    let run = (score.0, settings.speed);
+    match leaderboard.as_mut() {
+        Leaderboard::Finding { pending, .. } => {
+            *pending = Some(pending.map_or(
+                run,
+                |pending| {
+                    if pending.0 >= run.0 {
+                        pending
+                    } else {
+                        run
+                    }
+                },
+            ));
+        }
+        Leaderboard::Found(handle) => steam.upload_score(*handle, score.0, settings.speed),
+        Leaderboard::Missing => {}
+    }
+}
+
+pub struct SteamPlugin;
+
+impl Plugin for SteamPlugin {
+    fn build(&self, app: &mut App) {
+        let steam = match unsafe { SteamApi::load() } {
+            Ok(steam) => steam,
+            Err(err) => {
+                warn!("Playing without Steam: {err}");
+                return;
+            }
+        };
+
+        app.insert_resource(Leaderboard::Finding {
+            call: steam.find_leaderboard(LEADERBOARD),
+            pending: None,
+        })
+        .insert_non_send_resource(steam)
+        .add_startup_system(sync_achievements_system)
+        .add_system(run_callbacks_system)
+        .add_system(unlock_achievements_system)
+        .add_system(
+            upload_score_system
+                .run_if(endless_run)
+                .in_schedule(OnEnter(GameState::GameOver)),
+        );
+    }
+}
@@ -0,0 +1,200 @@
+use std::{
+    ffi::OsString,
+    fs::{self, File},
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+};
+
+use bevy::prelude::*;
+use directories::ProjectDirs;
+use serde::{de::DeserializeOwned, Serialize};
+
+/// Subdirectory of `profiles/` that config and data files are read from, `None`
+/// for the default profile, which keeps its files at the top level.
+static PROFILE: RwLock<Option<String>> = RwLock::new(None);
+
+/// Whether files are read and written at all. Off until [`enable`] is called,
+/// so an app that doesn't persist anything, like a headless test, leaves the
+/// player's own files alone.
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Replaces the platform's config directory, `None` to use it.
+static CONFIG_DIR: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+/// Files saved by a newer version of the game, which are never written over.
+static NEWER_FILES: RwLock<Vec<PathBuf>> = RwLock::new(Vec::new());
+
+/// Starts the first line of every saved file. RON takes it for a comment, so
+/// files stay readable by versions from before it was added.
+const VERSION_PREFIX: &str = "// version: ";
+
+/// Something saved as a RON file with [`save`] and read back with [`load`].
+pub trait SaveFile: Serialize + DeserializeOwned {
+    /// Layout version written into the file. Bumped whenever older files need
+    /// [`migrate`](SaveFile::migrate) to be brought up to date, rather than
+    /// just missing fields filled in by `#[serde(default)]`. Files from before
+    /// versions were written count as version 0.
+    const VERSION: u32;
+
+    /// Fixes up a value read from a file saved at an older `version`.
+    fn migrate(&mut self, version: u32) {
+        let _ = version;
+    }
+}
+
+fn project_dirs() -> Option<ProjectDirs> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    ProjectDirs::from("", "", "bevy-flappybird")
+}
+
+/// Starts handing out paths to read and write, with every later
+/// [`config_path`] under `config_dir` instead of the platform's config
+/// directory if given.
+pub fn enable(config_dir: Option<PathBuf>) {
+    *CONFIG_DIR.write().unwrap() = config_dir;
+    ENABLED.store(true, Ordering::Relaxed);
+}
+
+/// Switches every later [`config_path`] and [`data_path`] to another profile.
+pub fn set_profile(profile: Option<String>) {
+    *PROFILE.write().unwrap() = profile;
+}
+
+/// Where a file of the active profile lives relative to the config or data
+/// directory.
+pub fn profile_relative_path(file: &str) -> PathBuf {
+    match PROFILE.read().unwrap().as_deref() {
+        Some(profile) => Path::new("profiles").join(profile).join(file),
+        None => PathBuf::from(file),
+    }
+}
+
+/// Location of a file in the platform's config directory, for the active profile.
+pub fn config_path(file: &str) -> Option<PathBuf> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    let dir = match CONFIG_DIR.read().unwrap().clone() {
+        Some(dir) => dir,
+        None => project_dirs()?.config_dir().to_path_buf(),
+    };
+    Some(dir.join(profile_relative_path(file)))
+}
+
+/// Location of a file in the platform's data directory, for the active profile.
+pub fn data_path(file: &str) -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.data_dir().join(profile_relative_path(file)))
+}
+
+/// Location of a file in the platform's data directory shared by all profiles.
+pub fn shared_data_path(file: &str) -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.data_dir().join(file))
+}
+
+/// `path` with `extension` added on after its own.
+fn with_added_extension(path: &Path, extension: &str) -> PathBuf {
+    let mut path = OsString::from(path);
+    path.push(".");
+    path.push(extension);
+    path.into()
+}
+
+fn file_version(contents: &str) -> u32 {
+    contents
+        .lines()
+        .next()
+        .and_then(|line| line.strip_prefix(VERSION_PREFIX))
+        .and_then(|version| version.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+/// Reads a RON file, migrating it from an older version if need be. Returns
+/// `None` if it's missing, can't be parsed, or was saved by a newer version of
+/// the game. Files that can't be parsed are moved aside rather than being
+/// written over by the next save, and newer ones are left as they are.
+pub fn load<T: SaveFile>(path: &Path) -> Option<T> {
+    let contents = fs::read_to_string(path).ok()?;
+
+    let version = file_version(&contents);
+    if version > T::VERSION {
+        warn!(
+            "Not using {}, which was saved by a newer version of the game",
+            path.display()
+        );
+        NEWER_FILES.write().unwrap().push(path.to_path_buf());
+        return None;
+    }
+
+    match ron::from_str::<T>(&contents) {
+        Ok(mut value) => {
+            if version < T::VERSION {
+                value.migrate(version);
+            }
+            Some(value)
+        }
+        Err(err) => {
+            let aside = with_added_extension(path, "invalid");
+            warn!(
+                "Moving invalid file {} to {}: {err}",
+                path.display(),
+                aside.display()
+            );
+            if let Err(err) = fs::rename(path, &aside) {
+                error!("Failed to move {}: {err}", path.display());
+            }
+            None
+        }
+    }
+}
+
+/// Writes `value` as RON. Failures are logged as well as returned, so callers
+/// with nowhere to show them can go on, since losing a save shouldn't take the
+/// game down. Goes through a temporary file that replaces the old one once
+/// fully written, so a crash midway leaves the old one intact.
+pub fn save<T: SaveFile>(path: &Path, value: &T) -> Result<(), String> {
+    if NEWER_FILES
+        .read()
+        .unwrap()
+        .iter()
+        .any(|newer| newer == path)
+    {
+        warn!(
+            "Not saving over {}, which was saved by a newer version of the game",
+            path.display()
+        );
+        return Err("it was saved by a newer version of the game".into());
+    }
+
+    let result = ron::ser::to_string_pretty(value, Default::default())
+        .map_err(|err| err.to_string())
+        .and_then(|contents| {
+            if let Some(dir) = path.parent() {
+                fs::create_dir_all(dir).map_err(|err| err.to_string())?;
+            }
+            let temp = with_added_extension(path, "tmp");
+            let mut file = File::create(&temp).map_err(|err| err.to_string())?;
+            write!(file, "{VERSION_PREFIX}{}\n{contents}", T::VERSION)
+                .and_then(|_| file.sync_all())
+                .and_then(|_| fs::rename(&temp, path))
+                .map_err(|err| err.to_string())
+        });
+
+    if let Err(err) = &result {
+        error!("Failed to save {}: {err}", path.display());
+    }
+    result
+}
+
+pub fn remove(path: &Path) {
+    if let Err(err) = fs::remove_file(path) {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            error!("Failed to remove {}: {err}", path.display());
+        }
+    }
+}
@@ -0,0 +1,157 @@
+use std::{f32::consts::TAU, sync::Arc, time::Duration};
+
+use bevy::{
+    audio::{Decodable, Source},
+    reflect::TypeUuid,
+};
+
+const SAMPLE_RATE: u32 = 44_100;
+/// Every note fades in and out over this long, which keeps it from clicking.
+const FADE_SECS: f32 = 0.005;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Wave {
+    Sine,
+    Square,
+    Triangle,
+    /// Deterministic white noise, for hits and percussion.
+    Noise,
+}
+
+impl Wave {
+    /// Value at `phase`, in cycles.
+    fn sample(self, phase: f32) -> f32 {
+        let phase = phase.fract();
+        match self {
+            Wave::Sine => (phase * TAU).sin(),
+            Wave::Square if phase < 0.5 => 1.,
+            Wave::Square => -1.,
+            Wave::Triangle => 1. - 4. * (phase - 0.5).abs(),
+            Wave::Noise => {
+                // Hashing the phase keeps the noise the same every time it's played.
+                let bits = (phase * 1_000_000.) as u32;
+                let hash = bits.wrapping_mul(0x9E37_79B9).rotate_left(13) ^ 0x5bd1_e995;
+                hash as f32 / u32::MAX as f32 * 2. - 1.
+            }
+        }
+    }
+}
+
+/// A note sliding from one pitch to another, or a rest if `volume` is zero.
+#[derive(Clone, Copy, Debug)]
+pub struct Note {
+    pub wave: Wave,
+    pub from_hz: f32,
+    pub to_hz: f32,
+    pub secs: f32,
+    pub volume: f32,
+}
+
+impl Note {
+    pub const fn new(wave: Wave, hz: f32, secs: f32, volume: f32) -> Self {
+        Self {
+            wave,
+            from_hz: hz,
+            to_hz: hz,
+            secs,
+            volume,
+        }
+    }
+
+    pub const fn slide(wave: Wave, from_hz: f32, to_hz: f32, secs: f32, volume: f32) -> Self {
+        Self {
+            wave,
+            from_hz,
+            to_hz,
+            secs,
+            volume,
+        }
+    }
+
+    pub const fn rest(secs: f32) -> Self {
+        Self::new(Wave::Sine, 0., secs, 0.)
+    }
+}
+
+/// A sound synthesised from a sequence of notes, so the game needs no audio
+/// files.
+#[derive(TypeUuid, Clone, Debug)]
+#[uuid = "8c14f735-1646-4b49-84e7-0bafe27d8462"]
+pub struct Tone {
+    notes: Arc<[Note]>,
+}
+
+impl Tone {
+    pub fn new(notes: &[Note]) -> Self {
+        Self {
+            notes: notes.into(),
+        }
+    }
+}
+
+impl Decodable for Tone {
+    type DecoderItem = f32;
+    type Decoder = ToneDecoder;
+
+    fn decoder(&self) -> Self::Decoder {
+        ToneDecoder {
+            notes: self.notes.clone(),
+            note: 0,
+            sample: 0,
+            phase: 0.,
+        }
+    }
+}
+
+pub struct ToneDecoder {
+    notes: Arc<[Note]>,
+    note: usize,
+    /// Sample within the current note.
+    sample: u32,
+    /// Position within the current wave cycle.
+    phase: f32,
+}
+
+impl Iterator for ToneDecoder {
+    type Item = f32;
+
+    fn next(&mut self) -> Option<f32> {
+        let note = loop {
+            let note = self.notes.get(self.note)?;
+            if (self.sample as f32) < note.secs * SAMPLE_RATE as f32 {
+                break note;
+            }
+            self.note += 1;
+            self.sample = 0;
+        };
+
+        let t = self.sample as f32 / SAMPLE_RATE as f32;
+        let hz = note.from_hz + (note.to_hz - note.from_hz) * (t / note.secs);
+        let envelope = (t / FADE_SECS).min((note.secs - t) / FADE_SECS).min(1.);
+        let value = note.wave.sample(self.phase) * note.volume * envelope;
+
+        self.phase = (self.phase + hz / SAMPLE_RATE as f32).fract();
+        self.sample += 1;
+        Some(value)
+    }
+}
+
+impl Source for ToneDecoder {
+    fn current_frame_len(&self) -> Option<usize> {
+        None
+    }
+
+    fn channels(&self) -> u16 {
+        1
+    }
+
+    fn sample_rate(&self) -> u32 {
+        SAMPLE_RATE
+    }
+
+    fn total_duration(&self) -> Option<Duration> {
+        Some(Duration::from_secs_f32(
+            self.notes.iter().map(|note| note.secs).sum(),
+        ))
+    }
+}
@@ -0,0 +1,148 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    sync::{Arc, RwLock},
+};
+
+use bevy::{
+    asset::{AssetIo, AssetIoError, FileAssetIo, FileType, Metadata},
+    prelude::*,
+    utils::BoxedFuture,
+};
+
+use crate::{resolution::WorldScale, settings::Settings};
+
+/// Sprites a texture pack may replace. A pack is a folder in `mods/` containing
+/// any subset of these files, and of their `@2x` and `@3x` variants.
+const PACK_TEXTURES: &[&str] = &["bird.png", "pipe.png", "floor.png", "bg.png"];
+
+fn mods_dir() -> PathBuf {
+    FileAssetIo::get_base_path().join("mods")
+}
+
+/// Name of the pack currently used to resolve asset paths, shared between the
+/// asset I/O (which lives inside the asset server) and the ECS world.
+#[derive(Clone, Default)]
+struct ActivePack(Arc<RwLock<Option<String>>>);
+
+impl ActivePack {
+    fn get(&self) -> Option<String> {
+        self.0.read().unwrap().clone()
+    }
+
+    fn set(&self, pack: Option<String>) {
+        *self.0.write().unwrap() = pack;
+    }
+}
+
+/// Looks up files in the active pack first and falls back to the wrapped
+/// asset I/O for everything the pack doesn't provide.
+struct TexturePackAssetIo {
+    fallback: Box<dyn AssetIo>,
+    active: ActivePack,
+}
+
+impl TexturePackAssetIo {
+    fn pack_path(&self, path: &Path) -> Option<PathBuf> {
+        let pack = self.active.get()?;
+        Some(mods_dir().join(pack).join(path)).filter(|path| path.is_file())
+    }
+}
+
+impl AssetIo for TexturePackAssetIo {
+    fn load_path<'a>(&'a self, path: &'a Path) -> BoxedFuture<'a, Result<Vec<u8>, AssetIoError>> {
+        match self.pack_path(path) {
+            Some(pack_path) => Box::pin(async move { Ok(fs::read(pack_path)?) }),
+            None => self.fallback.load_path(path),
+        }
+    }
+
+    fn read_directory(
+        &self,
+        path: &Path,
+    ) -> Result<Box<dyn Iterator<Item = PathBuf>>, AssetIoError> {
+        self.fallback.read_directory(path)
+    }
+
+    fn get_metadata(&self, path: &Path) -> Result<Metadata, AssetIoError> {
+        match self.pack_path(path) {
+            Some(_) => Ok(Metadata::new(FileType::File)),
+            None => self.fallback.get_metadata(path),
+        }
+    }
+
+    fn watch_path_for_changes(
+        &self,
+        to_watch: &Path,
+        to_reload: Option<PathBuf>,
+    ) -> Result<(), AssetIoError> {
+        self.fallback.watch_path_for_changes(to_watch, to_reload)
+    }
+
+    fn watch_for_changes(&self) -> Result<(), AssetIoError> {
+        self.fallback.watch_for_changes()
+    }
+}
+
+/// The texture packs found in `mods/` at startup.
+#[derive(Resource)]
+pub struct TexturePacks {
+    pub available: Vec<String>,
+    active: ActivePack,
+}
+
+impl TexturePacks {
+    fn scan(active: ActivePack) -> Self {
+        let mut available: Vec<String> = fs::read_dir(mods_dir())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .filter(|entry| entry.path().is_dir())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .collect();
+        available.sort();
+
+        Self { available, active }
+    }
+}
+
+/// Makes the asset server resolve paths through the texture pack selected in
+/// [`Settings`]. Has to be added before bevy's `AssetPlugin`, which otherwise
+/// installs the default asset I/O.
+pub struct TexturePacksPlugin {
+    pub initial_pack: Option<String>,
+}
+
+impl Plugin for TexturePacksPlugin {
+    fn build(&self, app: &mut App) {
+        #[cfg(feature = "embedded-assets")]
+        let fallback: Box<dyn AssetIo> = Box::new(crate::embedded_assets::EmbeddedAssetIo);
+        #[cfg(not(feature = "embedded-assets"))]
+        let fallback = AssetPlugin::default().create_platform_default_asset_io();
+
+        let active = ActivePack::default();
+        active.set(self.initial_pack.clone());
+
+        app.insert_resource(TexturePacks::scan(active.clone()))
+            .insert_resource(AssetServer::new(TexturePackAssetIo { fallback, active }))
+            .add_system(switch_texture_pack_system);
+    }
+}
+
+/// Reloads the variants in the current set. The set is picked again after, in
+/// case the new pack has a different one.
+fn switch_texture_pack_system(
+    settings: Res<Settings>,
+    packs: Res<TexturePacks>,
+    asset_server: Res<AssetServer>,
+    world_scale: Res<WorldScale>,
+) {
+    if !settings.is_changed() || packs.active.get() == settings.texture_pack {
+        return;
+    }
+
+    packs.active.set(settings.texture_pack.clone());
+    for texture in PACK_TEXTURES {
+        asset_server.reload_asset(world_scale.path(texture));
+    }
+}
@@ -0,0 +1,270 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+use crate::{
+    death::diagram_rect, pause::not_paused, score::ScoredEvent, tournament::Tournament,
+    transition::transition_done, DespawnOnExit, FlapEvent, GameState, GameplaySet, Player,
+    PlayerDiedEvent, Velocity, CEILING_Y, FLOOR_HEIGHT, FONT, WINDOW_HEIGHT,
+};
+
+/// Only the end of longer runs is kept, which is where they went wrong.
+const MAX_SECS: f32 = 60.;
+/// Narrow enough to leave room for the crash panel on the other side.
+const GRAPH_SIZE: Vec2 = Vec2::new(170., 90.);
+/// The height line is drawn as dots this big, one per column of the graph.
+const DOT_SIZE: f32 = 2.;
+const DEATH_SIZE: f32 = 8.;
+const FLAP_TICK: f32 = 4.;
+const RISING_COLOR: Color = Color::rgb(0.6, 1., 0.6);
+const SCORE_COLOR: Color = Color::rgba(1., 0.85, 0.2, 0.5);
+const DEATH_COLOR: Color = Color::rgb(1., 0.25, 0.25);
+
+/// Where the bird was on one fixed tick.
+struct Sample {
+    time: f32,
+    y: f32,
+    velocity: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum MarkKind {
+    Flap,
+    Score,
+    Death,
+}
+
+/// Something that happened during the run, and where the bird was at the time.
+struct Mark {
+    kind: MarkKind,
+    time: f32,
+    y: f32,
+}
+
+/// The last [`MAX_SECS`] of the current run. Times are seconds of play since it
+/// started, leaving out time spent paused.
+#[derive(Resource, Default)]
+struct Timeline {
+    time: f32,
+    samples: VecDeque<Sample>,
+    marks: VecDeque<Mark>,
+}
+
+impl Timeline {
+    fn push(&mut self, sample: Sample) {
+        self.samples.push_back(sample);
+        let Some(cutoff) = self.samples.back().map(|last| last.time - MAX_SECS) else {
+            return;
+        };
+        while self
+            .samples
+            .front()
+            .is_some_and(|first| first.time < cutoff)
+        {
+            self.samples.pop_front();
+        }
+        while self.marks.front().is_some_and(|first| first.time < cutoff) {
+            self.marks.pop_front();
+        }
+    }
+
+    /// The sample closest to `time`, from just after it.
+    fn sample_at(&self, time: f32) -> Option<&Sample> {
+        let index = self.samples.partition_point(|sample| sample.time < time);
+        self.samples.get(index).or(self.samples.back())
+    }
+}
+
+fn clear_timeline_system(mut timeline: ResMut<Timeline>) {
+    *timeline = Timeline::default();
+}
+
+fn record_sample_system(
+    fixed_time: Res<FixedTime>,
+    mut timeline: ResMut<Timeline>,
+    player: Query<(&Transform, &Velocity), With<Player>>,
+) {
+    let Ok((transform, velocity)) = player.get_single() else {
+        return;
+    };
+    timeline.time += fixed_time.period.as_secs_f32();
+    let time = timeline.time;
+    timeline.push(Sample {
+        time,
+        y: transform.translation.y,
+        velocity: velocity.y,
+    });
+}
+
+fn record_marks_system(
+    mut timeline: ResMut<Timeline>,
+    mut flapped: EventReader<FlapEvent>,
+    mut scored: EventReader<ScoredEvent>,
+    mut died: EventReader<PlayerDiedEvent>,
+    player: Query<&Transform, With<Player>>,
+) {
+    let time = timeline.time;
+    let y = player
+        .get_single()
+        .map_or(0., |player| player.translation.y);
+    let marks = flapped
+        .iter()
+        .map(|_| (MarkKind::Flap, y))
+        .chain(scored.iter().map(|_| (MarkKind::Score, y)))
+        .chain(
+            died.iter()
+                .map(|PlayerDiedEvent(crash)| (MarkKind::Death, crash.player.y)),
+        );
+    timeline
+        .marks
+        .extend(marks.map(|(kind, y)| Mark { kind, time, y }));
+}
+
+/// A graph of the bird's height over the end of the run, with its flaps along
+/// the bottom, the points it scored and where it crashed, across from the crash
+/// panel.
+fn spawn_analysis_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    timeline: Res<Timeline>,
+) {
+    let (Some(first), Some(last)) = (timeline.samples.front(), timeline.samples.back()) else {
+        return;
+    };
+    let span = last.time - first.time;
+    if span <= 0. {
+        return;
+    }
+
+    let floor = -WINDOW_HEIGHT / 2. + FLOOR_HEIGHT;
+    let to_graph = |time: f32, y: f32| {
+        let height = ((y - floor) / (CEILING_Y - floor)).clamp(0., 1.);
+        Vec2::new(
+            (time - first.time) / span * GRAPH_SIZE.x,
+            (1. - height) * GRAPH_SIZE.y,
+        )
+    };
+    let font = asset_server.load(FONT);
+    let style = |font_size| TextStyle {
+        font: font.clone(),
+        font_size,
+        color: Color::WHITE,
+    };
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::GameOver),
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        top: Val::Px(70.),
+                        left: Val::Px(10.),
+                        ..Default::default()
+                    },
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(6.)),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!("Height over the last {span:.0} s"),
+                style(14.),
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(GRAPH_SIZE.x), Val::Px(GRAPH_SIZE.y)),
+                        overflow: Overflow::Hidden,
+                        ..Default::default()
+                    },
+                    background_color: Color::rgba(0., 0., 0., 0.5).into(),
+                    ..Default::default()
+                })
+                .with_children(|graph| {
+                    for mark in timeline.marks.iter() {
+                        let point = to_graph(mark.time, mark.y);
+                        match mark.kind {
+                            MarkKind::Flap => graph.spawn(diagram_rect(
+                                Vec2::new(point.x, GRAPH_SIZE.y - FLAP_TICK),
+                                Vec2::new(1., FLAP_TICK),
+                                Color::WHITE,
+                            )),
+                            MarkKind::Score => graph.spawn(diagram_rect(
+                                Vec2::new(point.x, 0.),
+                                Vec2::new(1., GRAPH_SIZE.y),
+                                SCORE_COLOR,
+                            )),
+                            MarkKind::Death => continue,
+                        };
+                    }
+
+                    let columns = (GRAPH_SIZE.x / DOT_SIZE) as usize;
+                    for column in 0..columns {
+                        let time = first.time + (column as f32 + 0.5) / columns as f32 * span;
+                        let Some(sample) = timeline.sample_at(time) else {
+                            continue;
+                        };
+                        let color = if sample.velocity > 0. {
+                            RISING_COLOR
+                        } else {
+                            Color::WHITE
+                        };
+                        let point = to_graph(sample.time, sample.y);
+                        graph.spawn(diagram_rect(
+                            point - Vec2::splat(DOT_SIZE / 2.),
+                            Vec2::splat(DOT_SIZE),
+                            color,
+                        ));
+                    }
+
+                    let deaths = timeline
+                        .marks
+                        .iter()
+                        .filter(|mark| mark.kind == MarkKind::Death);
+                    for mark in deaths {
+                        graph.spawn(diagram_rect(
+                            to_graph(mark.time, mark.y) - Vec2::splat(DEATH_SIZE / 2.),
+                            Vec2::splat(DEATH_SIZE),
+                            DEATH_COLOR,
+                        ));
+                    }
+                });
+            parent.spawn(TextBundle::from_section(
+                "Green: rising  Gold: points\nTicks: flaps  Red: crash",
+                style(12.),
+            ));
+        });
+}
+
+/// Records where the bird was on every fixed tick of a run, and graphs the end
+/// of it on the game over screen to show where it went wrong.
+pub struct TimelinePlugin;
+
+impl Plugin for TimelinePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Timeline>()
+            .add_system(clear_timeline_system.in_schedule(OnEnter(GameState::InGame)))
+            .add_system(
+                record_sample_system
+                    .in_schedule(CoreSchedule::FixedUpdate)
+                    .run_if(in_state(GameState::InGame))
+                    .run_if(not_paused)
+                    .run_if(transition_done),
+            )
+            .add_system(
+                record_marks_system
+                    .in_set(GameplaySet::Presentation)
+                    .in_set(OnUpdate(GameState::InGame)),
+            )
+            .add_system(
+                spawn_analysis_system
+                    .run_if(not(resource_exists::<Tournament>()))
+                    .in_schedule(OnEnter(GameState::GameOver)),
+            );
+    }
+}
@@ -0,0 +1,91 @@
+use bevy::{prelude::*, window::PrimaryWindow, winit::WinitWindows};
+use flappy_core::{score::Score, settings::Settings, stats::Stats};
+use winit::window::Icon;
+
+pub const TITLE: &str = "Flappy Bird";
+/// The title isn't changed more often than this, so the score doesn't have
+/// window managers and streaming tools redrawing it every frame.
+const TITLE_INTERVAL_SECS: f32 = 1.;
+
+#[derive(Resource)]
+struct WindowIcon(Handle<Image>);
+
+#[derive(Resource)]
+struct TitleTimer(Timer);
+
+impl Default for TitleTimer {
+    fn default() -> Self {
+        Self(Timer::from_seconds(
+            TITLE_INTERVAL_SECS,
+            TimerMode::Repeating,
+        ))
+    }
+}
+
+fn load_icon_system(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(WindowIcon(asset_server.load("icon.png")));
+}
+
+/// Hands the icon to the window once it has loaded. Bevy doesn't have a way to
+/// set one yet, so this goes through winit.
+fn set_icon_system(
+    mut commands: Commands,
+    icon: Res<WindowIcon>,
+    images: Res<Assets<Image>>,
+    winit_windows: NonSend<WinitWindows>,
+    windows: Query<Entity, With<PrimaryWindow>>,
+) {
+    let (Some(image), Ok(entity)) = (images.get(&icon.0), windows.get_single()) else {
+        return;
+    };
+    let Some(window) = winit_windows.get_window(entity) else {
+        return;
+    };
+
+    let size = image.texture_descriptor.size;
+    match Icon::from_rgba(image.data.clone(), size.width, size.height) {
+        Ok(icon) => window.set_window_icon(Some(icon)),
+        Err(err) => warn!("Failed to set the window icon: {err}"),
+    }
+    commands.remove_resource::<WindowIcon>();
+}
+
+/// Mirrors the score and high score into the title while that's switched on
+/// in the settings, and puts the plain title back once it's off.
+fn update_title_system(
+    time: Res<Time>,
+    settings: Res<Settings>,
+    score: Res<Score>,
+    stats: Res<Stats>,
+    mut timer: ResMut<TitleTimer>,
+    mut windows: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    if !timer.0.tick(time.raw_delta()).just_finished() && !settings.is_changed() {
+        return;
+    }
+
+    let title = if settings.score_in_title {
+        let best = stats.best_score.max(score.0);
+        format!("{TITLE} – {} (best {best})", score.0)
+    } else {
+        TITLE.into()
+    };
+    for mut window in windows.iter_mut() {
+        if window.title != title {
+            window.title = title.clone();
+        }
+    }
+}
+
+/// The window's icon, and its title with the score in it for streaming or
+/// playing windowed.
+pub struct TitleBarPlugin;
+
+impl Plugin for TitleBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TitleTimer>()
+            .add_startup_system(load_icon_system)
+            .add_system(set_icon_system.run_if(resource_exists::<WindowIcon>()))
+            .add_system(update_title_system);
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::{
+    ecs::system::SystemParam,
+    input::touch::{Touch, Touches},
+    prelude::*,
+};
+
+use crate::pause::on_pause_button;
+
+/// Space around the edges of the screen that notches, rounded corners and
+/// gesture bars may cover, in logical pixels. The HUD stays inside it.
+///
+/// Winit doesn't report the real insets, so phones get room for the usual
+/// status bar and home indicator, and desktops none.
+#[derive(Resource, Clone, Copy, Debug)]
+pub struct SafeArea {
+    pub top: f32,
+    pub bottom: f32,
+    pub left: f32,
+    pub right: f32,
+}
+
+impl Default for SafeArea {
+    fn default() -> Self {
+        if cfg!(any(target_os = "android", target_os = "ios")) {
+            Self {
+                top: 44.,
+                bottom: 34.,
+                left: 0.,
+                right: 0.,
+            }
+        } else {
+            Self {
+                top: 0.,
+                bottom: 0.,
+                left: 0.,
+                right: 0.,
+            }
+        }
+    }
+}
+
+/// Space on a keyboard, South on a gamepad, or a tap anywhere on a touch
+/// screen that doesn't hit the pause button.
+#[derive(SystemParam)]
+pub struct FlapInput<'w> {
+    keyboard_input: Res<'w, Input<KeyCode>>,
+    gamepads: Res<'w, Gamepads>,
+    gamepad_buttons: Res<'w, Input<GamepadButton>>,
+    touches: Res<'w, Touches>,
+    safe_area: Res<'w, SafeArea>,
+    ui_scale: Res<'w, UiScale>,
+}
+
+impl FlapInput<'_> {
+    pub fn just_pressed(&self) -> bool {
+        self.keyboard_input.just_pressed(KeyCode::Space)
+            || self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons
+                    .just_pressed(GamepadButton::new(gamepad, GamepadButtonType::South))
+            })
+            || self
+                .touches
+                .iter_just_pressed()
+                .any(|touch| self.flaps(touch))
+    }
+
+    /// Whether flap input was held last frame and let go of this one.
+    pub fn just_released(&self) -> bool {
+        self.keyboard_input.just_released(KeyCode::Space)
+            || self.gamepads.iter().any(|gamepad| {
+                self.gamepad_buttons
+                    .just_released(GamepadButton::new(gamepad, GamepadButtonType::South))
+            })
+            || self
+                .touches
+                .iter_just_released()
+                .any(|touch| self.flaps(touch))
+    }
+
+    fn flaps(&self, touch: &Touch) -> bool {
+        !on_pause_button(
+            touch.position(),
+            &self.safe_area,
+            self.ui_scale.scale as f32,
+        )
+    }
+}
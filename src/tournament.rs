@@ -0,0 +1,512 @@
+use bevy::{prelude::*, window::ReceivedCharacter};
+
+use crate::{mode::endless_run, score::Score, DespawnOnExit, GameState, FONT};
+
+const MIN_PLAYERS: usize = 2;
+const MAX_PLAYERS: usize = 4;
+const MAX_NAME_LEN: usize = 12;
+const RUNS_RANGE: (u32, u32) = (1, 5);
+/// Podium steps for first, second and third place.
+const PODIUM: [(f32, Color); 3] = [
+    (160., Color::rgb(1., 0.8, 0.2)),
+    (110., Color::rgb(0.75, 0.75, 0.8)),
+    (70., Color::rgb(0.8, 0.5, 0.25)),
+];
+
+/// Names being entered on the tournament screen.
+#[derive(Resource)]
+struct TournamentSetup {
+    names: Vec<String>,
+    typing: String,
+    runs: u32,
+}
+
+impl Default for TournamentSetup {
+    fn default() -> Self {
+        Self {
+            names: Vec::new(),
+            typing: String::new(),
+            runs: 3,
+        }
+    }
+}
+
+struct Entrant {
+    name: String,
+    scores: Vec<u32>,
+}
+
+impl Entrant {
+    fn best(&self) -> u32 {
+        self.scores.iter().copied().max().unwrap_or(0)
+    }
+
+    fn total(&self) -> u32 {
+        self.scores.iter().sum()
+    }
+}
+
+/// A hot-seat tournament in progress, where players take turns at the keyboard
+/// until each has had `runs` runs. Ranked by best run, then by total.
+#[derive(Resource)]
+pub struct Tournament {
+    entrants: Vec<Entrant>,
+    runs: u32,
+}
+
+impl Tournament {
+    fn turn(&self) -> usize {
+        self.entrants
+            .iter()
+            .map(|entrant| entrant.scores.len())
+            .sum()
+    }
+
+    fn finished(&self) -> bool {
+        self.turn() >= self.entrants.len() * self.runs as usize
+    }
+
+    fn current(&self) -> &Entrant {
+        &self.entrants[self.turn() % self.entrants.len()]
+    }
+
+    /// Which of their runs the current player is on, counting from 1.
+    fn current_run(&self) -> usize {
+        self.turn() / self.entrants.len() + 1
+    }
+
+    fn record(&mut self, score: u32) {
+        let index = self.turn() % self.entrants.len();
+        self.entrants[index].scores.push(score);
+    }
+
+    fn standings(&self) -> Vec<&Entrant> {
+        let mut standings = self.entrants.iter().collect::<Vec<_>>();
+        standings.sort_by_key(|entrant| std::cmp::Reverse((entrant.best(), entrant.total())));
+        standings
+    }
+
+    fn describe_turn(&self) -> String {
+        format!(
+            "{}'s turn, run {}/{}",
+            self.current().name,
+            self.current_run(),
+            self.runs
+        )
+    }
+}
+
+#[derive(Component)]
+struct SetupText;
+
+/// The tournament line on the menu, which goes away if the tournament is quit.
+#[derive(Component)]
+struct TournamentLabel;
+
+fn text_style(asset_server: &AssetServer, font_size: f32) -> TextStyle {
+    TextStyle {
+        font: asset_server.load(FONT),
+        font_size,
+        color: Color::WHITE,
+    }
+}
+
+fn open_tournament_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::H) {
+        next_state.set(GameState::TournamentSetup);
+    }
+}
+
+fn spawn_setup_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut characters: ResMut<Events<ReceivedCharacter>>,
+) {
+    commands.insert_resource(TournamentSetup::default());
+    // Keeps the H that opened the screen out of the first name.
+    characters.clear();
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::TournamentSetup),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(12.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Tournament",
+                text_style(&asset_server, 40.),
+            ));
+            parent.spawn((
+                SetupText,
+                TextBundle::from_section("", text_style(&asset_server, 22.)),
+            ));
+            parent.spawn(TextBundle::from_section(
+                "Type a name, Enter: add player   Backspace: remove\nLeft/Right: runs   Enter: start   Esc: back",
+                text_style(&asset_server, 16.),
+            ));
+        });
+}
+
+fn remove_setup_system(mut commands: Commands) {
+    commands.remove_resource::<TournamentSetup>();
+}
+
+fn setup_input_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    mut characters: EventReader<ReceivedCharacter>,
+    mut setup: ResMut<TournamentSetup>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.just_pressed(KeyCode::Escape) {
+        next_state.set(GameState::Menu);
+        return;
+    }
+
+    let room = setup.names.len() < MAX_PLAYERS;
+    for character in characters.iter() {
+        let valid = character.char.is_alphanumeric() || character.char == ' ';
+        if room && valid && setup.typing.chars().count() < MAX_NAME_LEN {
+            setup.typing.push(character.char);
+        }
+    }
+
+    if keyboard_input.just_pressed(KeyCode::Back) {
+        if setup.typing.pop().is_none() {
+            setup.names.pop();
+        }
+    } else if keyboard_input.just_pressed(KeyCode::Right) {
+        setup.runs = (setup.runs + 1).min(RUNS_RANGE.1);
+    } else if keyboard_input.just_pressed(KeyCode::Left) {
+        setup.runs = setup.runs.saturating_sub(1).max(RUNS_RANGE.0);
+    } else if keyboard_input.just_pressed(KeyCode::Return) {
+        let name = setup.typing.trim().to_string();
+        if !name.is_empty() {
+            let taken = setup
+                .names
+                .iter()
+                .any(|existing| existing.eq_ignore_ascii_case(&name));
+            if !taken {
+                setup.names.push(name);
+                setup.typing.clear();
+            }
+        } else if setup.names.len() >= MIN_PLAYERS {
+            commands.insert_resource(Tournament {
+                entrants: setup
+                    .names
+                    .iter()
+                    .map(|name| Entrant {
+                        name: name.clone(),
+                        scores: Vec::new(),
+                    })
+                    .collect(),
+                runs: setup.runs,
+            });
+            next_state.set(GameState::InGame);
+        }
+    }
+}
+
+fn update_setup_ui_system(
+    setup: Res<TournamentSetup>,
+    mut query: Query<&mut Text, With<SetupText>>,
+) {
+    if !setup.is_changed() {
+        return;
+    }
+
+    let mut lines = setup
+        .names
+        .iter()
+        .enumerate()
+        .map(|(index, name)| format!("{}. {name}", index + 1))
+        .collect::<Vec<_>>();
+    if setup.names.len() < MAX_PLAYERS {
+        lines.push(format!("{}. {}_", setup.names.len() + 1, setup.typing));
+    }
+    lines.push(String::new());
+    lines.push(format!("Runs each: < {} >", setup.runs));
+
+    for mut text in query.iter_mut() {
+        text.sections[0].value = lines.join("\n");
+    }
+}
+
+fn spawn_turn_label_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    tournament: Res<Tournament>,
+) {
+    commands.spawn((
+        DespawnOnExit(GameState::InGame),
+        TextBundle::from_section(tournament.describe_turn(), text_style(&asset_server, 18.))
+            .with_style(Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(70.),
+                    left: Val::Px(10.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            }),
+    ));
+}
+
+fn record_score_system(score: Res<Score>, mut tournament: ResMut<Tournament>) {
+    tournament.record(score.0);
+}
+
+fn spawn_standings_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    tournament: Res<Tournament>,
+) {
+    let mut lines = tournament
+        .standings()
+        .iter()
+        .enumerate()
+        .map(|(place, entrant)| {
+            format!(
+                "{}. {}  best {}  total {}",
+                place + 1,
+                entrant.name,
+                entrant.best(),
+                entrant.total()
+            )
+        })
+        .collect::<Vec<_>>();
+    lines.push(if tournament.finished() {
+        "Last run done!".to_string()
+    } else {
+        format!("Next: {}", tournament.describe_turn())
+    });
+
+    commands.spawn((
+        DespawnOnExit(GameState::GameOver),
+        TextBundle::from_section(lines.join("\n"), text_style(&asset_server, 16.)).with_style(
+            Style {
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    top: Val::Px(70.),
+                    left: Val::Px(10.),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ),
+    ));
+}
+
+/// Sends a finished tournament on to the podium, and otherwise reminds whose
+/// turn it is.
+fn tournament_menu_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    tournament: Res<Tournament>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if tournament.finished() {
+        next_state.set(GameState::Podium);
+        return;
+    }
+
+    commands
+        .spawn((
+            TournamentLabel,
+            DespawnOnExit(GameState::Menu),
+            NodeBundle {
+                style: Style {
+                    size: Size::width(Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::top(Val::Px(250.)),
+                    justify_content: JustifyContent::Center,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                format!(
+                    "Tournament: {}\nX: quit tournament",
+                    tournament.describe_turn()
+                ),
+                TextStyle {
+                    color: Color::YELLOW,
+                    ..text_style(&asset_server, 20.)
+                },
+            ));
+        });
+}
+
+fn quit_tournament_system(
+    mut commands: Commands,
+    keyboard_input: Res<Input<KeyCode>>,
+    labels: Query<Entity, With<TournamentLabel>>,
+) {
+    if !keyboard_input.just_pressed(KeyCode::X) {
+        return;
+    }
+
+    commands.remove_resource::<Tournament>();
+    for entity in labels.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+fn spawn_podium_ui_system(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    tournament: Res<Tournament>,
+) {
+    let standings = tournament.standings();
+    // Second place stands to the left of the winner and third to the right.
+    let order = [1, 0, 2];
+
+    commands
+        .spawn((
+            DespawnOnExit(GameState::Podium),
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    flex_direction: FlexDirection::Column,
+                    justify_content: JustifyContent::Center,
+                    align_items: AlignItems::Center,
+                    gap: Size::all(Val::Px(16.)),
+                    ..Default::default()
+                },
+                background_color: Color::rgba(0., 0., 0., 0.6).into(),
+                ..Default::default()
+            },
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle::from_section(
+                "Podium",
+                text_style(&asset_server, 40.),
+            ));
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        align_items: AlignItems::FlexEnd,
+                        gap: Size::all(Val::Px(8.)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|podium| {
+                    for place in order {
+                        let Some(entrant) = standings.get(place) else {
+                            continue;
+                        };
+                        let (height, color) = PODIUM[place];
+                        podium
+                            .spawn(NodeBundle {
+                                style: Style {
+                                    flex_direction: FlexDirection::Column,
+                                    align_items: AlignItems::Center,
+                                    gap: Size::all(Val::Px(6.)),
+                                    ..Default::default()
+                                },
+                                ..Default::default()
+                            })
+                            .with_children(|column| {
+                                column.spawn(TextBundle::from_section(
+                                    format!("{}\n{}", entrant.name, entrant.best()),
+                                    text_style(&asset_server, 20.),
+                                ));
+                                column.spawn(NodeBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Px(90.), Val::Px(height)),
+                                        justify_content: JustifyContent::Center,
+                                        ..Default::default()
+                                    },
+                                    background_color: color.into(),
+                                    ..Default::default()
+                                });
+                            });
+                    }
+                });
+            for (place, entrant) in standings.iter().enumerate().skip(PODIUM.len()) {
+                parent.spawn(TextBundle::from_section(
+                    format!("{}. {}  {}", place + 1, entrant.name, entrant.best()),
+                    text_style(&asset_server, 18.),
+                ));
+            }
+            parent.spawn(TextBundle::from_section(
+                "Space: menu",
+                text_style(&asset_server, 16.),
+            ));
+        });
+}
+
+fn podium_input_system(
+    keyboard_input: Res<Input<KeyCode>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    if keyboard_input.any_just_pressed([KeyCode::Space, KeyCode::Return, KeyCode::Escape]) {
+        next_state.set(GameState::Menu);
+    }
+}
+
+fn end_tournament_system(mut commands: Commands) {
+    commands.remove_resource::<Tournament>();
+}
+
+pub struct TournamentPlugin;
+
+impl Plugin for TournamentPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(
+            open_tournament_system
+                .run_if(in_state(GameState::Menu))
+                .run_if(not(resource_exists::<Tournament>())),
+        )
+        .add_system(spawn_setup_ui_system.in_schedule(OnEnter(GameState::TournamentSetup)))
+        .add_system(remove_setup_system.in_schedule(OnExit(GameState::TournamentSetup)))
+        .add_systems(
+            (setup_input_system, update_setup_ui_system)
+                .chain()
+                .in_set(OnUpdate(GameState::TournamentSetup)),
+        )
+        .add_system(
+            spawn_turn_label_system
+                .run_if(resource_exists::<Tournament>())
+                .run_if(endless_run)
+                .in_schedule(OnEnter(GameState::InGame)),
+        )
+        .add_systems(
+            (record_score_system, spawn_standings_system)
+                .chain()
+                .distributive_run_if(resource_exists::<Tournament>())
+                .distributive_run_if(endless_run)
+                .in_schedule(OnEnter(GameState::GameOver)),
+        )
+        .add_system(
+            tournament_menu_system
+                .run_if(resource_exists::<Tournament>())
+                .in_schedule(OnEnter(GameState::Menu)),
+        )
+        .add_system(
+            quit_tournament_system
+                .run_if(resource_exists::<Tournament>())
+                .in_set(OnUpdate(GameState::Menu)),
+        )
+        .add_system(spawn_podium_ui_system.in_schedule(OnEnter(GameState::Podium)))
+        .add_system(podium_input_system.in_set(OnUpdate(GameState::Podium)))
+        .add_system(end_tournament_system.in_schedule(OnExit(GameState::Podium)));
+    }
+}
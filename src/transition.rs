@@ -0,0 +1,119 @@
+use bevy::prelude::*;
+
+use crate::{
+    tween::{Ease, Tween, TweenCompleted, UiColor, UiLeft},
+    GameState,
+};
+
+const FADE_SECS: f32 = 0.3;
+const SLIDE_SECS: f32 = 0.25;
+/// Death flashes white like the original game, rather than fading from black.
+const FLASH_SECS: f32 = 0.2;
+const SLIDE_COLOR: Color = Color::rgb(0.2, 0.3, 0.45);
+
+/// Present while a screen is being revealed, holding the overlay that covers it.
+#[derive(Resource)]
+pub struct Transition(Entity);
+
+/// Covers the screen being entered and animates out of the way.
+#[derive(Component)]
+struct TransitionOverlay;
+
+/// Whether no screen is being revealed. A run doesn't start until it's fully
+/// shown.
+pub fn transition_done(transition: Option<Res<Transition>>) -> bool {
+    transition.is_none()
+}
+
+fn spawn_overlay(
+    commands: &mut Commands,
+    overlays: &Query<Entity, With<TransitionOverlay>>,
+    color: Color,
+) -> Entity {
+    for entity in overlays.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let overlay = commands
+        .spawn((
+            TransitionOverlay,
+            NodeBundle {
+                style: Style {
+                    size: Size::new(Val::Percent(100.), Val::Percent(100.)),
+                    position_type: PositionType::Absolute,
+                    position: UiRect::left(Val::Percent(0.)),
+                    ..Default::default()
+                },
+                background_color: color.into(),
+                // Under toasts and the console.
+                z_index: ZIndex::Global(5),
+                ..Default::default()
+            },
+        ))
+        .id();
+    commands.insert_resource(Transition(overlay));
+    overlay
+}
+
+fn fade_in(
+    color: Color,
+    secs: f32,
+) -> impl FnMut(Commands, Query<Entity, With<TransitionOverlay>>) {
+    move |mut commands, overlays| {
+        let overlay = spawn_overlay(&mut commands, &overlays, color);
+        commands.entity(overlay).insert(
+            Tween::new(
+                UiColor {
+                    start: color,
+                    end: color.with_a(0.),
+                },
+                secs,
+                Ease::QuadIn,
+            )
+            .despawning(),
+        );
+    }
+}
+
+/// Slides a panel off to the left, uncovering the screen behind it.
+fn slide_in_system(mut commands: Commands, overlays: Query<Entity, With<TransitionOverlay>>) {
+    let overlay = spawn_overlay(&mut commands, &overlays, SLIDE_COLOR);
+    commands.entity(overlay).insert(
+        Tween::new(
+            UiLeft {
+                start: 0.,
+                end: -100.,
+            },
+            SLIDE_SECS,
+            Ease::QuadOut,
+        )
+        .despawning(),
+    );
+}
+
+fn end_transition_system(
+    mut commands: Commands,
+    transition: Option<Res<Transition>>,
+    mut completed: EventReader<TweenCompleted>,
+) {
+    let Some(transition) = transition else {
+        completed.clear();
+        return;
+    };
+    if completed.iter().any(|event| event.entity == transition.0) {
+        commands.remove_resource::<Transition>();
+    }
+}
+
+pub struct TransitionPlugin;
+
+impl Plugin for TransitionPlugin {
+    fn build(&self, app: &mut App) {
+        app.configure_set(OnUpdate(GameState::InGame).run_if(transition_done))
+            .add_system(fade_in(Color::BLACK, FADE_SECS).in_schedule(OnEnter(GameState::Menu)))
+            .add_system(fade_in(Color::BLACK, FADE_SECS).in_schedule(OnEnter(GameState::InGame)))
+            .add_system(fade_in(Color::WHITE, FLASH_SECS).in_schedule(OnEnter(GameState::GameOver)))
+            .add_system(slide_in_system.in_schedule(OnEnter(GameState::Settings)))
+            .add_system(end_transition_system);
+    }
+}
@@ -0,0 +1,197 @@
+use bevy::{prelude::*, transform::TransformSystem};
+
+/// How a tween's progress maps onto the way from start to end.
+#[derive(Clone, Copy, Debug)]
+pub enum Ease {
+    Linear,
+    /// Starts slow and speeds up.
+    QuadIn,
+    /// Starts fast and slows down.
+    QuadOut,
+}
+
+impl Ease {
+    fn apply(self, t: f32) -> f32 {
+        match self {
+            Ease::Linear => t,
+            Ease::QuadIn => t * t,
+            Ease::QuadOut => t * (2. - t),
+        }
+    }
+}
+
+/// One property of a component that a [`Tween`] animates.
+pub trait Lens: Send + Sync + 'static {
+    type Target: Component;
+
+    /// Sets the property `t` of the way from start to end, after easing.
+    fn apply(&self, target: &mut Self::Target, t: f32);
+}
+
+pub struct Translation {
+    pub start: Vec3,
+    pub end: Vec3,
+}
+
+impl Lens for Translation {
+    type Target = Transform;
+
+    fn apply(&self, target: &mut Transform, t: f32) {
+        target.translation = self.start.lerp(self.end, t);
+    }
+}
+
+/// Scales about `pivot`, a point relative to the anchor in unscaled sprite
+/// pixels, which stays where it is while the entity keeps moving.
+pub struct Scale {
+    pub start: Vec3,
+    pub end: Vec3,
+    pub pivot: Vec3,
+}
+
+impl Lens for Scale {
+    type Target = Transform;
+
+    fn apply(&self, target: &mut Transform, t: f32) {
+        let pivot = target.translation + target.rotation * (self.pivot * target.scale);
+        target.scale = self.start.lerp(self.end, t);
+        target.translation = pivot - target.rotation * (self.pivot * target.scale);
+    }
+}
+
+pub struct Rotation {
+    pub start: Quat,
+    pub end: Quat,
+}
+
+impl Lens for Rotation {
+    type Target = Transform;
+
+    fn apply(&self, target: &mut Transform, t: f32) {
+        target.rotation = self.start.slerp(self.end, t);
+    }
+}
+
+pub struct SpriteColor {
+    pub start: Color,
+    pub end: Color,
+}
+
+impl Lens for SpriteColor {
+    type Target = Sprite;
+
+    fn apply(&self, target: &mut Sprite, t: f32) {
+        target.color = lerp_color(self.start, self.end, t);
+    }
+}
+
+/// Colour of a UI node's background.
+pub struct UiColor {
+    pub start: Color,
+    pub end: Color,
+}
+
+impl Lens for UiColor {
+    type Target = BackgroundColor;
+
+    fn apply(&self, target: &mut BackgroundColor, t: f32) {
+        target.0 = lerp_color(self.start, self.end, t);
+    }
+}
+
+/// Left edge of an absolutely positioned UI node, in percent of its parent's
+/// width. Layout overwrites the [`Transform`] of UI nodes, so they slide this
+/// way instead.
+pub struct UiLeft {
+    pub start: f32,
+    pub end: f32,
+}
+
+impl Lens for UiLeft {
+    type Target = Style;
+
+    fn apply(&self, target: &mut Style, t: f32) {
+        target.position.left = Val::Percent(self.start + (self.end - self.start) * t);
+    }
+}
+
+fn lerp_color(start: Color, end: Color, t: f32) -> Color {
+    let start = Vec4::from(start.as_rgba_f32());
+    let end = Vec4::from(end.as_rgba_f32());
+    start.lerp(end, t).into()
+}
+
+/// Animates one property of an entity over a fixed time, then removes itself and
+/// sends a [`TweenCompleted`].
+#[derive(Component)]
+pub struct Tween<L: Lens> {
+    lens: L,
+    timer: Timer,
+    ease: Ease,
+    despawn: bool,
+}
+
+impl<L: Lens> Tween<L> {
+    pub fn new(lens: L, secs: f32, ease: Ease) -> Self {
+        Self {
+            lens,
+            timer: Timer::from_seconds(secs, TimerMode::Once),
+            ease,
+            despawn: false,
+        }
+    }
+
+    /// Despawns the entity once finished, rather than only removing the tween.
+    pub fn despawning(mut self) -> Self {
+        self.despawn = true;
+        self
+    }
+}
+
+/// Sent when a [`Tween`] on `entity` has finished.
+pub struct TweenCompleted {
+    pub entity: Entity,
+}
+
+fn tween_system<L: Lens>(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut completed: EventWriter<TweenCompleted>,
+    mut query: Query<(Entity, &mut Tween<L>, &mut L::Target)>,
+) {
+    for (entity, mut tween, mut target) in query.iter_mut() {
+        tween.timer.tick(time.delta());
+        let t = tween.ease.apply(tween.timer.percent());
+        tween.lens.apply(&mut target, t);
+
+        if tween.timer.finished() {
+            if tween.despawn {
+                commands.entity(entity).despawn_recursive();
+            } else {
+                commands.entity(entity).remove::<Tween<L>>();
+            }
+            completed.send(TweenCompleted { entity });
+        }
+    }
+}
+
+/// Runs tweens after everything else has had its say about the frame, so they
+/// win over systems setting the same properties.
+pub struct TweenPlugin;
+
+impl Plugin for TweenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<TweenCompleted>().add_systems(
+            (
+                tween_system::<Translation>,
+                tween_system::<Scale>,
+                tween_system::<Rotation>,
+                tween_system::<SpriteColor>,
+                tween_system::<UiColor>,
+                tween_system::<UiLeft>,
+            )
+                .in_base_set(CoreSet::PostUpdate)
+                .before(TransformSystem::TransformPropagate),
+        );
+    }
+}
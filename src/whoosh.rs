@@ -0,0 +1,71 @@
+use bevy::prelude::*;
+
+use crate::{
+    audio::{AudioManager, Sound},
+    pipe_themes::PipeTheme,
+    score::ScorePipe,
+    GameState, Player,
+};
+
+/// Pixels per unit of audio space, where the ears are one unit apart. Small
+/// enough that a pipe on either side of the bird is clearly off to that side.
+const PIXELS_PER_UNIT: f32 = 60.;
+
+/// The whoosh starts when a pipe gets this close, so it peaks as it passes.
+const START_DISTANCE: f32 = 120.;
+
+/// A pipe whose whoosh is playing, or has played.
+#[derive(Component)]
+pub struct Whoosh(Handle<SpatialAudioSink>);
+
+/// Where something at `offset` from the bird is heard, with the bird's ears
+/// at the origin.
+fn audio_position(offset: Vec3) -> Vec3 {
+    Vec3::new(offset.x, 0., 0.) / PIXELS_PER_UNIT
+}
+
+fn start_whoosh_system(
+    mut commands: Commands,
+    audio: AudioManager,
+    theme: Res<PipeTheme>,
+    player: Query<&Transform, With<Player>>,
+    pipes: Query<(Entity, &Transform, Option<&Whoosh>), With<ScorePipe>>,
+) {
+    let player = player.single().translation;
+
+    for (entity, transform, whoosh) in pipes.iter() {
+        let centre = transform.translation + Vec3::X * theme.width() / 2.;
+        if whoosh.is_none() && centre.x - player.x < START_DISTANCE {
+            let sink =
+                audio.play_spatial(Sound::Whoosh, Vec3::ZERO, audio_position(centre - player));
+            commands.entity(entity).insert(Whoosh(sink));
+        }
+    }
+}
+
+/// Pans each whoosh from right to left as its pipe goes by.
+fn pan_whoosh_system(
+    sinks: Res<Assets<SpatialAudioSink>>,
+    theme: Res<PipeTheme>,
+    player: Query<&Transform, With<Player>>,
+    pipes: Query<(&Transform, &Whoosh)>,
+) {
+    let player = player.single().translation;
+
+    for (transform, whoosh) in pipes.iter() {
+        if let Some(sink) = sinks.get(&whoosh.0) {
+            let centre = transform.translation + Vec3::X * theme.width() / 2.;
+            sink.set_emitter_position(audio_position(centre - player));
+        }
+    }
+}
+
+pub struct WhooshPlugin;
+
+impl Plugin for WhooshPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (start_whoosh_system, pan_whoosh_system).in_set(OnUpdate(GameState::InGame)),
+        );
+    }
+}
@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use bevy::{
+    audio::AudioPlugin, input::InputPlugin, prelude::*, render::camera::CameraProjectionPlugin,
+    sprite::SpritePlugin, text::TextPlugin, time::TimeUpdateStrategy, ui::UiPlugin, utils::Instant,
+};
+use flappy_core::{score::Score, GameConfig, GamePlugin, GameState};
+
+/// The game on `MinimalPlugins`, with the bot flying.
+fn headless_app(seed: u64) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(TransformPlugin)
+        .add_plugin(HierarchyPlugin)
+        .add_plugin(InputPlugin)
+        .add_plugin(WindowPlugin {
+            primary_window: None,
+            ..Default::default()
+        })
+        .add_plugin(AssetPlugin::default())
+        .add_asset::<Shader>()
+        .add_asset::<Mesh>()
+        // Configures the camera set `UiPlugin` orders itself against.
+        .add_plugin(CameraProjectionPlugin::<OrthographicProjection>::default())
+        .add_plugin(ImagePlugin::default())
+        .add_plugin(SpritePlugin)
+        .add_plugin(TextPlugin)
+        .add_plugin(UiPlugin)
+        .add_plugin(AudioPlugin)
+        .add_plugin(GamePlugin {
+            config: GameConfig {
+                seed: Some(seed),
+                bot: true,
+                ..Default::default()
+            },
+        });
+    app
+}
+
+/// Runs one frame a 60th of a second after the last, however long it actually
+/// took.
+fn step(app: &mut App) {
+    let last = app.world.resource::<Time>().last_update();
+    let now = last.unwrap_or_else(Instant::now) + Duration::from_secs_f64(1. / 60.);
+    app.insert_resource(TimeUpdateStrategy::ManualInstant(now));
+    app.update();
+}
+
+#[test]
+fn bot_scores_headless() {
+    let mut app = headless_app(1);
+    app.update();
+    app.world
+        .resource_mut::<NextState<GameState>>()
+        .set(GameState::InGame);
+    let scored = (0..60 * 20).any(|_| {
+        step(&mut app);
+        app.world.resource::<Score>().0 > 0
+    });
+    assert!(scored, "the bot didn't score in 20 seconds");
+}